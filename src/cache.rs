@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: MIT
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::task::{DateTimeUtc, Priority, State, Task as TaskTrait, due_group};
+use serde::{Deserialize, Serialize};
+use sha256::digest;
+use std::any::Any;
+use std::error::Error;
+use std::path::PathBuf;
+use tokio::fs;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Project {
+    id: String,
+    name: String,
+    provider: String,
+    description: String,
+    parent_id: Option<String>,
+    is_inbox: bool,
+    is_favorite: bool,
+}
+
+impl Project {
+    fn from_project(p: &dyn ProjectTrait) -> Self {
+        Self {
+            id: p.id(),
+            name: p.name(),
+            provider: p.provider(),
+            description: p.description(),
+            parent_id: p.parent_id(),
+            is_inbox: p.is_inbox(),
+            is_favorite: p.is_favorite(),
+        }
+    }
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+    fn description(&self) -> String {
+        self.description.to_string()
+    }
+    fn parent_id(&self) -> Option<String> {
+        self.parent_id.clone()
+    }
+    fn is_inbox(&self) -> bool {
+        self.is_inbox
+    }
+    fn is_favorite(&self) -> bool {
+        self.is_favorite
+    }
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
+
+// A snapshot of a provider's task that is shown until the provider answers.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Task {
+    id: String,
+    text: String,
+    description: Option<String>,
+    priority: Priority,
+    state: State,
+    created_at: Option<DateTimeUtc>,
+    updated_at: Option<DateTimeUtc>,
+    completed_at: Option<DateTimeUtc>,
+    due: Option<DateTimeUtc>,
+    place: String,
+    labels: Vec<String>,
+    parent_id: Option<String>,
+    #[serde(default)]
+    recurrence: Option<String>,
+    provider: String,
+    project_id: Option<String>,
+
+    #[serde(skip)]
+    project: Option<Project>,
+}
+
+impl Task {
+    fn from_task(t: &dyn TaskTrait) -> Self {
+        Self {
+            id: t.id(),
+            text: t.text(),
+            description: t.description(),
+            priority: t.priority(),
+            state: t.state(),
+            created_at: t.created_at(),
+            updated_at: t.updated_at(),
+            completed_at: t.completed_at(),
+            due: t.due(),
+            place: t.place(),
+            labels: t.labels(),
+            parent_id: t.parent_id(),
+            recurrence: t.recurrence(),
+            provider: t.provider(),
+            project_id: t.project().map(|p| p.id()),
+            project: None,
+        }
+    }
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+    fn text(&self) -> String {
+        self.text.to_string()
+    }
+    fn description(&self) -> Option<String> {
+        self.description.clone()
+    }
+    fn priority(&self) -> Priority {
+        self.priority.clone()
+    }
+    fn state(&self) -> State {
+        self.state.clone()
+    }
+    fn created_at(&self) -> Option<DateTimeUtc> {
+        self.created_at
+    }
+    fn updated_at(&self) -> Option<DateTimeUtc> {
+        self.updated_at
+    }
+    fn completed_at(&self) -> Option<DateTimeUtc> {
+        self.completed_at
+    }
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.due
+    }
+    fn place(&self) -> String {
+        self.place.to_string()
+    }
+    fn labels(&self) -> Vec<String> {
+        self.labels.clone()
+    }
+    fn parent_id(&self) -> Option<String> {
+        self.parent_id.clone()
+    }
+    fn recurrence(&self) -> Option<String> {
+        self.recurrence.clone()
+    }
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        self.project.as_ref().map(|p| p.clone_boxed())
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    updated_at: DateTimeUtc,
+    projects: Vec<Project>,
+    tasks: Vec<Task>,
+}
+
+pub struct Snapshot {
+    pub updated_at: DateTimeUtc,
+    pub tasks: Vec<Box<dyn TaskTrait>>,
+}
+
+#[derive(Clone)]
+pub struct Cache {
+    dir: Option<PathBuf>,
+}
+
+impl Cache {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+
+    fn file_path(&self, provider: &str) -> Option<PathBuf> {
+        let file_name = provider
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+            .collect::<String>();
+        // Names like "Work/Jira" and "Work Jira" are the same after the replacement, the hash tells them apart
+        let hash = digest(provider);
+        self.dir
+            .as_ref()
+            .map(|d| d.join(format!("{file_name}-{}.json", &hash[..8])))
+    }
+
+    pub async fn save(&self, provider: &str, tasks: &[Box<dyn TaskTrait>]) -> Result<(), Box<dyn Error>> {
+        let path = match self.file_path(provider) {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+
+        let mut projects: Vec<Project> = Vec::new();
+        for t in tasks {
+            if let Some(p) = t.project() {
+                if !projects.iter().any(|pp| pp.id == p.id()) {
+                    projects.push(Project::from_project(p.as_ref()));
+                }
+            }
+        }
+
+        let entry = Entry {
+            updated_at: chrono::Utc::now(),
+            projects,
+            tasks: tasks.iter().map(|t| Task::from_task(t.as_ref())).collect(),
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).await?;
+        }
+        fs::write(path, serde_json::to_string(&entry)?).await?;
+
+        Ok(())
+    }
+
+    // Returns the tasks from the last successful loading which satisfy the filter
+    pub async fn load(&self, provider: &str, f: &filter::Filter) -> Option<Snapshot> {
+        let path = self.file_path(provider)?;
+        let content = fs::read_to_string(path).await.ok()?;
+        let entry = serde_json::from_str::<Entry>(&content).ok()?;
+
+        let tasks = entry
+            .tasks
+            .into_iter()
+            .filter(|t| f.states.contains(&filter::FilterState::from(&t.state)) && f.due.contains(&due_group(t)))
+            .map(|mut t| {
+                t.project = t
+                    .project_id
+                    .as_ref()
+                    .and_then(|id| entry.projects.iter().find(|p| p.id == *id).cloned());
+                Box::new(t) as Box<dyn TaskTrait>
+            })
+            .collect();
+
+        Some(Snapshot {
+            updated_at: entry.updated_at,
+            tasks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn save_and_load_test() {
+        let dir = std::env::temp_dir().join(format!("tatuin-cache-test-{}", std::process::id()));
+        let c = Cache::new(Some(dir.clone()));
+
+        let project = Project {
+            id: "p1".to_string(),
+            name: "Project".to_string(),
+            provider: "My provider".to_string(),
+            description: String::new(),
+            parent_id: None,
+            is_inbox: false,
+            is_favorite: false,
+        };
+        let task = |id: &str, state: State| Task {
+            id: id.to_string(),
+            text: format!("Task {id}"),
+            description: None,
+            priority: Priority::High,
+            state,
+            created_at: None,
+            updated_at: None,
+            completed_at: None,
+            due: None,
+            place: String::new(),
+            labels: vec!["label".to_string()],
+            parent_id: None,
+            recurrence: None,
+            provider: "My provider".to_string(),
+            project_id: Some("p1".to_string()),
+            project: Some(project.clone()),
+        };
+        let tasks: Vec<Box<dyn TaskTrait>> = vec![
+            Box::new(task("1", State::Uncompleted)),
+            Box::new(task("2", State::Completed)),
+        ];
+
+        c.save("My provider", &tasks).await.unwrap();
+
+        let f = filter::Filter {
+            states: vec![filter::FilterState::Uncompleted],
+            due: vec![filter::Due::NoDate],
+            labels: Vec::new(),
+        };
+        let snapshot = c.load("My provider", &f).await.unwrap();
+        assert_eq!(snapshot.tasks.len(), 1);
+        let t = &snapshot.tasks[0];
+        assert_eq!(t.id(), "1");
+        assert_eq!(t.priority(), Priority::High);
+        assert_eq!(t.labels(), vec!["label".to_string()]);
+        assert_eq!(t.project().map(|p| p.name()), Some("Project".to_string()));
+
+        // An empty due filter matches no tasks as in the providers
+        let no_due = filter::Filter {
+            due: Vec::new(),
+            ..f.clone()
+        };
+        assert!(c.load("My provider", &no_due).await.unwrap().tasks.is_empty());
+
+        assert!(c.load("Another provider", &f).await.is_none());
+        assert!(c.load("My/provider", &f).await.is_none());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
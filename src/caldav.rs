@@ -0,0 +1,634 @@
+// SPDX-License-Identifier: MIT
+
+// A provider for VTODO tasks stored on a CalDAV server (Nextcloud, Radicale, etc.).
+// Calendars are shown as projects.
+
+mod client;
+mod ical;
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::provider::{DuePatch, NewTask, PatchError, Provider as ProviderTrait, TaskPatch};
+use crate::task::{DateTimeUtc, Priority, State, Task as TaskTrait, due_group};
+use async_trait::async_trait;
+use chrono::Utc;
+use ratatui::style::Color;
+use std::any::Any;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "CalDAV";
+
+#[derive(Clone, Debug)]
+pub struct Project {
+    calendar: client::Calendar,
+    provider: String,
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.calendar.href.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.calendar.name.to_string()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        self.calendar.description.to_string()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        None
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Task {
+    href: String,
+    etag: String,
+    url: String,
+    ical: ical::Calendar,
+    provider: String,
+    project: Project,
+}
+
+impl Task {
+    fn text_property(&self, name: &str) -> Option<String> {
+        self.ical.property(name).map(|p| ical::unescape_text(&p.value))
+    }
+
+    fn date_property(&self, name: &str) -> Option<DateTimeUtc> {
+        self.ical.property(name).and_then(|p| ical::parse_datetime(&p))
+    }
+}
+
+fn priority_from_ical(v: &str) -> Priority {
+    match v.trim().parse::<u8>().unwrap_or_default() {
+        1 => Priority::Highest,
+        2..=4 => Priority::High,
+        5 => Priority::Medium,
+        6..=8 => Priority::Low,
+        9 => Priority::Lowest,
+        _ => Priority::Normal,
+    }
+}
+
+fn priority_to_ical(p: &Priority) -> Option<&'static str> {
+    match p {
+        Priority::Highest => Some("1"),
+        Priority::High => Some("3"),
+        Priority::Medium => Some("5"),
+        Priority::Low => Some("7"),
+        Priority::Lowest => Some("9"),
+        Priority::Normal => None,
+    }
+}
+
+fn state_from_ical(v: Option<&str>) -> State {
+    match v.map(|v| v.trim().to_uppercase()).as_deref() {
+        None | Some("NEEDS-ACTION") => State::Uncompleted,
+        Some("IN-PROCESS") => State::InProgress,
+        Some("COMPLETED") => State::Completed,
+        Some("CANCELLED") => State::Unknown('-'),
+        Some(_) => State::Unknown('?'),
+    }
+}
+
+fn state_to_ical(s: &State) -> &'static str {
+    match s {
+        State::Uncompleted => "NEEDS-ACTION",
+        State::InProgress => "IN-PROCESS",
+        State::Completed => "COMPLETED",
+        State::Unknown(_) => "CANCELLED",
+    }
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        self.text_property("UID").unwrap_or_else(|| self.href.to_string())
+    }
+
+    fn text(&self) -> String {
+        self.text_property("SUMMARY").unwrap_or_default()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.text_property("DESCRIPTION").filter(|d| !d.is_empty())
+    }
+
+    fn priority(&self) -> Priority {
+        self.ical
+            .property("PRIORITY")
+            .map(|p| priority_from_ical(&p.value))
+            .unwrap_or_default()
+    }
+
+    fn state(&self) -> State {
+        state_from_ical(self.ical.property("STATUS").map(|p| p.value).as_deref())
+    }
+
+    fn created_at(&self) -> Option<DateTimeUtc> {
+        self.date_property("CREATED")
+    }
+
+    fn updated_at(&self) -> Option<DateTimeUtc> {
+        self.date_property("LAST-MODIFIED")
+    }
+
+    fn completed_at(&self) -> Option<DateTimeUtc> {
+        self.date_property("COMPLETED")
+    }
+
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.date_property("DUE")
+    }
+
+    fn place(&self) -> String {
+        self.url.to_string()
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.ical
+            .properties("CATEGORIES")
+            .iter()
+            .flat_map(|p| ical::split_list(&p.value))
+            .collect()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.ical
+            .properties("RELATED-TO")
+            .into_iter()
+            .find(|p| p.param("RELTYPE").is_none_or(|t| t.eq_ignore_ascii_case("PARENT")))
+            .map(|p| p.value.trim().to_string())
+    }
+
+    fn recurrence(&self) -> Option<String> {
+        self.ical.property("RRULE").map(|p| p.value)
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        Some(self.project.clone_boxed())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+fn touch(c: &mut ical::Calendar) {
+    let now = ical::format_datetime(&Utc::now());
+    c.set_property("LAST-MODIFIED", &[], Some(&now));
+    c.set_property("DTSTAMP", &[], Some(&now));
+}
+
+fn change_state(c: &mut ical::Calendar, state: &State) {
+    c.set_property("STATUS", &[], Some(state_to_ical(state)));
+    match state {
+        State::Completed => {
+            c.set_property("COMPLETED", &[], Some(&ical::format_datetime(&Utc::now())));
+            c.set_property("PERCENT-COMPLETE", &[], Some("100"));
+        }
+        State::Uncompleted => {
+            c.set_property("COMPLETED", &[], None);
+            c.set_property("PERCENT-COMPLETE", &[], None);
+        }
+        State::InProgress | State::Unknown(_) => c.set_property("COMPLETED", &[], None),
+    }
+    touch(c);
+}
+
+fn apply_changes(
+    c: &mut ical::Calendar,
+    text: Option<&str>,
+    description: Option<&str>,
+    due: Option<&DuePatch>,
+    priority: Option<&Priority>,
+) {
+    if let Some(text) = text {
+        c.set_property("SUMMARY", &[], Some(&ical::escape_text(text)));
+    }
+
+    if let Some(description) = description {
+        let value = ical::escape_text(description);
+        c.set_property("DESCRIPTION", &[], Some(value.as_str()).filter(|v| !v.is_empty()));
+    }
+
+    match due {
+        Some(DuePatch::NoDate) => c.set_property("DUE", &[], None),
+        Some(DuePatch::Date(d)) => c.set_property("DUE", &[("VALUE", "DATE")], Some(&ical::format_date(d))),
+        None => {}
+    }
+
+    if let Some(p) = priority {
+        c.set_property("PRIORITY", &[], priority_to_ical(p));
+    }
+
+    touch(c);
+}
+
+// Applies all the changes of the patch to the task at once, so only one request with the task's etag is needed
+fn patched_ical(t: &Task, patch: &TaskPatch) -> ical::Calendar {
+    let mut c = t.ical.clone();
+
+    if patch.has_edits() {
+        apply_changes(
+            &mut c,
+            patch.text.as_deref(),
+            patch.description.as_deref(),
+            patch.due.as_ref(),
+            patch.priority.as_ref(),
+        );
+    }
+
+    if let Some(s) = &patch.state {
+        change_state(&mut c, s);
+    }
+
+    c
+}
+
+fn new_todo(uid: &str, t: &NewTask) -> ical::Calendar {
+    let now = ical::format_datetime(&Utc::now());
+    let mut c = ical::Calendar::parse(
+        format!(
+            "BEGIN:VCALENDAR\nVERSION:2.0\nPRODID:-//Tatuin//EN\nBEGIN:VTODO\nUID:{uid}\nCREATED:{now}\nSTATUS:NEEDS-ACTION\nEND:VTODO\nEND:VCALENDAR\n"
+        )
+        .as_str(),
+    );
+
+    apply_changes(
+        &mut c,
+        Some(&t.text),
+        t.description.as_deref(),
+        t.due.map(DuePatch::Date).as_ref(),
+        Some(&t.priority),
+    );
+
+    c
+}
+
+pub struct Provider {
+    name: String,
+    color: Color,
+    client: client::Client,
+    projects: Vec<Project>,
+    tasks: Vec<Task>,
+}
+
+impl Provider {
+    pub fn new(name: &str, url: &str, username: &str, password: &str, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            color: *color,
+            client: client::Client::new(url, username, password),
+            projects: Vec::new(),
+            tasks: Vec::new(),
+        }
+    }
+
+    async fn load_projects(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.projects.is_empty() {
+            for c in self.client.calendars().await? {
+                self.projects.push(Project {
+                    calendar: c,
+                    provider: self.name.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn load_tasks(&mut self) -> Result<(), Box<dyn Error>> {
+        self.load_projects().await?;
+
+        let mut tasks = Vec::new();
+        for p in &self.projects {
+            for r in self.client.todos(&p.calendar.href).await? {
+                let ical = ical::Calendar::parse(&r.data);
+                if !ical.has_todo() {
+                    continue;
+                }
+
+                tasks.push(Task {
+                    url: self
+                        .client
+                        .url(&r.href)
+                        .map(|u| u.to_string())
+                        .unwrap_or(r.href.to_string()),
+                    href: r.href,
+                    etag: r.etag,
+                    ical,
+                    provider: self.name.to_string(),
+                    project: p.clone(),
+                });
+            }
+        }
+
+        self.tasks = tasks;
+        Ok(())
+    }
+}
+
+fn downcast(task: &dyn TaskTrait) -> &Task {
+    match task.as_any().downcast_ref::<Task>() {
+        Some(t) => t,
+        None => panic!("Wrong casting!"),
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        if self.tasks.is_empty() {
+            self.load_tasks().await?;
+        }
+
+        let project_id = project.map(|p| p.id());
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+
+        for t in &self.tasks {
+            if project_id.as_ref().is_some_and(|id| *id != t.project.id()) {
+                continue;
+            }
+
+            if f.states.contains(&filter::FilterState::from(&t.state())) && f.due.contains(&due_group(t)) {
+                result.push(Box::new(t.clone()));
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        self.load_projects().await?;
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for p in &self.projects {
+            result.push(Box::new(p.clone()));
+        }
+
+        Ok(result)
+    }
+
+    async fn create_task(&mut self, project: Option<Box<dyn ProjectTrait>>, t: &NewTask) -> Result<(), Box<dyn Error>> {
+        self.load_projects().await?;
+
+        let calendar = match project {
+            Some(p) => p.id(),
+            None => self
+                .projects
+                .first()
+                .map(|p| p.id())
+                .ok_or("there are no calendars to create the task in")?,
+        };
+
+        let uid = sha256::digest(format!("{}{}{}", self.name, calendar, Utc::now().to_rfc3339()));
+        let c = new_todo(&uid, t);
+        let href = format!("{}/{uid}.ics", calendar.trim_end_matches('/'));
+
+        // If-None-Match: * guarantees that an existing task won't be overwritten
+        self.client.put(&href, None, &c.to_string()).await?;
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let t = downcast(task);
+
+        let mut c = t.ical.clone();
+        change_state(&mut c, &state);
+
+        // The task is written only if it wasn't changed on the server since it was loaded
+        self.client.put(&t.href, Some(&t.etag), &c.to_string()).await?;
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn update_task(&mut self, patch: &TaskPatch) -> Result<(), Box<dyn Error>> {
+        let t = downcast(patch.task.as_ref());
+        let c = patched_ical(t, patch);
+
+        self.client.put(&t.href, Some(&t.etag), &c.to_string()).await?;
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        let t = downcast(task);
+        self.client.delete(&t.href, &t.etag).await?;
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn patch_tasks(&mut self, patches: &[TaskPatch]) -> Vec<PatchError> {
+        let mut errors = Vec::new();
+
+        for patch in patches {
+            let result = if patch.delete {
+                self.delete_task(patch.task.as_ref()).await
+            } else {
+                self.update_task(patch).await
+            };
+
+            if let Err(e) = result {
+                errors.push(PatchError {
+                    task: patch.task.clone_boxed(),
+                    error: e.to_string(),
+                });
+            }
+        }
+
+        errors
+    }
+
+    async fn reload(&mut self) {
+        self.projects.clear();
+        self.tasks.clear();
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(ical: &str) -> Task {
+        Task {
+            href: "/user/tasks/1.ics".to_string(),
+            etag: "\"1\"".to_string(),
+            url: "http://localhost:5232/user/tasks/1.ics".to_string(),
+            ical: ical::Calendar::parse(ical),
+            provider: "CalDAV".to_string(),
+            project: Project {
+                calendar: client::Calendar {
+                    href: "/user/tasks/".to_string(),
+                    name: "Tasks".to_string(),
+                    description: String::new(),
+                },
+                provider: "CalDAV".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn task_test() {
+        struct Case<'a> {
+            name: &'a str,
+            todo: &'a str,
+            state: State,
+            priority: Priority,
+            due: Option<&'a str>,
+            parent_id: Option<&'a str>,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "minimal task",
+                todo: "BEGIN:VTODO\nUID:1\nSUMMARY:Task\nEND:VTODO",
+                state: State::Uncompleted,
+                priority: Priority::Normal,
+                due: None,
+                parent_id: None,
+            },
+            Case {
+                name: "in progress task with high priority",
+                todo: "BEGIN:VTODO\nUID:1\nSUMMARY:Task\nSTATUS:IN-PROCESS\nPRIORITY:2\nDUE:20250127T100000Z\nEND:VTODO",
+                state: State::InProgress,
+                priority: Priority::High,
+                due: Some("2025-01-27T10:00:00+00:00"),
+                parent_id: None,
+            },
+            Case {
+                name: "completed subtask",
+                todo: "BEGIN:VTODO\nUID:2\nSUMMARY:Task\nSTATUS:COMPLETED\nPRIORITY:9\nRELATED-TO:1\nEND:VTODO",
+                state: State::Completed,
+                priority: Priority::Lowest,
+                due: None,
+                parent_id: Some("1"),
+            },
+            Case {
+                name: "cancelled task with a child relation",
+                todo: "BEGIN:VTODO\nUID:1\nSUMMARY:Task\nSTATUS:CANCELLED\nRELATED-TO;RELTYPE=CHILD:2\nEND:VTODO",
+                state: State::Unknown('-'),
+                priority: Priority::Normal,
+                due: None,
+                parent_id: None,
+            },
+        ];
+
+        for c in CASES {
+            let t = task(c.todo);
+            assert_eq!(t.state(), c.state, "Test '{}' was failed", c.name);
+            assert_eq!(t.priority(), c.priority, "Test '{}' was failed", c.name);
+            assert_eq!(
+                t.due().map(|d| d.to_rfc3339()).as_deref(),
+                c.due,
+                "Test '{}' was failed",
+                c.name
+            );
+            assert_eq!(t.parent_id().as_deref(), c.parent_id, "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn patched_ical_test() {
+        let t = task(
+            "BEGIN:VCALENDAR\nBEGIN:VTODO\nUID:1\nSUMMARY:Task\nPRIORITY:1\nDUE:20250127T100000Z\nEND:VTODO\nEND:VCALENDAR",
+        );
+
+        let mut patch = TaskPatch::new(t.clone_boxed());
+        patch.text = Some("New, text".to_string());
+        patch.due = Some(DuePatch::NoDate);
+        patch.priority = Some(Priority::Normal);
+        patch.state = Some(State::Completed);
+
+        let patched = task(&patched_ical(&t, &patch).to_string());
+        assert_eq!(patched.text(), "New, text");
+        assert_eq!(patched.due(), None);
+        assert_eq!(patched.priority(), Priority::Normal);
+        assert_eq!(patched.state(), State::Completed);
+        assert!(patched.completed_at().is_some());
+        assert!(patched.ical.to_string().contains("SUMMARY:New\\, text\r\n"));
+    }
+
+    // Runs against a real server, e.g. a local Radicale instance:
+    // radicale --storage-filesystem-folder=/tmp/radicale --auth-type=none
+    // TATUIN_CALDAV_TEST_URL=http://localhost:5232/user/tasks/ cargo test -- --ignored
+    // The calendar collection should exist and support VTODO.
+    #[tokio::test]
+    #[ignore]
+    async fn server_test() {
+        let url = std::env::var("TATUIN_CALDAV_TEST_URL").unwrap();
+        let mut p = Provider::new("CalDAV", &url, "user", "password", &Color::White);
+        let f = filter::Filter {
+            states: vec![filter::FilterState::Uncompleted, filter::FilterState::Completed],
+            due: vec![filter::Due::NoDate, filter::Due::Today],
+            labels: Vec::new(),
+        };
+
+        let text = format!("Tatuin test task {}", Utc::now().to_rfc3339());
+        p.create_task(
+            None,
+            &NewTask {
+                text: text.to_string(),
+                description: None,
+                due: Some(Utc::now()),
+                priority: Priority::High,
+            },
+        )
+        .await
+        .unwrap();
+
+        let tasks = p.tasks(None, &f).await.unwrap();
+        let t = tasks.iter().find(|t| t.text() == text).unwrap();
+        assert_eq!(t.priority(), Priority::High);
+
+        p.change_task_state(t.as_ref(), State::Completed).await.unwrap();
+        // The etag of the loaded task is outdated now
+        assert!(p.change_task_state(t.as_ref(), State::Uncompleted).await.is_err());
+
+        let tasks = p.tasks(None, &f).await.unwrap();
+        let t = tasks.iter().find(|t| t.text() == text).unwrap();
+        assert_eq!(t.state(), State::Completed);
+
+        p.delete_task(t.as_ref()).await.unwrap();
+    }
+}
@@ -0,0 +1,405 @@
+// SPDX-License-Identifier: MIT
+
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use std::error::Error;
+use url::Url;
+
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <d:resourcetype/>
+    <d:displayname/>
+    <d:current-user-principal/>
+    <c:calendar-home-set/>
+    <c:calendar-description/>
+    <c:supported-calendar-component-set/>
+  </d:prop>
+</d:propfind>"#;
+
+const TODOS_REPORT_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <d:getetag/>
+    <c:calendar-data/>
+  </d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR">
+      <c:comp-filter name="VTODO"/>
+    </c:comp-filter>
+  </c:filter>
+</c:calendar-query>"#;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calendar {
+    pub href: String,
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resource {
+    pub href: String,
+    pub etag: String,
+    pub data: String,
+}
+
+pub struct Client {
+    base_url: String,
+    username: String,
+    password: String,
+    client: reqwest::Client,
+}
+
+impl Client {
+    pub fn new(url: &str, username: &str, password: &str) -> Self {
+        let mut base_url = url.to_string();
+        if !base_url.ends_with('/') {
+            base_url.push('/');
+        }
+
+        Self {
+            base_url,
+            username: username.to_string(),
+            password: password.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    // Resolves the href (usually an absolute path) against the base url
+    pub fn url(&self, href: &str) -> Result<Url, Box<dyn Error>> {
+        let base = Url::parse(&self.base_url).map_err(|e| format!("wrong url '{}': {e}", self.base_url))?;
+        Ok(base.join(href)?)
+    }
+
+    fn request(&self, method: Method, href: &str) -> Result<reqwest::RequestBuilder, Box<dyn Error>> {
+        Ok(self
+            .client
+            .request(method, self.url(href)?)
+            .basic_auth(&self.username, Some(&self.password)))
+    }
+
+    async fn propfind(&self, href: &str, depth: &str) -> Result<String, Box<dyn Error>> {
+        let mut headers = HeaderMap::new();
+        headers.insert("Depth", depth.parse().unwrap());
+        headers.insert("Content-Type", "application/xml; charset=utf-8".parse().unwrap());
+
+        let req = self.request(Method::from_bytes(b"PROPFIND").unwrap(), href)?;
+        let resp = req.headers(headers).body(PROPFIND_BODY).send().await?;
+        check_status(resp.status())?;
+        Ok(resp.text().await?)
+    }
+
+    // Returns the calendars of the url or, if the url isn't a calendar collection, of the current user
+    pub async fn calendars(&self) -> Result<Vec<Calendar>, Box<dyn Error>> {
+        let xml = self.propfind(&self.base_url, "1").await?;
+        let calendars = parse_calendars(&xml)?;
+        if !calendars.is_empty() {
+            return Ok(calendars);
+        }
+
+        let principal = parse_href_property(&xml, "current-user-principal")?
+            .ok_or("the url doesn't contain calendars and the current user principal isn't found")?;
+        let xml = self.propfind(&principal, "0").await?;
+        let home = parse_href_property(&xml, "calendar-home-set")?.ok_or("the calendar home set isn't found")?;
+
+        let xml = self.propfind(&home, "1").await?;
+        parse_calendars(&xml)
+    }
+
+    pub async fn todos(&self, calendar_href: &str) -> Result<Vec<Resource>, Box<dyn Error>> {
+        let mut headers = HeaderMap::new();
+        headers.insert("Depth", "1".parse().unwrap());
+        headers.insert("Content-Type", "application/xml; charset=utf-8".parse().unwrap());
+
+        let req = self.request(Method::from_bytes(b"REPORT").unwrap(), calendar_href)?;
+        let resp = req.headers(headers).body(TODOS_REPORT_BODY).send().await?;
+        check_status(resp.status())?;
+        parse_resources(&resp.text().await?)
+    }
+
+    // Puts the resource only if it wasn't changed on the server since it was loaded.
+    // A resource without an etag is created only if it doesn't exist yet.
+    pub async fn put(&self, href: &str, etag: Option<&str>, data: &str) -> Result<(), Box<dyn Error>> {
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Type", "text/calendar; charset=utf-8".parse().unwrap());
+        match etag {
+            Some(etag) if !etag.is_empty() => {
+                headers.insert("If-Match", etag.parse()?);
+            }
+            Some(_) => {}
+            None => {
+                headers.insert("If-None-Match", "*".parse().unwrap());
+            }
+        }
+
+        let req = self.request(Method::PUT, href)?;
+        let resp = req.headers(headers).body(data.to_string()).send().await?;
+        check_status(resp.status())
+    }
+
+    pub async fn delete(&self, href: &str, etag: &str) -> Result<(), Box<dyn Error>> {
+        let mut headers = HeaderMap::new();
+        if !etag.is_empty() {
+            headers.insert("If-Match", etag.parse()?);
+        }
+
+        let req = self.request(Method::DELETE, href)?;
+        let resp = req.headers(headers).send().await?;
+        check_status(resp.status())
+    }
+}
+
+fn check_status(status: StatusCode) -> Result<(), Box<dyn Error>> {
+    match status {
+        s if s.is_success() => Ok(()),
+        StatusCode::UNAUTHORIZED => Err(Box::<dyn Error>::from("wrong username or password")),
+        StatusCode::PRECONDITION_FAILED => Err(Box::<dyn Error>::from(
+            "the task was changed on the server, reload the tasks and try again",
+        )),
+        s => Err(Box::<dyn Error>::from(format!("wrong status: {}", s.as_str()))),
+    }
+}
+
+fn child<'a, 'input>(n: roxmltree::Node<'a, 'input>, name: &str) -> Option<roxmltree::Node<'a, 'input>> {
+    n.children().find(|c| c.tag_name().name() == name)
+}
+
+// Returns the properties of the response which were found (the propstat status is 200)
+fn found_properties<'a, 'input>(
+    response: roxmltree::Node<'a, 'input>,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    response
+        .children()
+        .filter(|c| c.tag_name().name() == "propstat")
+        .filter(|ps| {
+            child(*ps, "status")
+                .and_then(|s| s.text())
+                .is_none_or(|s| s.contains(" 200 "))
+        })
+        .filter_map(|ps| child(ps, "prop"))
+        .flat_map(|p| p.children().filter(|c| c.is_element()))
+}
+
+fn responses<'a, 'input>(doc: &'a roxmltree::Document<'input>) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    doc.root_element()
+        .children()
+        .filter(|c| c.tag_name().name() == "response")
+}
+
+fn text(n: roxmltree::Node) -> String {
+    n.text().unwrap_or_default().trim().to_string()
+}
+
+fn parse_calendars(xml: &str) -> Result<Vec<Calendar>, Box<dyn Error>> {
+    let doc = roxmltree::Document::parse(xml)?;
+    let mut result = Vec::new();
+
+    for r in responses(&doc) {
+        let href = match child(r, "href") {
+            Some(h) => text(h),
+            None => continue,
+        };
+
+        let mut is_calendar = false;
+        let mut has_todos = true;
+        let mut name = String::new();
+        let mut description = String::new();
+
+        for p in found_properties(r) {
+            match p.tag_name().name() {
+                "resourcetype" => is_calendar = child(p, "calendar").is_some(),
+                "supported-calendar-component-set" => {
+                    has_todos = p
+                        .children()
+                        .filter(|c| c.tag_name().name() == "comp")
+                        .any(|c| c.attribute("name") == Some("VTODO"))
+                }
+                "displayname" => name = text(p),
+                "calendar-description" => description = text(p),
+                _ => {}
+            }
+        }
+
+        if !is_calendar || !has_todos {
+            continue;
+        }
+
+        if name.is_empty() {
+            name = href
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+        }
+
+        result.push(Calendar {
+            href,
+            name,
+            description,
+        });
+    }
+
+    Ok(result)
+}
+
+fn parse_href_property(xml: &str, property: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let doc = roxmltree::Document::parse(xml)?;
+
+    for r in responses(&doc) {
+        for p in found_properties(r) {
+            if p.tag_name().name() == property {
+                if let Some(h) = child(p, "href") {
+                    return Ok(Some(text(h)));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn parse_resources(xml: &str) -> Result<Vec<Resource>, Box<dyn Error>> {
+    let doc = roxmltree::Document::parse(xml)?;
+    let mut result = Vec::new();
+
+    for r in responses(&doc) {
+        let href = match child(r, "href") {
+            Some(h) => text(h),
+            None => continue,
+        };
+
+        let mut etag = String::new();
+        let mut data = String::new();
+        for p in found_properties(r) {
+            match p.tag_name().name() {
+                "getetag" => etag = text(p),
+                "calendar-data" => data = p.text().unwrap_or_default().to_string(),
+                _ => {}
+            }
+        }
+
+        if !data.is_empty() {
+            result.push(Resource { href, etag, data });
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_calendars_test() {
+        let xml = r#"<?xml version="1.0"?>
+<multistatus xmlns="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <response>
+    <href>/user/</href>
+    <propstat>
+      <prop><resourcetype><collection/></resourcetype></prop>
+      <status>HTTP/1.1 200 OK</status>
+    </propstat>
+  </response>
+  <response>
+    <href>/user/tasks/</href>
+    <propstat>
+      <prop>
+        <resourcetype><collection/><C:calendar/></resourcetype>
+        <displayname>Tasks</displayname>
+        <C:supported-calendar-component-set><C:comp name="VTODO"/></C:supported-calendar-component-set>
+      </prop>
+      <status>HTTP/1.1 200 OK</status>
+    </propstat>
+    <propstat>
+      <prop><C:calendar-description/></prop>
+      <status>HTTP/1.1 404 Not Found</status>
+    </propstat>
+  </response>
+  <response>
+    <href>/user/events/</href>
+    <propstat>
+      <prop>
+        <resourcetype><collection/><C:calendar/></resourcetype>
+        <C:supported-calendar-component-set><C:comp name="VEVENT"/></C:supported-calendar-component-set>
+      </prop>
+      <status>HTTP/1.1 200 OK</status>
+    </propstat>
+  </response>
+  <response>
+    <href>/user/all/</href>
+    <propstat>
+      <prop><resourcetype><collection/><C:calendar/></resourcetype></prop>
+      <status>HTTP/1.1 200 OK</status>
+    </propstat>
+  </response>
+</multistatus>"#;
+
+        let calendars = parse_calendars(xml).unwrap();
+        assert_eq!(
+            calendars,
+            vec![
+                Calendar {
+                    href: "/user/tasks/".to_string(),
+                    name: "Tasks".to_string(),
+                    description: String::new(),
+                },
+                Calendar {
+                    href: "/user/all/".to_string(),
+                    name: "all".to_string(),
+                    description: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_resources_test() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+  <d:response>
+    <d:href>/user/tasks/1.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>"abc"</d:getetag>
+        <cal:calendar-data>BEGIN:VCALENDAR&#13;
+BEGIN:VTODO&#13;
+UID:1&#13;
+END:VTODO&#13;
+END:VCALENDAR&#13;
+</cal:calendar-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#;
+
+        let resources = parse_resources(xml).unwrap();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].href, "/user/tasks/1.ics");
+        assert_eq!(resources[0].etag, "\"abc\"");
+        assert!(resources[0].data.contains("UID:1\r\n"));
+    }
+
+    #[test]
+    fn parse_href_property_test() {
+        let xml = r#"<?xml version="1.0"?>
+<multistatus xmlns="DAV:">
+  <response>
+    <href>/</href>
+    <propstat>
+      <prop><current-user-principal><href>/principals/user/</href></current-user-principal></prop>
+      <status>HTTP/1.1 200 OK</status>
+    </propstat>
+  </response>
+</multistatus>"#;
+
+        assert_eq!(
+            parse_href_property(xml, "current-user-principal").unwrap(),
+            Some("/principals/user/".to_string())
+        );
+        assert_eq!(parse_href_property(xml, "calendar-home-set").unwrap(), None);
+    }
+}
@@ -0,0 +1,338 @@
+// SPDX-License-Identifier: MIT
+
+// Just enough of iCalendar (RFC 5545) to read and change a single VTODO.
+// All the other components and properties are kept as is.
+
+use crate::task::DateTimeUtc;
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+const TODO_BEGIN: &str = "BEGIN:VTODO";
+const TODO_END: &str = "END:VTODO";
+const MAX_LINE_LENGTH: usize = 75;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl Property {
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        // The value starts after the first colon which is not inside a quoted parameter value
+        let mut in_quotes = false;
+        let mut colon = None;
+        for (i, c) in line.char_indices() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                ':' if !in_quotes => {
+                    colon = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let colon = colon?;
+        let mut parts = line[..colon].split(';');
+        let name = parts.next()?.to_uppercase();
+        let params = parts
+            .filter_map(|p| p.split_once('='))
+            .map(|(n, v)| (n.to_uppercase(), v.trim_matches('"').to_string()))
+            .collect();
+
+        Some(Self {
+            name,
+            params,
+            value: line[colon + 1..].to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    lines: Vec<String>,
+}
+
+impl Calendar {
+    pub fn parse(text: &str) -> Self {
+        let mut lines: Vec<String> = Vec::new();
+
+        for l in text.split('\n') {
+            let l = l.strip_suffix('\r').unwrap_or(l);
+            if let Some(continuation) = l.strip_prefix([' ', '\t']) {
+                if let Some(last) = lines.last_mut() {
+                    last.push_str(continuation);
+                    continue;
+                }
+            }
+            if !l.is_empty() {
+                lines.push(l.to_string());
+            }
+        }
+
+        Self { lines }
+    }
+
+    // Returns indexes of the lines of the first VTODO's own properties (without nested components like VALARM)
+    fn todo_lines(&self) -> Vec<usize> {
+        let mut result = Vec::new();
+        let mut depth = 0;
+        let mut in_todo = false;
+
+        for (i, l) in self.lines.iter().enumerate() {
+            if !in_todo {
+                in_todo = l.eq_ignore_ascii_case(TODO_BEGIN);
+                continue;
+            }
+
+            if l.eq_ignore_ascii_case(TODO_END) && depth == 0 {
+                break;
+            }
+
+            let upper = l.to_uppercase();
+            if upper.starts_with("BEGIN:") {
+                depth += 1;
+            } else if upper.starts_with("END:") {
+                depth -= 1;
+            } else if depth == 0 {
+                result.push(i);
+            }
+        }
+
+        result
+    }
+
+    fn todo_end(&self) -> Option<usize> {
+        let start = self.lines.iter().position(|l| l.eq_ignore_ascii_case(TODO_BEGIN))?;
+        let mut depth = 0;
+        for (i, l) in self.lines.iter().enumerate().skip(start + 1) {
+            let upper = l.to_uppercase();
+            if upper == TODO_END && depth == 0 {
+                return Some(i);
+            }
+            if upper.starts_with("BEGIN:") {
+                depth += 1;
+            } else if upper.starts_with("END:") {
+                depth -= 1;
+            }
+        }
+        None
+    }
+
+    pub fn has_todo(&self) -> bool {
+        self.todo_end().is_some()
+    }
+
+    pub fn property(&self, name: &str) -> Option<Property> {
+        self.properties(name).into_iter().next()
+    }
+
+    pub fn properties(&self, name: &str) -> Vec<Property> {
+        self.todo_lines()
+            .iter()
+            .filter_map(|i| Property::parse(&self.lines[*i]))
+            .filter(|p| p.name.eq_ignore_ascii_case(name))
+            .collect()
+    }
+
+    // Replaces all the properties with the name by the new one or removes them if the value is None
+    pub fn set_property(&mut self, name: &str, params: &[(&str, &str)], value: Option<&str>) {
+        let to_remove = self
+            .todo_lines()
+            .into_iter()
+            .filter(|i| Property::parse(&self.lines[*i]).is_some_and(|p| p.name.eq_ignore_ascii_case(name)))
+            .collect::<Vec<usize>>();
+
+        for i in to_remove.iter().rev() {
+            self.lines.remove(*i);
+        }
+
+        if let Some(v) = value {
+            let mut line = name.to_string();
+            for (n, pv) in params {
+                line.push_str(format!(";{n}={pv}").as_str());
+            }
+            line.push(':');
+            line.push_str(v);
+
+            if let Some(end) = self.todo_end() {
+                self.lines.insert(end, line);
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Calendar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for l in &self.lines {
+            let mut len = 0;
+            for c in l.chars() {
+                if len + c.len_utf8() > MAX_LINE_LENGTH {
+                    write!(f, "\r\n ")?;
+                    len = 1;
+                }
+                write!(f, "{c}")?;
+                len += c.len_utf8();
+            }
+            write!(f, "\r\n")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+pub fn unescape_text(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => result.push('\n'),
+                Some(x) => result.push(x),
+                None => result.push(c),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+// Splits a list value like CATEGORIES taking the escaped commas into account
+pub fn split_list(s: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in s.chars() {
+        if escaped {
+            current.push('\\');
+            current.push(c);
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == ',' {
+            result.push(unescape_text(&current));
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    result.push(unescape_text(&current));
+    result.into_iter().filter(|s| !s.is_empty()).collect()
+}
+
+pub fn parse_datetime(p: &Property) -> Option<DateTimeUtc> {
+    let v = p.value.trim();
+
+    if p.param("VALUE") == Some("DATE") || v.len() == 8 {
+        let d = NaiveDate::parse_from_str(v, "%Y%m%d").ok()?;
+        return Some(DateTimeUtc::from_naive_utc_and_offset(d.and_hms_opt(0, 0, 0)?, Utc));
+    }
+
+    if let Some(v) = v.strip_suffix('Z') {
+        let dt = NaiveDateTime::parse_from_str(v, "%Y%m%dT%H%M%S").ok()?;
+        return Some(DateTimeUtc::from_naive_utc_and_offset(dt, Utc));
+    }
+
+    // Floating time or time with TZID: time zone definitions aren't supported, so the local time zone is used
+    let dt = NaiveDateTime::parse_from_str(v, "%Y%m%dT%H%M%S").ok()?;
+    Local
+        .from_local_datetime(&dt)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+pub fn format_date(d: &DateTimeUtc) -> String {
+    d.format("%Y%m%d").to_string()
+}
+
+pub fn format_datetime(d: &DateTimeUtc) -> String {
+    d.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TODO: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Test//EN\r
+BEGIN:VTODO\r
+UID:task-1\r
+SUMMARY:Buy milk\\, bread\r
+DESCRIPTION:First line\\nsecond line which is long enough to be folded by the serv\r
+ er\r
+STATUS:NEEDS-ACTION\r
+DUE;VALUE=DATE:20250127\r
+CATEGORIES:home,shop\r
+RELATED-TO;RELTYPE=PARENT:task-0\r
+BEGIN:VALARM\r
+ACTION:DISPLAY\r
+DESCRIPTION:Alarm\r
+END:VALARM\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+    #[test]
+    fn parse_test() {
+        let c = Calendar::parse(TODO);
+        assert!(c.has_todo());
+        assert_eq!(c.property("uid").unwrap().value, "task-1");
+        assert_eq!(unescape_text(&c.property("SUMMARY").unwrap().value), "Buy milk, bread");
+        assert_eq!(
+            unescape_text(&c.property("DESCRIPTION").unwrap().value),
+            "First line\nsecond line which is long enough to be folded by the server"
+        );
+        assert_eq!(
+            c.properties("DESCRIPTION").len(),
+            1,
+            "VALARM properties shouldn't be taken"
+        );
+        assert_eq!(
+            parse_datetime(&c.property("DUE").unwrap())
+                .unwrap()
+                .format("%Y-%m-%d")
+                .to_string(),
+            "2025-01-27"
+        );
+        assert_eq!(
+            split_list(&c.property("CATEGORIES").unwrap().value),
+            vec!["home", "shop"]
+        );
+        assert_eq!(c.property("RELATED-TO").unwrap().param("reltype"), Some("PARENT"));
+    }
+
+    #[test]
+    fn set_property_test() {
+        let mut c = Calendar::parse(TODO);
+        c.set_property("STATUS", &[], Some("COMPLETED"));
+        c.set_property("COMPLETED", &[], Some("20250128T100000Z"));
+        c.set_property("DUE", &[], None);
+
+        let text = c.to_string();
+        let c = Calendar::parse(&text);
+        assert_eq!(c.property("STATUS").unwrap().value, "COMPLETED");
+        assert_eq!(
+            parse_datetime(&c.property("COMPLETED").unwrap()).unwrap().to_rfc3339(),
+            "2025-01-28T10:00:00+00:00"
+        );
+        assert!(c.property("DUE").is_none());
+        assert!(text.contains("BEGIN:VALARM\r\nACTION:DISPLAY\r\nDESCRIPTION:Alarm\r\nEND:VALARM\r\n"));
+        assert!(text.lines().all(|l| l.len() <= MAX_LINE_LENGTH + 1));
+    }
+}
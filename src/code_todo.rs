@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: MIT
+
+// A provider for TODO, FIXME, HACK and XXX comments of a source tree. The tasks are read-only,
+// completing or deleting a task removes its comment from the code.
+
+mod client;
+mod project;
+mod syntax;
+mod task;
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::provider::{PatchError, Provider as ProviderTrait, TaskPatch};
+use crate::task::{State, Task as TaskTrait, due_group};
+use async_trait::async_trait;
+use itertools::Itertools;
+use ratatui::style::Color;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "Code TODO";
+
+pub struct Provider {
+    name: String,
+    c: client::Client,
+    color: Color,
+}
+
+impl Provider {
+    pub fn new(name: &str, path: &str, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            c: client::Client::new(path),
+            color: *color,
+        }
+    }
+}
+
+fn cast(t: &dyn TaskTrait) -> &task::Task {
+    match t.as_any().downcast_ref::<task::Task>() {
+        Some(t) => t,
+        None => panic!("Wrong casting!"),
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        let project = project.map(|p| p.id());
+
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        if !f.states.contains(&filter::FilterState::Uncompleted) {
+            return Ok(result);
+        }
+
+        for mut t in self.c.tasks().await? {
+            t.provider = self.name();
+            if !f.due.contains(&due_group(&t)) {
+                continue;
+            }
+            if project
+                .as_ref()
+                .is_some_and(|p| t.project().is_none_or(|tp| tp.id() != *p))
+            {
+                continue;
+            }
+            result.push(Box::new(t));
+        }
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for f in self.c.tasks().await?.iter().map(|t| t.file_path.to_string()).unique() {
+            result.push(Box::new(project::Project::new(&self.name, self.c.path(), &f)));
+        }
+        Ok(result)
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let mut patch = TaskPatch::new(task.clone_boxed());
+        patch.state = Some(state);
+
+        match self.patch_tasks(&[patch]).await.pop() {
+            Some(e) => Err(Box::<dyn Error>::from(e.error)),
+            None => Ok(()),
+        }
+    }
+
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        let mut patch = TaskPatch::new(task.clone_boxed());
+        patch.delete = true;
+
+        match self.patch_tasks(&[patch]).await.pop() {
+            Some(e) => Err(Box::<dyn Error>::from(e.error)),
+            None => Ok(()),
+        }
+    }
+
+    async fn patch_tasks(&mut self, patches: &[TaskPatch]) -> Vec<PatchError> {
+        let mut tasks = Vec::new();
+        let mut errors = Vec::new();
+        for p in patches.iter() {
+            if !p.delete && (p.has_edits() || p.state.as_ref().is_some_and(|s| *s != State::Completed)) {
+                errors.push(PatchError {
+                    task: p.task.clone_boxed(),
+                    error: "Code comments are read-only, they can only be removed by completing the task".to_string(),
+                });
+                continue;
+            }
+
+            if p.delete || p.state.is_some() {
+                tasks.push(cast(p.task.as_ref()));
+            }
+        }
+
+        for e in self.c.resolve_tasks(&tasks) {
+            errors.push(PatchError {
+                task: e.task.clone_boxed(),
+                error: e.error,
+            })
+        }
+
+        errors
+    }
+
+    async fn reload(&mut self) {
+        // The files are scanned on every request
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+
+    fn completion_deletes_tasks(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: MIT
+
+use super::syntax::Syntax;
+use super::task::Task;
+use ignore::WalkBuilder;
+use itertools::Itertools;
+use std::error::Error;
+use std::fs;
+
+// Bigger files are usually generated or minified, they aren't scanned
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+pub struct PatchError {
+    pub task: Task,
+    pub error: String,
+}
+
+pub struct Client {
+    path: String,
+}
+
+impl Client {
+    pub fn new(path: &str) -> Self {
+        Self { path: path.to_string() }
+    }
+
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    pub async fn tasks(&self) -> Result<Vec<Task>, Box<dyn Error>> {
+        let path = self.path.to_string();
+        let tasks = tokio::task::spawn_blocking(move || scan(&path)).await?;
+        Ok(tasks)
+    }
+
+    // Removes the comments of the tasks from the files
+    pub fn resolve_tasks(&self, tasks: &[&Task]) -> Vec<PatchError> {
+        let mut errors = Vec::new();
+
+        for file in tasks.iter().map(|t| t.file_path.as_str()).unique() {
+            let mut file_tasks = tasks.iter().filter(|t| t.file_path == file).collect::<Vec<_>>();
+            // Removed lines shift the next ones, so the file is changed from the end
+            file_tasks.sort_by_key(|t| std::cmp::Reverse(t.line));
+
+            let mut lines = match fs::read_to_string(file) {
+                Ok(content) => content.split('\n').map(|l| l.to_string()).collect::<Vec<String>>(),
+                Err(e) => {
+                    errors.extend(file_tasks.iter().map(|t| PatchError {
+                        task: (**t).clone(),
+                        error: e.to_string(),
+                    }));
+                    continue;
+                }
+            };
+
+            for t in &file_tasks {
+                if lines.get(t.line) != Some(&t.raw) {
+                    errors.push(PatchError {
+                        task: (**t).clone(),
+                        error: "Task has been changed since last loading".to_string(),
+                    });
+                    continue;
+                }
+
+                // Cutting the line at a marker inside a string literal would break the code
+                if !t.is_comment() {
+                    errors.push(PatchError {
+                        task: (**t).clone(),
+                        error: "The marker isn't at the start of a comment".to_string(),
+                    });
+                    continue;
+                }
+
+                match t.line_without_comment() {
+                    Some(mut l) => {
+                        if t.raw.ends_with('\r') {
+                            l.push('\r');
+                        }
+                        lines[t.line] = l;
+                    }
+                    None => {
+                        lines.remove(t.line);
+                    }
+                }
+            }
+
+            if let Err(e) = fs::write(file, lines.join("\n")) {
+                errors.extend(file_tasks.iter().map(|t| PatchError {
+                    task: (**t).clone(),
+                    error: e.to_string(),
+                }));
+            }
+        }
+
+        errors
+    }
+}
+
+// Walks the directory skipping hidden files, the ones ignored by .gitignore and files of unknown languages
+fn scan(root: &str) -> Vec<Task> {
+    let mut result = Vec::new();
+
+    for entry in WalkBuilder::new(root).build().flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) || entry.metadata().is_ok_and(|m| m.len() > MAX_FILE_SIZE) {
+            continue;
+        }
+
+        let file_path = match entry.path().to_str() {
+            Some(p) => p.to_string(),
+            None => continue,
+        };
+        let syntax = match Syntax::for_path(&file_path) {
+            Some(s) => s,
+            None => continue,
+        };
+        // Binary files aren't valid UTF-8 and are skipped here
+        let content = match fs::read_to_string(&file_path) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        for (i, l) in content.split('\n').enumerate() {
+            if let Some(t) = Task::parse(l, syntax) {
+                result.push(Task {
+                    root_path: root.to_string(),
+                    file_path: file_path.to_string(),
+                    line: i,
+                    ..t
+                });
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Task as TaskTrait;
+
+    #[tokio::test]
+    async fn tasks_and_resolve_test() {
+        let root = std::env::temp_dir().join(format!("tatuin-code-todo-{}", std::process::id()));
+        for dir in ["src", "target", ".git"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::write(
+            root.join("src/main.rs"),
+            "fn main() {\n    // TODO(@bob, 2025-01-01): greet\n    let x = 1; // FIXME magic\n    println!(\"{x}\");\n    let s = \"a // TODO b\";\n}\n",
+        )
+        .unwrap();
+        fs::write(root.join("TODO.md"), "## TODO list\n- [ ] write docs\n").unwrap();
+        fs::write(root.join("target/generated.rs"), "// TODO ignored\n").unwrap();
+
+        let mut path = root.to_str().unwrap().to_string();
+        path.push('/');
+
+        let c = Client::new(&path);
+        let tasks = c.tasks().await.unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].place(), "src/main.rs:2");
+        assert_eq!(tasks[0].labels(), vec!["todo", "@bob"]);
+        assert_eq!(tasks[1].place(), "src/main.rs:3");
+
+        let errors = c.resolve_tasks(&tasks.iter().collect::<Vec<&Task>>());
+        assert!(errors.is_empty());
+        assert_eq!(
+            fs::read_to_string(root.join("src/main.rs")).unwrap(),
+            "fn main() {\n    let x = 1;\n    println!(\"{x}\");\n    let s = \"a // TODO b\";\n}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("TODO.md")).unwrap(),
+            "## TODO list\n- [ ] write docs\n"
+        );
+
+        // A string literal with a marker is refused even if such a task is passed
+        let fake = Task {
+            root_path: path.to_string(),
+            file_path: root.join("src/main.rs").to_str().unwrap().to_string(),
+            line: 3,
+            raw: "    let s = \"a // TODO b\";".to_string(),
+            comment_start: 15,
+            keyword: "TODO".to_string(),
+            text: "b\";".to_string(),
+            ..Task::default()
+        };
+        assert_eq!(c.resolve_tasks(&[&fake]).len(), 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
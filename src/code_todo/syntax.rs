@@ -0,0 +1,245 @@
+// SPDX-License-Identifier: MIT
+
+// Comment syntax of the source files. Only files of the known languages are scanned, so headings of
+// markdown (## TODO) or org (* TODO) files aren't taken as comments.
+
+pub struct Syntax {
+    line: &'static [&'static str],
+    // Start and end markers of block comments
+    block: &'static [(&'static str, &'static str)],
+    // Quotes of string literals, the markers inside them aren't comments
+    quotes: &'static [char],
+}
+
+const C_LIKE: Syntax = Syntax {
+    line: &["//"],
+    block: &[("/*", "*/")],
+    quotes: &['"', '\''],
+};
+
+// Single quotes are lifetimes in Rust
+const RUST: Syntax = Syntax {
+    line: &["//"],
+    block: &[("/*", "*/")],
+    quotes: &['"'],
+};
+
+const JS_LIKE: Syntax = Syntax {
+    line: &["//"],
+    block: &[("/*", "*/")],
+    quotes: &['"', '\'', '`'],
+};
+
+const CSS: Syntax = Syntax {
+    line: &[],
+    block: &[("/*", "*/")],
+    quotes: &['"', '\''],
+};
+
+const HASH: Syntax = Syntax {
+    line: &["#"],
+    block: &[],
+    quotes: &['"', '\''],
+};
+
+const DASH: Syntax = Syntax {
+    line: &["--"],
+    block: &[],
+    quotes: &['"', '\''],
+};
+
+const SEMICOLON: Syntax = Syntax {
+    line: &[";"],
+    block: &[],
+    quotes: &['"'],
+};
+
+const PERCENT: Syntax = Syntax {
+    line: &["%"],
+    block: &[],
+    quotes: &[],
+};
+
+const MARKUP: Syntax = Syntax {
+    line: &[],
+    block: &[("<!--", "-->")],
+    quotes: &[],
+};
+
+// Vue and Svelte components mix markup with scripts
+const COMPONENT: Syntax = Syntax {
+    line: &["//"],
+    block: &[("<!--", "-->"), ("/*", "*/")],
+    quotes: &['"', '\'', '`'],
+};
+
+impl Syntax {
+    pub fn for_path(path: &str) -> Option<&'static Syntax> {
+        let file_name = std::path::Path::new(path).file_name()?.to_str()?;
+        match file_name {
+            "Makefile" | "Dockerfile" | "CMakeLists.txt" => return Some(&HASH),
+            _ => {}
+        }
+
+        let ext = file_name.rsplit_once('.')?.1.to_lowercase();
+        let syntax = match ext.as_str() {
+            "rs" => &RUST,
+            "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "cs" | "java" | "kt" | "kts" | "scala" | "swift"
+            | "dart" | "php" | "proto" | "scss" | "less" => &C_LIKE,
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "go" => &JS_LIKE,
+            "css" => &CSS,
+            "py" | "sh" | "bash" | "zsh" | "fish" | "rb" | "pl" | "r" | "yaml" | "yml" | "toml" | "nix" | "cmake"
+            | "ps1" | "tf" | "ex" | "exs" | "jl" => &HASH,
+            "sql" | "lua" | "hs" | "elm" => &DASH,
+            "lisp" | "el" | "clj" | "cljs" | "scm" | "asm" | "ini" => &SEMICOLON,
+            "tex" | "erl" | "hrl" => &PERCENT,
+            "html" | "htm" | "xml" => &MARKUP,
+            "vue" | "svelte" => &COMPONENT,
+            _ => return None,
+        };
+        Some(syntax)
+    }
+
+    // Returns (marker start, text start) of the comments which begin outside of string literals
+    pub fn comments(&self, line: &str) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+
+        // A line of a block comment like " * TODO: describe"
+        let indent = line.len() - line.trim_start().len();
+        if self.block.iter().any(|(start, _)| *start == "/*") && line[indent..].starts_with('*') {
+            result.push((indent, marker_end(line, indent, "*")));
+        }
+
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        let mut iter = line.char_indices();
+        while let Some((i, c)) = iter.next() {
+            if let Some(q) = quote {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+                continue;
+            }
+
+            if self.quotes.contains(&c) {
+                quote = Some(c);
+                continue;
+            }
+
+            let rest = &line[i..];
+            if let Some(m) = self.line.iter().find(|m| rest.starts_with(**m)) {
+                // Everything after a line comment marker is the comment
+                result.push((i, marker_end(line, i, m)));
+                break;
+            }
+
+            if let Some((start, end)) = self.block.iter().find(|(start, _)| rest.starts_with(*start)) {
+                let text_start = marker_end(line, i, start);
+                result.push((i, text_start));
+                match line[text_start..].find(end) {
+                    Some(idx) => {
+                        let skip_to = text_start + idx + end.len();
+                        // The iterator continues after the end of the block
+                        while iter.clone().next().is_some_and(|(j, _)| j < skip_to) {
+                            iter.next();
+                        }
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        result
+    }
+}
+
+// Repeated marker chars like /// or ## and the ! of doc comments belong to the marker
+fn marker_end(line: &str, start: usize, marker: &str) -> usize {
+    let last = marker.chars().last().unwrap_or_default();
+    let mut end = start + marker.len();
+    for c in line[end..].chars() {
+        if c != last && c != '!' {
+            break;
+        }
+        end += c.len_utf8();
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comments_test() {
+        struct Case<'a> {
+            name: &'a str,
+            path: &'a str,
+            line: &'a str,
+            expected: &'a [usize],
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "line comment",
+                path: "main.rs",
+                line: "let x = 1; // TODO",
+                expected: &[11],
+            },
+            Case {
+                name: "marker in a string",
+                path: "main.rs",
+                line: r#"let s = "a // TODO b";"#,
+                expected: &[],
+            },
+            Case {
+                name: "escaped quote",
+                path: "main.c",
+                line: r#"puts("\" // x"); // TODO"#,
+                expected: &[17],
+            },
+            Case {
+                name: "rust lifetime",
+                path: "lib.rs",
+                line: "fn f<'a>(s: &'a str) {} // TODO",
+                expected: &[24],
+            },
+            Case {
+                name: "block before a line comment",
+                path: "main.ts",
+                line: "/* a */ call(); // TODO",
+                expected: &[0, 16],
+            },
+            Case {
+                name: "block comment line",
+                path: "main.java",
+                line: "   * TODO",
+                expected: &[3],
+            },
+            Case {
+                name: "hash in python string",
+                path: "main.py",
+                line: "x = '#TODO' # FIXME",
+                expected: &[12],
+            },
+        ];
+
+        for c in CASES {
+            let syntax = Syntax::for_path(c.path).unwrap();
+            let starts: Vec<usize> = syntax.comments(c.line).iter().map(|(s, _)| *s).collect();
+            assert_eq!(starts, c.expected, "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn for_path_test() {
+        assert!(Syntax::for_path("/src/main.rs").is_some());
+        assert!(Syntax::for_path("/Makefile").is_some());
+        assert!(Syntax::for_path("/README.md").is_none());
+        assert!(Syntax::for_path("/notes.org").is_none());
+        assert!(Syntax::for_path("/LICENSE").is_none());
+    }
+}
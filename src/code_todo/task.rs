@@ -0,0 +1,290 @@
+// SPDX-License-Identifier: MIT
+
+use super::project::Project;
+use super::syntax::Syntax;
+use crate::project::Project as ProjectTrait;
+use crate::task::{DateTimeUtc, State, Task as TaskTrait};
+use chrono::{NaiveDate, Utc};
+use regex::Regex;
+use sha256::digest;
+use std::any::Any;
+use std::sync::LazyLock;
+
+// The keyword has to start the text of a comment
+static KEYWORD_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(TODO|FIXME|HACK|XXX)\b(?:\(([^)]*)\))?:?\s*(.*?)\s*$").unwrap());
+static BLOCK_END_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s*(\*/|-->).*$").unwrap());
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Task {
+    pub root_path: String,
+    pub provider: String,
+
+    pub file_path: String,
+    pub line: usize,
+    pub raw: String,
+    // Byte index of the comment marker in the line
+    pub comment_start: usize,
+
+    pub keyword: String,
+    pub text: String,
+    pub assignee: Option<String>,
+    pub due: Option<NaiveDate>,
+}
+
+impl Task {
+    pub fn parse(line: &str, syntax: &Syntax) -> Option<Self> {
+        let (comment_start, caps) = syntax
+            .comments(line)
+            .into_iter()
+            .find_map(|(start, text_start)| KEYWORD_RE.captures(&line[text_start..]).map(|caps| (start, caps)))?;
+
+        let mut t = Task {
+            raw: line.to_string(),
+            comment_start,
+            keyword: caps[1].to_string(),
+            text: BLOCK_END_RE.replace(&caps[3], "").to_string(),
+            ..Default::default()
+        };
+
+        // The arguments like (@user, 2025-01-01) are an assignee and a due date in any order
+        for arg in caps.get(2).map(|m| m.as_str()).unwrap_or_default().split(',') {
+            let arg = arg.trim();
+            if arg.is_empty() {
+                continue;
+            }
+            match NaiveDate::parse_from_str(arg, DATE_FORMAT) {
+                Ok(d) => t.due = Some(d),
+                Err(_) => t.assignee = Some(arg.trim_start_matches('@').to_string()),
+            }
+        }
+
+        Some(t)
+    }
+
+    // The marker is still at the start of a comment and not inside a string literal
+    pub fn is_comment(&self) -> bool {
+        Syntax::for_path(&self.file_path).is_some_and(|s| {
+            s.comments(&self.raw)
+                .iter()
+                .any(|(start, _)| *start == self.comment_start)
+        })
+    }
+
+    // Returns the line without the comment or None if nothing but the comment is in the line
+    pub fn line_without_comment(&self) -> Option<String> {
+        let code = self.raw[..self.comment_start].trim_end();
+        let rest = &self.raw[self.comment_start..];
+
+        // The code after a block comment like "/* TODO: check */ call();" is kept
+        let block_end = if rest.starts_with("/*") {
+            rest.find("*/").map(|i| i + 2)
+        } else if rest.starts_with("<!--") {
+            rest.find("-->").map(|i| i + 3)
+        } else {
+            None
+        };
+        let tail = block_end.map(|i| rest[i..].trim()).unwrap_or_default();
+
+        match (code.is_empty(), tail.is_empty()) {
+            (true, true) => None,
+            (true, false) => {
+                let indent = &self.raw[..self.raw.len() - self.raw.trim_start().len()];
+                Some(format!("{indent}{tail}"))
+            }
+            (false, true) => Some(code.to_string()),
+            (false, false) => Some(format!("{code} {tail}")),
+        }
+    }
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        digest(format!("{}:{}:{}", self.file_path, self.line, self.raw))
+    }
+
+    fn text(&self) -> String {
+        if self.text.is_empty() {
+            self.keyword.to_string()
+        } else {
+            self.text.to_string()
+        }
+    }
+
+    fn state(&self) -> State {
+        State::Uncompleted
+    }
+
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.due
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|dt| DateTimeUtc::from_naive_utc_and_offset(dt, Utc))
+    }
+
+    fn place(&self) -> String {
+        format!(
+            "{}:{}",
+            self.file_path.strip_prefix(self.root_path.as_str()).unwrap_or_default(),
+            self.line + 1,
+        )
+    }
+
+    fn labels(&self) -> Vec<String> {
+        let mut labels = vec![self.keyword.to_lowercase()];
+        if let Some(a) = &self.assignee {
+            labels.push(format!("@{a}"));
+        }
+        labels
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        Some(Box::new(Project::new(&self.provider, &self.root_path, &self.file_path)))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        struct Case<'a> {
+            name: &'a str,
+            path: &'a str,
+            line: &'a str,
+            expected: Option<(&'a str, &'a str, Option<&'a str>, Option<&'a str>)>,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "rust comment",
+                path: "main.c",
+                line: "    // TODO: handle errors",
+                expected: Some(("TODO", "handle errors", None, None)),
+            },
+            Case {
+                name: "assignee and due date",
+                path: "main.py",
+                line: "# FIXME(@panter, 2025-01-01) remove the workaround",
+                expected: Some(("FIXME", "remove the workaround", Some("panter"), Some("2025-01-01"))),
+            },
+            Case {
+                name: "trailing comment",
+                path: "main.c",
+                line: "let x = 1; // HACK(bob): magic number",
+                expected: Some(("HACK", "magic number", Some("bob"), None)),
+            },
+            Case {
+                name: "block comment",
+                path: "main.c",
+                line: "/* XXX check the overflow */",
+                expected: Some(("XXX", "check the overflow", None, None)),
+            },
+            Case {
+                name: "block comment line",
+                path: "main.c",
+                line: " * TODO document it",
+                expected: Some(("TODO", "document it", None, None)),
+            },
+            Case {
+                name: "not a comment",
+                path: "main.c",
+                line: "let todo = \"TODO\";",
+                expected: None,
+            },
+            Case {
+                name: "string literal",
+                path: "main.c",
+                line: "let s = \"a // TODO b\";",
+                expected: None,
+            },
+            Case {
+                name: "comment after a string literal",
+                path: "main.c",
+                line: "let s = \"a // b\"; // TODO: c",
+                expected: Some(("TODO", "c", None, None)),
+            },
+            Case {
+                name: "not the first comment",
+                path: "main.c",
+                line: "// see the notes // TODO: c",
+                expected: None,
+            },
+            Case {
+                name: "part of a word",
+                path: "main.c",
+                line: "// TODOS are here",
+                expected: None,
+            },
+        ];
+
+        for c in CASES {
+            let t = Task::parse(c.line, Syntax::for_path(c.path).unwrap());
+            let result = t.as_ref().map(|t| {
+                (
+                    t.keyword.as_str(),
+                    t.text.as_str(),
+                    t.assignee.as_deref(),
+                    t.due.map(|d| d.format(DATE_FORMAT).to_string()),
+                )
+            });
+            let expected = c.expected.map(|(k, t, a, d)| (k, t, a, d.map(|d| d.to_string())));
+            assert_eq!(result, expected, "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn line_without_comment_test() {
+        struct Case<'a> {
+            name: &'a str,
+            line: &'a str,
+            expected: Option<&'a str>,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "comment only",
+                line: "    // TODO: handle errors",
+                expected: None,
+            },
+            Case {
+                name: "trailing comment",
+                line: "    let x = 1; // HACK: magic number",
+                expected: Some("    let x = 1;"),
+            },
+            Case {
+                name: "block comment before code",
+                line: "    /* TODO: check */ call();",
+                expected: Some("    call();"),
+            },
+            Case {
+                name: "block comment after code",
+                line: "call(); /* FIXME */",
+                expected: Some("call();"),
+            },
+        ];
+
+        let syntax = Syntax::for_path("main.c").unwrap();
+        for c in CASES {
+            let t = Task::parse(c.line, syntax).unwrap();
+            assert_eq!(
+                t.line_without_comment().as_deref(),
+                c.expected,
+                "Test '{}' was failed",
+                c.name
+            );
+        }
+    }
+}
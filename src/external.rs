@@ -0,0 +1,467 @@
+// SPDX-License-Identifier: MIT
+
+// A provider that runs an external process and talks to it with JSON messages over stdin/stdout.
+// The protocol is described in docs/external_provider.md.
+
+mod client;
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::provider::{DuePatch, PatchError, Provider as ProviderTrait, TaskPatch};
+use crate::task::{DateTimeUtc, Priority, State as TaskState, Task as TaskTrait};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::error::Error;
+use std::time::Duration;
+
+pub const PROVIDER_NAME: &str = "External";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+enum State {
+    #[default]
+    Uncompleted,
+    Completed,
+    InProgress,
+    Unknown,
+}
+
+impl From<TaskState> for State {
+    fn from(s: TaskState) -> Self {
+        match s {
+            TaskState::Uncompleted => State::Uncompleted,
+            TaskState::Completed => State::Completed,
+            TaskState::InProgress => State::InProgress,
+            TaskState::Unknown(_) => State::Unknown,
+        }
+    }
+}
+
+fn str_to_date(s: &str) -> Option<DateTimeUtc> {
+    if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let dt = d.and_hms_opt(0, 0, 0)?;
+        return Some(DateTimeUtc::from_naive_utc_and_offset(dt, Utc));
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(DateTimeUtc::from(dt));
+    }
+
+    None
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct Project {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+    parent_id: Option<String>,
+    #[serde(default)]
+    is_inbox: bool,
+    #[serde(default)]
+    is_favorite: bool,
+
+    #[serde(skip)]
+    provider: String,
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        self.description.to_string()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.parent_id.clone()
+    }
+
+    fn is_inbox(&self) -> bool {
+        self.is_inbox
+    }
+
+    fn is_favorite(&self) -> bool {
+        self.is_favorite
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct Task {
+    id: String,
+    text: String,
+    description: Option<String>,
+    #[serde(default)]
+    state: State,
+    #[serde(default)]
+    priority: Priority,
+    due: Option<String>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    completed_at: Option<String>,
+    #[serde(default)]
+    place: String,
+    #[serde(default)]
+    labels: Vec<String>,
+    parent_id: Option<String>,
+    recurrence: Option<String>,
+    project_id: Option<String>,
+
+    #[serde(skip)]
+    provider: String,
+    #[serde(skip)]
+    project: Option<Project>,
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn text(&self) -> String {
+        self.text.to_string()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.description.clone()
+    }
+
+    fn priority(&self) -> Priority {
+        self.priority.clone()
+    }
+
+    fn state(&self) -> TaskState {
+        match self.state {
+            State::Uncompleted => TaskState::Uncompleted,
+            State::Completed => TaskState::Completed,
+            State::InProgress => TaskState::InProgress,
+            State::Unknown => TaskState::Unknown('?'),
+        }
+    }
+
+    fn created_at(&self) -> Option<DateTimeUtc> {
+        self.created_at.as_ref().and_then(|s| str_to_date(s))
+    }
+
+    fn updated_at(&self) -> Option<DateTimeUtc> {
+        self.updated_at.as_ref().and_then(|s| str_to_date(s))
+    }
+
+    fn completed_at(&self) -> Option<DateTimeUtc> {
+        self.completed_at.as_ref().and_then(|s| str_to_date(s))
+    }
+
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.due.as_ref().and_then(|s| str_to_date(s))
+    }
+
+    fn place(&self) -> String {
+        self.place.to_string()
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.labels.clone()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.parent_id.clone()
+    }
+
+    fn recurrence(&self) -> Option<String> {
+        self.recurrence.clone()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        self.project.as_ref().map(|p| p.clone_boxed())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Serialize)]
+struct TasksParams<'a> {
+    project_id: Option<String>,
+    filter: &'a filter::Filter,
+}
+
+#[derive(Serialize)]
+struct ChangeTaskStateParams {
+    task_id: String,
+    state: State,
+}
+
+#[derive(Serialize)]
+struct Patch {
+    task_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state: Option<State>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    // An empty string removes the due date
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<Priority>,
+    delete: bool,
+}
+
+impl From<&TaskPatch> for Patch {
+    fn from(p: &TaskPatch) -> Self {
+        Self {
+            task_id: p.task.id(),
+            state: p.state.clone().map(State::from),
+            text: p.text.clone(),
+            description: p.description.clone(),
+            due: p.due.as_ref().map(|d| match d {
+                DuePatch::NoDate => String::new(),
+                DuePatch::Date(d) => d.format("%Y-%m-%d").to_string(),
+            }),
+            priority: p.priority.clone(),
+            delete: p.delete,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PatchTasksParams {
+    patches: Vec<Patch>,
+}
+
+#[derive(Deserialize)]
+struct PatchTaskError {
+    task_id: String,
+    error: String,
+}
+
+#[derive(Serialize)]
+struct NoParams {}
+
+pub struct Provider {
+    name: String,
+    color: Color,
+    c: client::Client,
+    projects: Vec<Project>,
+    timeout: Duration,
+}
+
+impl Provider {
+    pub fn new(name: &str, command: &str, timeout: Duration, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            color: *color,
+            c: client::Client::new(command),
+            projects: Vec::new(),
+            timeout,
+        }
+    }
+
+    async fn load_projects(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.projects.is_empty() {
+            self.projects = self.c.call("projects", NoParams {}).await?;
+            for p in &mut self.projects {
+                p.provider = self.name.to_string();
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        let params = TasksParams {
+            project_id: project.map(|p| p.id()),
+            filter: f,
+        };
+        let tasks: Vec<Task> = self.c.call("tasks", params).await?;
+
+        if tasks.iter().any(|t| t.project_id.is_some()) {
+            self.load_projects().await?;
+        }
+
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for mut t in tasks {
+            t.provider = self.name();
+            t.project = t
+                .project_id
+                .as_ref()
+                .and_then(|id| self.projects.iter().find(|p| p.id == *id).cloned());
+            result.push(Box::new(t));
+        }
+
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        self.load_projects().await?;
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for p in &self.projects {
+            result.push(Box::new(p.clone()));
+        }
+
+        Ok(result)
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: TaskState) -> Result<(), Box<dyn Error>> {
+        let params = ChangeTaskStateParams {
+            task_id: task.id(),
+            state: State::from(state),
+        };
+        self.c.call::<_, serde_json::Value>("change_task_state", params).await?;
+        Ok(())
+    }
+
+    async fn patch_tasks(&mut self, patches: &[TaskPatch]) -> Vec<PatchError> {
+        let params = PatchTasksParams {
+            patches: patches.iter().map(Patch::from).collect(),
+        };
+
+        match self.c.call::<_, Vec<PatchTaskError>>("patch_tasks", params).await {
+            Ok(errors) => errors
+                .iter()
+                .filter_map(|e| {
+                    patches.iter().find(|p| p.task.id() == e.task_id).map(|p| PatchError {
+                        task: p.task.clone_boxed(),
+                        error: e.error.to_string(),
+                    })
+                })
+                .collect(),
+            Err(e) => patches
+                .iter()
+                .map(|p| PatchError {
+                    task: p.task.clone_boxed(),
+                    error: e.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    async fn reload(&mut self) {
+        self.projects.clear();
+        // The result isn't reported, but a hanging adapter mustn't block the provider
+        let _ = tokio::time::timeout(self.timeout, self.c.call::<_, serde_json::Value>("reload", NoParams {})).await;
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Answers to the tasks and projects requests, the id is copied from the request
+    fn fake_adapter(tasks: &str, projects: &str) -> String {
+        let response = |result: &str| format!(r#"echo "{{\"id\":$id,\"result\":{}}}""#, result.replace('"', "\\\""));
+        format!(
+            r#"while read -r line; do id=$(echo "$line" | sed 's/^{{"id":\([0-9]*\).*/\1/'); case "$line" in *'"method":"tasks"'*) {};; *) {};; esac; done"#,
+            response(tasks),
+            response(projects)
+        )
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn tasks_test() {
+        let tasks = r#"[{"id":"1","text":"Some task","state":"InProgress","priority":"High","due":"2025-01-27","labels":["work"],"project_id":"p1"}]"#;
+        let projects = r#"[{"id":"p1","name":"Project"}]"#;
+        let mut p = Provider::new(
+            "External provider",
+            fake_adapter(tasks, projects).as_str(),
+            crate::provider::DEFAULT_TIMEOUT,
+            &Color::White,
+        );
+        let f = filter::Filter {
+            states: vec![filter::FilterState::Uncompleted],
+            due: Vec::new(),
+            labels: Vec::new(),
+        };
+
+        let tasks = p.tasks(None, &f).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        let t = &tasks[0];
+        assert_eq!(t.id(), "1");
+        assert_eq!(t.text(), "Some task");
+        assert_eq!(t.state(), TaskState::InProgress);
+        assert_eq!(t.priority(), Priority::High);
+        assert_eq!(t.due().unwrap().format("%Y-%m-%d").to_string(), "2025-01-27");
+        assert_eq!(t.labels(), vec!["work".to_string()]);
+        assert_eq!(t.provider(), "External provider");
+        assert_eq!(t.project().map(|p| p.name()), Some("Project".to_string()));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn error_test() {
+        let mut p = Provider::new(
+            "External provider",
+            r#"while read -r line; do echo '{"id":1,"error":"Something went wrong"}'; done"#,
+            crate::provider::DEFAULT_TIMEOUT,
+            &Color::White,
+        );
+
+        let err = p.projects().await.err().unwrap();
+        assert_eq!(err.to_string(), "Something went wrong");
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn cancelled_request_test() {
+        let mut c = client::Client::new(
+            r#"while read -r line; do id=$(echo "$line" | sed 's/^{"id":\([0-9]*\).*/\1/'); case "$line" in *'"method":"slow"'*) sleep 2;; esac; echo "{\"id\":$id,\"result\":$id}"; done"#,
+        );
+
+        let cancelled = tokio::time::timeout(
+            std::time::Duration::from_millis(200),
+            c.call::<_, u64>("slow", NoParams {}),
+        )
+        .await;
+        assert!(cancelled.is_err());
+
+        // The adapter is restarted, so the next request doesn't wait for the cancelled one
+        let result =
+            tokio::time::timeout(std::time::Duration::from_secs(1), c.call::<_, u64>("fast", NoParams {})).await;
+        assert_eq!(result.unwrap().unwrap(), 2);
+    }
+}
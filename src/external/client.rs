@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: MIT
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+#[derive(Serialize)]
+struct Request<'a, P: Serialize> {
+    id: u64,
+    method: &'a str,
+    params: P,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    id: u64,
+    #[serde(default)]
+    result: serde_json::Value,
+    error: Option<String>,
+}
+
+struct Process {
+    // The process is killed when it's dropped
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+pub struct Client {
+    command: String,
+    process: Option<Process>,
+    last_request_id: u64,
+    // The last exchange was cancelled in the middle, so the pipes may keep a part of its request or response
+    interrupted: bool,
+}
+
+impl Client {
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            process: None,
+            last_request_id: 0,
+            interrupted: false,
+        }
+    }
+
+    fn start(&self) -> Result<Process, Box<dyn Error>> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(self.command.as_str())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("start '{}': {e}", self.command))?;
+
+        let stdin = child.stdin.take().ok_or("stdin of the process isn't available")?;
+        let stdout = child.stdout.take().ok_or("stdout of the process isn't available")?;
+
+        Ok(Process {
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    pub async fn call<P: Serialize, R: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        params: P,
+    ) -> Result<R, Box<dyn Error>> {
+        self.last_request_id += 1;
+        let request = Request {
+            id: self.last_request_id,
+            method,
+            params,
+        };
+        let mut line = serde_json::to_string(&request)?;
+        line.push('\n');
+
+        if self.interrupted {
+            // A stale response can't be told apart reliably, so the process is started again
+            self.process = None;
+        }
+
+        // The flag stays set if the future is dropped before the exchange is finished
+        self.interrupted = true;
+        let result = self.exchange(request.id, line.as_str()).await;
+        self.interrupted = false;
+        if result.is_err() {
+            // The process is in an unknown state, so start it again on the next request
+            self.process = None;
+        }
+
+        let resp = result?;
+        if let Some(e) = resp.error {
+            return Err(Box::<dyn Error>::from(e));
+        }
+
+        Ok(serde_json::from_value::<R>(resp.result)?)
+    }
+
+    async fn exchange(&mut self, id: u64, line: &str) -> Result<Response, Box<dyn Error>> {
+        if self.process.is_none() {
+            self.process = Some(self.start()?);
+        }
+        let p = self.process.as_mut().unwrap();
+
+        p.stdin.write_all(line.as_bytes()).await?;
+        p.stdin.flush().await?;
+
+        let mut resp_line = String::new();
+        if p.stdout.read_line(&mut resp_line).await? == 0 {
+            return Err(Box::<dyn Error>::from("the process has exited"));
+        }
+
+        let resp = serde_json::from_str::<Response>(resp_line.trim())
+            .map_err(|e| format!("wrong response '{}': {e}", resp_line.trim()))?;
+
+        if resp.id != id {
+            return Err(Box::<dyn Error>::from(format!(
+                "wrong response id {}, expected {id}",
+                resp.id
+            )));
+        }
+
+        Ok(resp)
+    }
+}
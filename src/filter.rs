@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: MIT
 
+use crate::task::State;
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +12,17 @@ pub enum FilterState {
     Unknown,
 }
 
+impl From<&State> for FilterState {
+    fn from(s: &State) -> Self {
+        match s {
+            State::Completed => FilterState::Completed,
+            State::Uncompleted => FilterState::Uncompleted,
+            State::InProgress => FilterState::InProgress,
+            State::Unknown(_) => FilterState::Unknown,
+        }
+    }
+}
+
 impl std::fmt::Display for FilterState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
@@ -35,4 +47,12 @@ impl std::fmt::Display for Due {
 pub struct Filter {
     pub states: Vec<FilterState>,
     pub due: Vec<Due>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+impl Filter {
+    pub fn accept_labels(&self, labels: &[String]) -> bool {
+        self.labels.is_empty() || self.labels.iter().any(|l| labels.contains(l))
+    }
 }
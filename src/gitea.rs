@@ -0,0 +1,275 @@
+// SPDX-License-Identifier: MIT
+
+// Issues assigned to the authenticated user of a Gitea or Forgejo instance
+
+mod client;
+mod structs;
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::provider::Provider as ProviderTrait;
+use crate::task::{DateTimeUtc, State, Task as TaskTrait, due_group};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use client::{Client, IssuePatch};
+use itertools::Itertools;
+use ratatui::style::Color;
+use std::any::Any;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "Gitea";
+
+fn str_to_date(s: &str) -> Option<DateTimeUtc> {
+    if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let dt = d.and_hms_opt(0, 0, 0)?;
+        return Some(DateTimeUtc::from_naive_utc_and_offset(dt, Utc));
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(DateTimeUtc::from(dt));
+    }
+
+    None
+}
+
+#[derive(Clone)]
+pub struct Task {
+    issue: structs::Issue,
+    provider: String,
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        self.issue.id.to_string()
+    }
+
+    fn text(&self) -> String {
+        self.issue.title.to_string()
+    }
+
+    fn description(&self) -> Option<String> {
+        Some(self.issue.body.to_string()).filter(|b| !b.is_empty())
+    }
+
+    fn state(&self) -> State {
+        match self.issue.state.as_str() {
+            "open" => State::Uncompleted,
+            "closed" => State::Completed,
+            _ => State::Unknown('?'),
+        }
+    }
+
+    fn created_at(&self) -> Option<DateTimeUtc> {
+        str_to_date(self.issue.created_at.as_str())
+    }
+
+    fn updated_at(&self) -> Option<DateTimeUtc> {
+        str_to_date(self.issue.updated_at.as_str())
+    }
+
+    fn completed_at(&self) -> Option<DateTimeUtc> {
+        self.issue.closed_at.as_deref().and_then(str_to_date)
+    }
+
+    // The own deadline of the issue is more precise than the milestone's one
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.issue
+            .due_date
+            .as_deref()
+            .or(self.issue.milestone.as_ref().and_then(|m| m.due_on.as_deref()))
+            .and_then(str_to_date)
+    }
+
+    fn place(&self) -> String {
+        self.issue.html_url.to_string()
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.issue.labels.iter().map(|l| l.name.to_string()).collect()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        let mut p = self.issue.repository.clone();
+        p.provider = self.provider.to_string();
+        Some(Box::new(p))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+pub struct Provider {
+    name: String,
+    color: Color,
+    client: Client,
+    tasks: Vec<Task>,
+    last_filter: Option<filter::Filter>,
+}
+
+impl Provider {
+    pub fn new(name: &str, base_url: &str, api_key: &str, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            color: *color,
+            client: Client::new(base_url, api_key),
+            tasks: Vec::new(),
+            last_filter: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        if self.last_filter.as_ref().is_some_and(|last| last != f) {
+            self.tasks.clear();
+        }
+
+        if self.tasks.is_empty() {
+            for issue in self.client.assigned_issues(&f.states).await? {
+                self.tasks.push(Task {
+                    issue,
+                    provider: self.name(),
+                })
+            }
+        }
+
+        let project = project.map(|p| p.id());
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for t in &self.tasks {
+            if project
+                .as_ref()
+                .is_some_and(|p| *p != t.issue.repository.id.to_string())
+            {
+                continue;
+            }
+            if f.states.contains(&filter::FilterState::from(&t.state())) && f.due.contains(&due_group(t)) {
+                result.push(Box::new(t.clone()));
+            }
+        }
+
+        self.last_filter = Some(f.clone());
+
+        Ok(result)
+    }
+
+    // Repositories of the open assigned issues
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        let issues = self.client.assigned_issues(&[filter::FilterState::Uncompleted]).await?;
+
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for mut r in issues.into_iter().map(|i| i.repository).unique_by(|r| r.id) {
+            r.provider = self.name();
+            result.push(Box::new(r));
+        }
+        Ok(result)
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let t: &Task = match task.as_any().downcast_ref::<Task>() {
+            Some(t) => t,
+            None => panic!("Wrong casting!"),
+        };
+
+        let patch = match state {
+            State::Completed => IssuePatch {
+                state: Some("closed".to_string()),
+            },
+            State::Uncompleted => IssuePatch {
+                state: Some("open".to_string()),
+            },
+            State::InProgress | State::Unknown(_) => return Err(Box::<dyn Error>::from("wrong state")),
+        };
+
+        self.client
+            .patch_issue(&t.issue.repository.full_name, t.issue.number, &patch)
+            .await?;
+
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn reload(&mut self) {
+        self.tasks.clear();
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn due_test() {
+        struct Case<'a> {
+            name: &'a str,
+            due_date: Option<&'a str>,
+            milestone_due: Option<&'a str>,
+            expected: Option<&'a str>,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "no dates",
+                due_date: None,
+                milestone_due: None,
+                expected: None,
+            },
+            Case {
+                name: "milestone",
+                due_date: None,
+                milestone_due: Some("2025-02-01T00:00:00Z"),
+                expected: Some("2025-02-01"),
+            },
+            Case {
+                name: "own deadline",
+                due_date: Some("2025-01-25T00:00:00+01:00"),
+                milestone_due: Some("2025-02-01T00:00:00Z"),
+                expected: Some("2025-01-24"),
+            },
+        ];
+
+        for c in CASES {
+            let t = Task {
+                issue: structs::Issue {
+                    due_date: c.due_date.map(|d| d.to_string()),
+                    milestone: c.milestone_due.map(|d| structs::Milestone {
+                        title: "v1".to_string(),
+                        due_on: Some(d.to_string()),
+                    }),
+                    ..structs::Issue::default()
+                },
+                provider: String::new(),
+            };
+            assert_eq!(
+                t.due().map(|d| d.format("%Y-%m-%d").to_string()).as_deref(),
+                c.expected,
+                "Test '{}' was failed",
+                c.name
+            );
+        }
+    }
+}
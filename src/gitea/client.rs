@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: MIT
+
+use super::structs::Issue;
+use crate::filter::FilterState;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use std::error::Error;
+
+#[derive(Serialize, Default)]
+pub struct IssuePatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
+pub struct Client {
+    base_url: String,
+    default_header: HeaderMap,
+    client: reqwest::Client,
+}
+
+impl Client {
+    pub fn new(base_url: &str, api_key: &str) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert("Authorization", format!("token {api_key}").parse().unwrap());
+        headers.insert("Accept", "application/json".parse().unwrap());
+        Self {
+            base_url: format!("{}/api/v1", base_url.trim_end_matches('/')),
+            default_header: headers,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    // Issues of all repositories which are assigned to the authenticated user
+    pub async fn assigned_issues(&self, states: &[FilterState]) -> Result<Vec<Issue>, Box<dyn Error>> {
+        let mut result = Vec::new();
+
+        const PER_PAGE: i8 = 50;
+        let mut page = 1;
+
+        let has_open = states.contains(&FilterState::Uncompleted);
+        let has_closed = states.contains(&FilterState::Completed);
+        let state_query = match (has_open, has_closed) {
+            (true, false) => "state=open",
+            (false, true) => "state=closed",
+            (true, true) => "state=all",
+            (false, false) => return Ok(result),
+        };
+
+        loop {
+            let resp = self
+                .client
+                .get(format!(
+                    "{}/repos/issues/search?type=issues&assigned=true&page={page}&limit={PER_PAGE}&{state_query}",
+                    self.base_url
+                ))
+                .headers(self.default_header.clone())
+                .send()
+                .await?;
+            check_status("", resp.status())?;
+
+            let mut resp = resp.json::<Vec<Issue>>().await?;
+            if resp.is_empty() {
+                break;
+            }
+
+            result.append(&mut resp);
+            page += 1;
+        }
+
+        Ok(result)
+    }
+
+    pub async fn patch_issue(&self, repo: &str, number: i64, patch: &IssuePatch) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .client
+            .patch(format!("{}/repos/{repo}/issues/{number}", self.base_url))
+            .headers(self.default_header.clone())
+            .json(patch)
+            .send()
+            .await?;
+        check_status(repo, resp.status())
+    }
+}
+
+fn check_status(repo: &str, status: StatusCode) -> Result<(), Box<dyn Error>> {
+    match status {
+        s if s.is_success() => Ok(()),
+        StatusCode::UNAUTHORIZED => Err(Box::<dyn Error>::from("the token is invalid or expired")),
+        StatusCode::FORBIDDEN if !repo.is_empty() => Err(Box::<dyn Error>::from(format!(
+            "the token doesn't have write permission for the repository {repo}"
+        ))),
+        StatusCode::NOT_FOUND if !repo.is_empty() => Err(Box::<dyn Error>::from(format!(
+            "the issue isn't found in the repository {repo}"
+        ))),
+        s => Err(Box::<dyn Error>::from(format!("wrong status: {}", s.as_str()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn issue_json(id: i64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "number": id,
+            "title": format!("Issue {id}"),
+            "body": "",
+            "html_url": format!("https://git.example.com/owner/repo/issues/{id}"),
+            "state": "open",
+            "labels": [],
+            "milestone": null,
+            "due_date": null,
+            "created_at": "2025-01-20T10:00:00Z",
+            "updated_at": "2025-01-20T10:00:00Z",
+            "closed_at": null,
+            "repository": {"id": 1, "name": "repo", "owner": "owner", "full_name": "owner/repo"},
+        })
+    }
+
+    #[tokio::test]
+    async fn assigned_issues_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        let first = server
+            .mock("GET", "/api/v1/repos/issues/search")
+            .match_header("Authorization", "token secret")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("assigned".into(), "true".into()),
+                Matcher::UrlEncoded("state".into(), "open".into()),
+                Matcher::UrlEncoded("page".into(), "1".into()),
+            ]))
+            .with_body(serde_json::json!([issue_json(1), issue_json(2)]).to_string())
+            .create_async()
+            .await;
+        let last = server
+            .mock("GET", "/api/v1/repos/issues/search")
+            .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let c = Client::new(&format!("{}/", server.url()), "secret");
+        let issues = c.assigned_issues(&[FilterState::Uncompleted]).await.unwrap();
+        assert_eq!(issues.iter().map(|i| i.id).collect::<Vec<i64>>(), vec![1, 2]);
+        assert_eq!(issues[0].repository.full_name, "owner/repo");
+
+        first.assert_async().await;
+        last.assert_async().await;
+
+        assert!(c.assigned_issues(&[FilterState::InProgress]).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn check_status_test() {
+        assert!(check_status("owner/repo", StatusCode::OK).is_ok());
+
+        let err = check_status("owner/repo", StatusCode::FORBIDDEN).unwrap_err();
+        assert!(err.to_string().contains("owner/repo"), "{err}");
+
+        let err = check_status("owner/repo", StatusCode::NOT_FOUND).unwrap_err();
+        assert_eq!(err.to_string(), "the issue isn't found in the repository owner/repo");
+
+        let err = check_status("", StatusCode::NOT_FOUND).unwrap_err();
+        assert_eq!(err.to_string(), "wrong status: 404");
+
+        assert!(check_status("owner/repo", StatusCode::UNAUTHORIZED).is_err());
+    }
+}
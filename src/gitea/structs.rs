@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MIT
+
+use crate::project::Project as ProjectTrait;
+use serde::Deserialize;
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct Issue {
+    pub id: i64,
+    pub number: i64,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    pub html_url: String,
+    pub state: String,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    pub milestone: Option<Milestone>,
+    pub due_date: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_at: Option<String>,
+    pub repository: Repository,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct Milestone {
+    pub title: String,
+    pub due_on: Option<String>,
+}
+
+// The short form of a repository which is returned with issues
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+pub struct Repository {
+    pub id: i64,
+    pub name: String,
+    pub owner: String,
+    pub full_name: String,
+
+    #[serde(skip)]
+    pub provider: String,
+}
+
+impl ProjectTrait for Repository {
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.full_name.to_string()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        self.full_name.to_string()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        None
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
@@ -1,4 +1,5 @@
 // SPDX-License-Identifier: MIT
 
 pub mod client;
+pub mod project;
 pub mod structs;
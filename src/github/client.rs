@@ -2,10 +2,20 @@
 
 use crate::filter::FilterState;
 
-use super::structs::Issue;
+use super::structs::{Issue, SearchIssues, Thread};
+use reqwest::StatusCode;
 use reqwest::header::HeaderMap;
+use serde::Serialize;
 use std::error::Error;
 
+#[derive(Serialize, Default)]
+pub struct IssuePatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_reason: Option<String>,
+}
+
 pub struct Client {
     base_url: String,
     default_header: HeaderMap,
@@ -32,14 +42,12 @@ impl Client {
         const PER_PAGE: i8 = 100;
         let mut page = 1;
 
-        let state_query = if states.len() == 1 {
-            match states[0] {
-                FilterState::Completed => "state=closed".to_string(),
-                FilterState::Uncompleted => "state=open".to_string(),
-                _ => return Err(Box::<dyn Error>::from(format!("wrong state {}", states[0]))),
-            }
-        } else {
-            "state=all".to_string()
+        let has_open = states.contains(&FilterState::Uncompleted) || states.contains(&FilterState::InProgress);
+        let has_closed = states.contains(&FilterState::Completed) || states.contains(&FilterState::Unknown);
+        let state_query = match (has_open, has_closed) {
+            (true, false) => "state=open",
+            (false, true) => "state=closed",
+            _ => "state=all",
         };
 
         loop {
@@ -64,4 +72,168 @@ impl Client {
 
         Ok(result)
     }
+
+    // Unread notifications of the user, read ones are returned too when all is set
+    pub async fn notifications(&self, all: bool) -> Result<Vec<Thread>, Box<dyn Error>> {
+        let mut result = Vec::new();
+
+        const PER_PAGE: i8 = 50;
+        let mut page = 1;
+
+        loop {
+            let resp = self
+                .client
+                .get(format!(
+                    "{}/notifications?page={page}&per_page={PER_PAGE}&all={all}",
+                    self.base_url
+                ))
+                .headers(self.default_header.clone())
+                .send()
+                .await?;
+            check_status("", resp.status())?;
+
+            let mut resp = resp.json::<Vec<Thread>>().await?;
+            if resp.is_empty() {
+                break;
+            }
+
+            result.append(&mut resp);
+            page += 1;
+        }
+
+        Ok(result)
+    }
+
+    pub async fn mark_thread_as_read(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .client
+            .patch(format!("{}/notifications/threads/{id}", self.base_url))
+            .headers(self.default_header.clone())
+            .send()
+            .await?;
+        check_status("", resp.status())
+    }
+
+    pub async fn mark_thread_as_done(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .client
+            .delete(format!("{}/notifications/threads/{id}", self.base_url))
+            .headers(self.default_header.clone())
+            .send()
+            .await?;
+        check_status("", resp.status())
+    }
+
+    // The search API returns at most 1000 results for a query, the pages after them fail with 422
+    pub async fn search_issues(&self, query: &str) -> Result<Vec<Issue>, Box<dyn Error>> {
+        let mut result = Vec::new();
+
+        const PER_PAGE: i8 = 100;
+        const MAX_RESULTS: i64 = 1000;
+        let mut page = 1;
+
+        loop {
+            let resp = self
+                .client
+                .get(format!(
+                    "{}/search/issues?page={page}&per_page={PER_PAGE}&q={}",
+                    self.base_url,
+                    urlencoding::encode(query)
+                ))
+                .headers(self.default_header.clone())
+                .send()
+                .await?;
+            check_status("", resp.status())?;
+
+            let mut resp = resp.json::<SearchIssues>().await?;
+            if resp.items.is_empty() {
+                break;
+            }
+
+            result.append(&mut resp.items);
+            if result.len() as i64 >= resp.total_count.min(MAX_RESULTS) {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(result)
+    }
+
+    pub async fn patch_issue(&self, repo: &str, number: i64, patch: &IssuePatch) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .client
+            .patch(format!("{}/repos/{repo}/issues/{number}", self.base_url))
+            .headers(self.default_header.clone())
+            .json(patch)
+            .send()
+            .await?;
+        check_status(repo, resp.status())
+    }
+
+    pub async fn add_label(&self, repo: &str, number: i64, label: &str) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .client
+            .post(format!("{}/repos/{repo}/issues/{number}/labels", self.base_url))
+            .headers(self.default_header.clone())
+            .json(&serde_json::json!({ "labels": [label] }))
+            .send()
+            .await?;
+        check_status(repo, resp.status())
+    }
+
+    pub async fn remove_label(&self, repo: &str, number: i64, label: &str) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .client
+            .delete(format!(
+                "{}/repos/{repo}/issues/{number}/labels/{}",
+                self.base_url,
+                urlencoding::encode(label)
+            ))
+            .headers(self.default_header.clone())
+            .send()
+            .await?;
+        if resp.status() == StatusCode::NOT_FOUND {
+            // The label isn't set
+            return Ok(());
+        }
+        check_status(repo, resp.status())
+    }
+}
+
+fn check_status(repo: &str, status: StatusCode) -> Result<(), Box<dyn Error>> {
+    match status {
+        s if s.is_success() => Ok(()),
+        StatusCode::UNAUTHORIZED => Err(Box::<dyn Error>::from("the token is invalid or expired")),
+        StatusCode::FORBIDDEN if !repo.is_empty() => Err(Box::<dyn Error>::from(format!(
+            "the token doesn't have write permission for the repository {repo}"
+        ))),
+        StatusCode::NOT_FOUND if !repo.is_empty() => Err(Box::<dyn Error>::from(format!(
+            "the issue isn't found in the repository {repo}"
+        ))),
+        s => Err(Box::<dyn Error>::from(format!("wrong status: {}", s.as_str()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_status_test() {
+        assert!(check_status("owner/repo", StatusCode::OK).is_ok());
+        assert!(check_status("owner/repo", StatusCode::CREATED).is_ok());
+
+        let err = check_status("owner/repo", StatusCode::FORBIDDEN).unwrap_err();
+        assert!(err.to_string().contains("write permission"), "{err}");
+
+        let err = check_status("owner/repo", StatusCode::NOT_FOUND).unwrap_err();
+        assert_eq!(err.to_string(), "the issue isn't found in the repository owner/repo");
+
+        let err = check_status("", StatusCode::FORBIDDEN).unwrap_err();
+        assert_eq!(err.to_string(), "wrong status: 403");
+
+        assert!(check_status("owner/repo", StatusCode::UNAUTHORIZED).is_err());
+        assert!(check_status("owner/repo", StatusCode::INTERNAL_SERVER_ERROR).is_err());
+    }
 }
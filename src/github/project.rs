@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: MIT
+
+use crate::project::Project as ProjectTrait;
+
+// A repository, its full name (owner/repo) is used as the id
+#[derive(Clone)]
+pub struct Project {
+    pub full_name: String,
+    pub description: String,
+    pub provider: String,
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.full_name.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.full_name.to_string()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        self.description.to_string()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        None
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
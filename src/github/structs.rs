@@ -34,6 +34,17 @@ pub struct Issue {
     pub state_reason: Option<String>,
 }
 
+impl Issue {
+    // The full name of the repository (owner/repo)
+    pub fn repository(&self) -> String {
+        let path = self.repository_url.trim_end_matches('/');
+        match path.rsplitn(3, '/').collect::<Vec<&str>>().as_slice() {
+            [repo, owner, _] => format!("{owner}/{repo}"),
+            _ => path.to_string(),
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct User {
     pub login: String,
@@ -62,7 +73,7 @@ pub struct Label {
     pub node_id: String,
     pub url: String,
     pub name: String,
-    pub description: String,
+    pub description: Option<String>,
     pub color: String,
     pub default: bool,
 }
@@ -93,3 +104,39 @@ pub struct PullRequest {
     pub diff_url: String,
     pub patch_url: String,
 }
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchIssues {
+    pub total_count: i64,
+    pub items: Vec<Issue>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Thread {
+    pub id: String,
+    pub unread: bool,
+    pub reason: String,
+    pub updated_at: String,
+    pub last_read_at: Option<String>,
+    pub subject: Subject,
+    pub repository: Repository,
+    pub url: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subject {
+    pub title: String,
+    pub url: Option<String>,
+    pub latest_comment_url: Option<String>,
+    #[serde(rename = "type")]
+    pub type_field: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Repository {
+    pub id: i64,
+    pub name: String,
+    pub full_name: String,
+    pub html_url: String,
+    pub description: Option<String>,
+}
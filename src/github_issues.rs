@@ -1,13 +1,15 @@
 // SPDX-License-Identifier: MIT
 
 use crate::filter;
-use crate::github::client::Client;
+use crate::github::client::{Client, IssuePatch};
+use crate::github::project::Project;
 use crate::github::structs;
 use crate::project::Project as ProjectTrait;
 use crate::provider::Provider as ProviderTrait;
 use crate::task::due_group;
 use crate::task::{DateTimeUtc, State, Task as TaskTrait};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use itertools::Itertools;
 use ratatui::style::Color;
 use std::any::Any;
 use std::error::Error;
@@ -20,6 +22,13 @@ pub const PROVIDER_NAME: &str = "GitHub Issues";
 pub struct Task {
     issue: structs::Issue,
     provider: String,
+    in_progress_label: Option<String>,
+}
+
+impl Task {
+    fn has_label(&self, name: &str) -> bool {
+        self.issue.labels.iter().any(|l| l.name == name)
+    }
 }
 
 fn str_to_date(s: &str) -> Option<DateTimeUtc> {
@@ -66,18 +75,32 @@ impl TaskTrait for Task {
 
     fn state(&self) -> State {
         match self.issue.state.as_str() {
-            "open" => State::Uncompleted,
-            "closed" => State::Completed,
+            "open" => match &self.in_progress_label {
+                Some(l) if self.has_label(l) => State::InProgress,
+                _ => State::Uncompleted,
+            },
+            "closed" => match self.issue.state_reason.as_deref() {
+                Some("not_planned") => State::Unknown('-'),
+                _ => State::Completed,
+            },
             _ => panic!("Unknown state {}", self.issue.state),
         }
     }
 
+    fn labels(&self) -> Vec<String> {
+        self.issue.labels.iter().map(|l| l.name.to_string()).collect()
+    }
+
     fn provider(&self) -> String {
         self.provider.to_string()
     }
 
     fn project(&self) -> Option<Box<dyn ProjectTrait>> {
-        None
+        Some(Box::new(Project {
+            full_name: self.issue.repository(),
+            description: String::new(),
+            provider: self.provider.to_string(),
+        }))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -89,24 +112,136 @@ impl TaskTrait for Task {
     }
 }
 
+// Where the issues are loaded from, every item of the repository setting is either owner/repo or a whole owner
+#[derive(Debug, PartialEq)]
+enum Source {
+    Repository(String),
+    Owner(String),
+    Query(String),
+}
+
+fn parse_sources(repository: &str, query: Option<&str>) -> Vec<Source> {
+    let mut result: Vec<Source> = repository
+        .split(',')
+        .map(|r| r.trim())
+        .filter(|r| !r.is_empty())
+        .map(|r| match r.contains('/') {
+            true => Source::Repository(r.to_string()),
+            false => Source::Owner(r.to_string()),
+        })
+        .collect();
+    if let Some(q) = query.map(|q| q.trim()).filter(|q| !q.is_empty()) {
+        result.push(Source::Query(q.to_string()));
+    }
+    result
+}
+
+// Adds the qualifiers of the filter's states unless the query has its own ones
+fn search_query(query: &str, states: &[filter::FilterState], exclude_pull_requests: bool) -> String {
+    let mut result = query.to_string();
+
+    let has_state = query
+        .split_whitespace()
+        .any(|w| w == "is:open" || w == "is:closed" || w.starts_with("state:"));
+    if !has_state {
+        let has_open =
+            states.contains(&filter::FilterState::Uncompleted) || states.contains(&filter::FilterState::InProgress);
+        let has_closed =
+            states.contains(&filter::FilterState::Completed) || states.contains(&filter::FilterState::Unknown);
+        match (has_open, has_closed) {
+            (true, false) => result.push_str(" is:open"),
+            (false, true) => result.push_str(" is:closed"),
+            _ => {}
+        }
+    }
+
+    if exclude_pull_requests && !query.split_whitespace().any(|w| w == "is:issue" || w == "type:issue") {
+        result.push_str(" is:issue");
+    }
+
+    result
+}
+
 pub struct Provider {
     name: String,
     color: Color,
-    repo: String,
+    sources: Vec<Source>,
+    in_progress_label: Option<String>,
+    exclude_pull_requests: bool,
     client: Client,
     tasks: Vec<Task>,
     last_filter: Option<filter::Filter>,
 }
 
 impl Provider {
-    pub fn new(name: &str, api_key: &str, repo: &str, color: &Color) -> Self {
-        Self {
+    pub fn new(
+        name: &str,
+        api_key: &str,
+        repository: &str,
+        query: Option<&str>,
+        in_progress_label: Option<&str>,
+        exclude_pull_requests: bool,
+        color: &Color,
+    ) -> Result<Self, Box<dyn Error>> {
+        let sources = parse_sources(repository, query);
+        if sources.is_empty() {
+            return Err(Box::<dyn Error>::from("either repository or query should be set"));
+        }
+
+        Ok(Self {
             name: name.to_string(),
             color: *color,
-            repo: repo.to_string(),
+            sources,
+            in_progress_label: in_progress_label.filter(|l| !l.is_empty()).map(|l| l.to_string()),
+            exclude_pull_requests,
             client: Client::new(api_key),
             tasks: Vec::new(),
             last_filter: None,
+        })
+    }
+
+    async fn issues(&self, states: &[filter::FilterState]) -> Result<Vec<structs::Issue>, Box<dyn Error>> {
+        let mut result = Vec::new();
+        for s in &self.sources {
+            let issues = match s {
+                Source::Repository(repo) => self.client.issues(repo, states).await?,
+                Source::Owner(owner) => {
+                    let q = search_query(&format!("user:{owner}"), states, self.exclude_pull_requests);
+                    self.client.search_issues(&q).await?
+                }
+                Source::Query(q) => {
+                    let q = search_query(q, states, self.exclude_pull_requests);
+                    self.client.search_issues(&q).await?
+                }
+            };
+            result.extend(issues);
+        }
+
+        Ok(result
+            .into_iter()
+            .filter(|i| !self.exclude_pull_requests || i.pull_request.is_none())
+            .unique_by(|i| i.id)
+            .collect())
+    }
+
+    async fn set_in_progress_label(&self, t: &Task, is_set: bool) -> Result<(), Box<dyn Error>> {
+        let label = match &self.in_progress_label {
+            Some(l) => l,
+            None if is_set => {
+                return Err(Box::<dyn Error>::from(
+                    "the in progress state requires the in_progress_label option in the provider's settings",
+                ));
+            }
+            None => return Ok(()),
+        };
+
+        let repo = t.issue.repository();
+        if is_set {
+            self.client.add_label(&repo, t.issue.number, label).await
+        } else if t.has_label(label) {
+            self.client.remove_label(&repo, t.issue.number, label).await
+        } else {
+            Ok(())
         }
     }
 }
@@ -123,7 +258,7 @@ impl ProviderTrait for Provider {
 
     async fn tasks(
         &mut self,
-        _project: Option<Box<dyn ProjectTrait>>,
+        project: Option<Box<dyn ProjectTrait>>,
         f: &filter::Filter,
     ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
         let mut should_clear = false;
@@ -136,18 +271,23 @@ impl ProviderTrait for Provider {
         }
 
         if self.tasks.is_empty() {
-            for t in self.client.issues(&self.repo, &f.states).await? {
+            for t in self.issues(&f.states).await? {
                 self.tasks.push(Task {
                     issue: t,
                     provider: self.name(),
+                    in_progress_label: self.in_progress_label.clone(),
                 })
             }
         }
 
+        let project = project.map(|p| p.id());
         let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
 
         for t in &self.tasks {
-            if f.due.contains(&due_group(t)) {
+            if project.as_ref().is_some_and(|p| *p != t.issue.repository()) {
+                continue;
+            }
+            if f.states.contains(&filter::FilterState::from(&t.state())) && f.due.contains(&due_group(t)) {
                 result.push(Box::new(t.clone()));
             }
         }
@@ -157,11 +297,62 @@ impl ProviderTrait for Provider {
         Ok(result)
     }
 
+    // The configured repositories and the ones of the open issues of owners and queries
     async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
-        Ok(Vec::new())
+        let mut repos: Vec<String> = self
+            .sources
+            .iter()
+            .filter_map(|s| match s {
+                Source::Repository(r) => Some(r.to_string()),
+                _ => None,
+            })
+            .collect();
+        if self.sources.iter().any(|s| !matches!(s, Source::Repository(_))) {
+            let issues = self.issues(&[filter::FilterState::Uncompleted]).await?;
+            repos.extend(issues.iter().map(|i| i.repository()));
+        }
+
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for full_name in repos.into_iter().unique() {
+            result.push(Box::new(Project {
+                full_name,
+                description: String::new(),
+                provider: self.name(),
+            }));
+        }
+        Ok(result)
     }
 
-    async fn change_task_state(&mut self, _task: &dyn TaskTrait, _state: State) -> Result<(), Box<dyn Error>> {
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let t: &Task = match task.as_any().downcast_ref::<Task>() {
+            Some(t) => t,
+            None => panic!("Wrong casting!"),
+        };
+
+        let patch = match state {
+            State::Completed => Some(IssuePatch {
+                state: Some("closed".to_string()),
+                state_reason: Some("completed".to_string()),
+            }),
+            State::Unknown(_) => Some(IssuePatch {
+                state: Some("closed".to_string()),
+                state_reason: Some("not_planned".to_string()),
+            }),
+            State::Uncompleted | State::InProgress if t.issue.state == "open" => None,
+            State::Uncompleted | State::InProgress => Some(IssuePatch {
+                state: Some("open".to_string()),
+                state_reason: Some("reopened".to_string()),
+            }),
+        };
+
+        self.set_in_progress_label(t, state == State::InProgress).await?;
+        if let Some(p) = patch {
+            self.client
+                .patch_issue(&t.issue.repository(), t.issue.number, &p)
+                .await?;
+        }
+
+        self.tasks.clear();
         Ok(())
     }
 
@@ -173,3 +364,78 @@ impl ProviderTrait for Provider {
         self.color
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sources_test() {
+        assert_eq!(
+            parse_sources(" panter-dsd/tatuin, acme ,,", Some("is:open assignee:@me")),
+            vec![
+                Source::Repository("panter-dsd/tatuin".to_string()),
+                Source::Owner("acme".to_string()),
+                Source::Query("is:open assignee:@me".to_string()),
+            ]
+        );
+        assert!(parse_sources("", Some(" ")).is_empty());
+    }
+
+    #[test]
+    fn search_query_test() {
+        struct Case<'a> {
+            name: &'a str,
+            query: &'a str,
+            states: &'a [filter::FilterState],
+            exclude_pull_requests: bool,
+            expected: &'a str,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "open issues",
+                query: "org:acme",
+                states: &[filter::FilterState::Uncompleted, filter::FilterState::InProgress],
+                exclude_pull_requests: false,
+                expected: "org:acme is:open",
+            },
+            Case {
+                name: "all states",
+                query: "org:acme",
+                states: &[filter::FilterState::Uncompleted, filter::FilterState::Completed],
+                exclude_pull_requests: false,
+                expected: "org:acme",
+            },
+            Case {
+                name: "own state of the query",
+                query: "is:closed assignee:@me",
+                states: &[filter::FilterState::Uncompleted],
+                exclude_pull_requests: false,
+                expected: "is:closed assignee:@me",
+            },
+            Case {
+                name: "exclude pull requests",
+                query: "user:acme",
+                states: &[filter::FilterState::Completed],
+                exclude_pull_requests: true,
+                expected: "user:acme is:closed is:issue",
+            },
+            Case {
+                name: "exclude pull requests with the own type",
+                query: "is:issue user:acme",
+                states: &[filter::FilterState::Completed],
+                exclude_pull_requests: true,
+                expected: "is:issue user:acme is:closed",
+            },
+        ];
+
+        for c in CASES {
+            assert_eq!(
+                search_query(c.query, c.states, c.exclude_pull_requests),
+                c.expected,
+                "Test '{}' was failed",
+                c.name
+            );
+        }
+    }
+}
@@ -0,0 +1,429 @@
+// SPDX-License-Identifier: MIT
+
+use crate::filter;
+use crate::github::client::Client;
+use crate::github::project::Project;
+use crate::github::structs;
+use crate::project::Project as ProjectTrait;
+use crate::provider::Provider as ProviderTrait;
+use crate::task::{DateTimeUtc, State, Task as TaskTrait, due_group};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveTime};
+use itertools::Itertools;
+use ratatui::style::Color;
+use std::any::Any;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "GitHub Notifications";
+
+const REVIEW_REQUESTS_QUERY: &str = "is:open is:pr review-requested:@me archived:false";
+const REVIEW_REQUESTED_REASON: &str = "review_requested";
+
+#[derive(Clone)]
+enum Item {
+    Notification(Box<structs::Thread>),
+    // A pull request which waits for the user's review
+    ReviewRequest(Box<structs::Issue>),
+}
+
+#[derive(Clone)]
+pub struct Task {
+    item: Item,
+    provider: String,
+}
+
+// Notifications point to the API, e.g. https://api.github.com/repos/owner/repo/pulls/1
+fn api_url_to_html_url(url: &str) -> Option<String> {
+    let path = url.strip_prefix("https://api.github.com/repos/")?;
+    let parts: Vec<&str> = path.split('/').collect();
+    match parts.as_slice() {
+        [owner, repo, "pulls", number] => Some(format!("https://github.com/{owner}/{repo}/pull/{number}")),
+        [owner, repo, "issues", number] => Some(format!("https://github.com/{owner}/{repo}/issues/{number}")),
+        [owner, repo, "commits", sha] => Some(format!("https://github.com/{owner}/{repo}/commit/{sha}")),
+        [owner, repo, "discussions", number] => Some(format!("https://github.com/{owner}/{repo}/discussions/{number}")),
+        _ => None,
+    }
+}
+
+impl Task {
+    fn repository(&self) -> String {
+        match &self.item {
+            Item::Notification(t) => t.repository.full_name.to_string(),
+            Item::ReviewRequest(i) => i.repository(),
+        }
+    }
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        match &self.item {
+            Item::Notification(t) => format!("thread-{}", t.id),
+            Item::ReviewRequest(i) => format!("pr-{}", i.id),
+        }
+    }
+
+    fn text(&self) -> String {
+        match &self.item {
+            Item::Notification(t) => t.subject.title.to_string(),
+            Item::ReviewRequest(i) => i.title.to_string(),
+        }
+    }
+
+    fn updated_at(&self) -> Option<DateTimeUtc> {
+        let s = match &self.item {
+            Item::Notification(t) => &t.updated_at,
+            Item::ReviewRequest(i) => &i.updated_at,
+        };
+        DateTime::parse_from_rfc3339(s).ok().map(DateTimeUtc::from)
+    }
+
+    // Like GitLab TODOs, the items are due on the day of the last activity
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.updated_at()
+            .map(|dt| dt.with_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap()).unwrap())
+    }
+
+    fn place(&self) -> String {
+        match &self.item {
+            Item::Notification(t) => t
+                .subject
+                .url
+                .as_deref()
+                .and_then(api_url_to_html_url)
+                .unwrap_or(t.repository.html_url.to_string()),
+            Item::ReviewRequest(i) => i.html_url.to_string(),
+        }
+    }
+
+    fn state(&self) -> State {
+        match &self.item {
+            Item::Notification(t) if !t.unread => State::Completed,
+            _ => State::Uncompleted,
+        }
+    }
+
+    fn labels(&self) -> Vec<String> {
+        match &self.item {
+            Item::Notification(t) => vec![t.reason.to_string()],
+            Item::ReviewRequest(_) => vec![REVIEW_REQUESTED_REASON.to_string()],
+        }
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        let description = match &self.item {
+            Item::Notification(t) => t.repository.description.clone().unwrap_or_default(),
+            Item::ReviewRequest(_) => String::new(),
+        };
+        Some(Box::new(Project {
+            full_name: self.repository(),
+            description,
+            provider: self.provider.to_string(),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+// A review request usually comes with a notification, the pull request is shown once then
+fn merge_items(threads: Vec<structs::Thread>, review_requests: Vec<structs::Issue>) -> Vec<Item> {
+    let urls: Vec<String> = threads
+        .iter()
+        .filter_map(|t| t.subject.url.as_deref().and_then(api_url_to_html_url))
+        .collect();
+
+    let mut result: Vec<Item> = threads.into_iter().map(|t| Item::Notification(Box::new(t))).collect();
+    for i in review_requests {
+        if !urls.contains(&i.html_url) {
+            result.push(Item::ReviewRequest(Box::new(i)));
+        }
+    }
+    result
+}
+
+pub struct Provider {
+    name: String,
+    color: Color,
+    // Completed notifications are marked as done instead of read, they disappear from the inbox then
+    mark_as_done: bool,
+    client: Client,
+    tasks: Vec<Task>,
+    last_filter: Option<filter::Filter>,
+}
+
+impl Provider {
+    pub fn new(name: &str, api_key: &str, mark_as_done: bool, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            color: *color,
+            mark_as_done,
+            client: Client::new(api_key),
+            tasks: Vec::new(),
+            last_filter: None,
+        }
+    }
+
+    async fn load(&self, f: &filter::Filter) -> Result<Vec<Task>, Box<dyn Error>> {
+        if !f.states.contains(&filter::FilterState::Uncompleted) && !f.states.contains(&filter::FilterState::Completed)
+        {
+            return Ok(Vec::new());
+        }
+
+        let threads = self
+            .client
+            .notifications(f.states.contains(&filter::FilterState::Completed))
+            .await?;
+        let review_requests = if f.states.contains(&filter::FilterState::Uncompleted) {
+            self.client.search_issues(REVIEW_REQUESTS_QUERY).await?
+        } else {
+            Vec::new()
+        };
+
+        Ok(merge_items(threads, review_requests)
+            .into_iter()
+            .map(|item| Task {
+                item,
+                provider: self.name(),
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        if self.last_filter.as_ref().is_some_and(|last| last != f) {
+            self.tasks.clear();
+        }
+
+        if self.tasks.is_empty() {
+            self.tasks = self.load(f).await?;
+        }
+
+        let project = project.map(|p| p.id());
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for t in &self.tasks {
+            if project.as_ref().is_some_and(|p| *p != t.repository()) {
+                continue;
+            }
+            if f.states.contains(&filter::FilterState::from(&t.state())) && f.due.contains(&due_group(t)) {
+                result.push(Box::new(t.clone()));
+            }
+        }
+
+        self.last_filter = Some(f.clone());
+
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        let f = self.last_filter.clone().unwrap_or(filter::Filter {
+            states: vec![filter::FilterState::Uncompleted],
+            due: vec![
+                filter::Due::Overdue,
+                filter::Due::Today,
+                filter::Due::Future,
+                filter::Due::NoDate,
+            ],
+            labels: Vec::new(),
+        });
+
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for t in self.load(&f).await?.iter().unique_by(|t| t.repository()) {
+            if let Some(p) = t.project() {
+                result.push(p);
+            }
+        }
+        Ok(result)
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let t: &Task = match task.as_any().downcast_ref::<Task>() {
+            Some(t) => t,
+            None => panic!("Wrong casting!"),
+        };
+
+        let thread = match (&t.item, &state) {
+            (Item::Notification(thread), State::Completed) => thread,
+            (Item::ReviewRequest(_), State::Completed) => {
+                return Err(Box::<dyn Error>::from(
+                    "the review request is completed by reviewing the pull request",
+                ));
+            }
+            _ => return Err(Box::<dyn Error>::from("wrong state")),
+        };
+
+        if self.mark_as_done {
+            self.client.mark_thread_as_done(&thread.id).await?;
+        } else {
+            self.client.mark_thread_as_read(&thread.id).await?;
+        }
+
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        let t: &Task = match task.as_any().downcast_ref::<Task>() {
+            Some(t) => t,
+            None => panic!("Wrong casting!"),
+        };
+
+        // GitHub doesn't delete notifications, marking as done removes them from the inbox
+        match &t.item {
+            Item::Notification(thread) => self.client.mark_thread_as_done(&thread.id).await?,
+            Item::ReviewRequest(_) => return Err(Box::<dyn Error>::from("review requests can't be deleted")),
+        }
+
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn reload(&mut self) {
+        self.tasks.clear();
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_url_to_html_url_test() {
+        struct Case<'a> {
+            name: &'a str,
+            url: &'a str,
+            expected: Option<&'a str>,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "pull request",
+                url: "https://api.github.com/repos/owner/repo/pulls/12",
+                expected: Some("https://github.com/owner/repo/pull/12"),
+            },
+            Case {
+                name: "issue",
+                url: "https://api.github.com/repos/owner/repo/issues/3",
+                expected: Some("https://github.com/owner/repo/issues/3"),
+            },
+            Case {
+                name: "commit",
+                url: "https://api.github.com/repos/owner/repo/commits/abc",
+                expected: Some("https://github.com/owner/repo/commit/abc"),
+            },
+            Case {
+                name: "release",
+                url: "https://api.github.com/repos/owner/repo/releases/1",
+                expected: None,
+            },
+            Case {
+                name: "another host",
+                url: "https://example.com/repos/owner/repo/pulls/12",
+                expected: None,
+            },
+        ];
+
+        for c in CASES {
+            assert_eq!(
+                api_url_to_html_url(c.url).as_deref(),
+                c.expected,
+                "Test '{}' was failed",
+                c.name
+            );
+        }
+    }
+
+    #[test]
+    fn merge_items_test() {
+        let thread = structs::Thread {
+            id: "1".to_string(),
+            unread: true,
+            reason: REVIEW_REQUESTED_REASON.to_string(),
+            subject: structs::Subject {
+                url: Some("https://api.github.com/repos/owner/repo/pulls/12".to_string()),
+                ..structs::Subject::default()
+            },
+            ..structs::Thread::default()
+        };
+        let pr = |number: i64| structs::Issue {
+            id: number,
+            number,
+            html_url: format!("https://github.com/owner/repo/pull/{number}"),
+            repository_url: "https://api.github.com/repos/owner/repo".to_string(),
+            ..issue()
+        };
+
+        let items = merge_items(vec![thread], vec![pr(12), pr(13)]);
+        let tasks: Vec<Task> = items
+            .into_iter()
+            .map(|item| Task {
+                item,
+                provider: String::new(),
+            })
+            .collect();
+
+        assert_eq!(
+            tasks.iter().map(|t| t.id()).collect::<Vec<String>>(),
+            vec!["thread-1", "pr-13"]
+        );
+        assert_eq!(tasks[1].repository(), "owner/repo");
+        assert_eq!(tasks[0].place(), "https://github.com/owner/repo/pull/12");
+    }
+
+    fn issue() -> structs::Issue {
+        structs::Issue {
+            id: 0,
+            node_id: String::new(),
+            url: String::new(),
+            repository_url: String::new(),
+            labels_url: String::new(),
+            comments_url: String::new(),
+            events_url: String::new(),
+            html_url: String::new(),
+            number: 0,
+            state: "open".to_string(),
+            title: String::new(),
+            body: None,
+            user: structs::User::default(),
+            labels: Vec::new(),
+            assignee: None,
+            assignees: Vec::new(),
+            milestone: None,
+            locked: false,
+            active_lock_reason: None,
+            comments: 0,
+            pull_request: None,
+            closed_at: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            closed_by: None,
+            author_association: String::new(),
+            state_reason: None,
+        }
+    }
+}
@@ -0,0 +1,593 @@
+// SPDX-License-Identifier: MIT
+
+// Items of a GitHub Projects (v2) board. The columns of the Status field are mapped to the states,
+// iterations are shown as projects.
+
+mod client;
+mod structs;
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::provider::Provider as ProviderTrait;
+use crate::task::{DateTimeUtc, State, Task as TaskTrait, due_group};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use client::Client;
+use ratatui::style::Color;
+use std::any::Any;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "GitHub Projects";
+pub const DEFAULT_URL: &str = client::DEFAULT_URL;
+const DEFAULT_STATUS_FIELD: &str = "Status";
+
+const SINGLE_SELECT_TYPE: &str = "SINGLE_SELECT";
+const ITERATION_TYPE: &str = "ITERATION";
+
+fn str_to_date(s: &str) -> Option<DateTimeUtc> {
+    if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let dt = d.and_hms_opt(0, 0, 0)?;
+        return Some(DateTimeUtc::from_naive_utc_and_offset(dt, Utc));
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(DateTimeUtc::from(dt));
+    }
+
+    None
+}
+
+// Names of the Status columns for every state, the first one is used to move a card
+pub struct Columns {
+    pub todo: Vec<String>,
+    pub in_progress: Vec<String>,
+    pub done: Vec<String>,
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self {
+            todo: vec!["Todo".to_string()],
+            in_progress: vec!["In Progress".to_string()],
+            done: vec!["Done".to_string()],
+        }
+    }
+}
+
+impl Columns {
+    // Cards without a status are new ones
+    fn state(&self, column: Option<&str>) -> State {
+        let column = match column {
+            Some(c) => c,
+            None => return State::Uncompleted,
+        };
+
+        let contains = |columns: &[String]| columns.iter().any(|c| c.eq_ignore_ascii_case(column));
+        if contains(&self.todo) {
+            State::Uncompleted
+        } else if contains(&self.in_progress) {
+            State::InProgress
+        } else if contains(&self.done) {
+            State::Completed
+        } else {
+            State::Unknown('?')
+        }
+    }
+
+    fn column(&self, state: &State) -> Option<&str> {
+        match state {
+            State::Uncompleted => self.todo.first(),
+            State::InProgress => self.in_progress.first(),
+            State::Completed => self.done.first(),
+            State::Unknown(_) => None,
+        }
+        .map(|c| c.as_str())
+    }
+}
+
+#[derive(Clone)]
+pub struct Project {
+    iteration: structs::Iteration,
+    provider: String,
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.iteration.id.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.iteration.title.to_string()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        format!("{}, {} days", self.iteration.start_date, self.iteration.duration)
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        None
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct Task {
+    item: structs::Item,
+    state: State,
+    due_field: Option<String>,
+    iteration: Option<structs::Iteration>,
+    board_url: String,
+    provider: String,
+}
+
+impl Task {
+    fn value(&self, field: &str) -> Option<&structs::FieldValue> {
+        self.item.field_values.nodes.iter().find(|v| {
+            v.field
+                .as_ref()
+                .and_then(|f| f.name.as_deref())
+                .is_some_and(|n| n.eq_ignore_ascii_case(field))
+        })
+    }
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        self.item.id.to_string()
+    }
+
+    fn text(&self) -> String {
+        self.item
+            .content
+            .as_ref()
+            .and_then(|c| c.title.clone())
+            .unwrap_or_default()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.item
+            .content
+            .as_ref()
+            .and_then(|c| c.body.clone())
+            .filter(|b| !b.is_empty())
+    }
+
+    fn state(&self) -> State {
+        self.state.clone()
+    }
+
+    fn created_at(&self) -> Option<DateTimeUtc> {
+        self.item.created_at.as_deref().and_then(str_to_date)
+    }
+
+    fn updated_at(&self) -> Option<DateTimeUtc> {
+        self.item.updated_at.as_deref().and_then(str_to_date)
+    }
+
+    // The configured date field or the first date of the card
+    fn due(&self) -> Option<DateTimeUtc> {
+        let value = match &self.due_field {
+            Some(f) => self.value(f).and_then(|v| v.date.as_deref()),
+            None => self.item.field_values.nodes.iter().find_map(|v| v.date.as_deref()),
+        };
+        value.and_then(str_to_date)
+    }
+
+    // Draft issues don't have their own page
+    fn place(&self) -> String {
+        self.item
+            .content
+            .as_ref()
+            .and_then(|c| c.url.clone())
+            .unwrap_or(self.board_url.to_string())
+    }
+
+    fn labels(&self) -> Vec<String> {
+        match self.item.content.as_ref().map(|c| c.typename.as_str()) {
+            Some("DraftIssue") => vec!["draft".to_string()],
+            Some("PullRequest") => vec!["pull_request".to_string()],
+            _ => Vec::new(),
+        }
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        self.iteration.as_ref().map(|i| {
+            Box::new(Project {
+                iteration: i.clone(),
+                provider: self.provider.to_string(),
+            }) as Box<dyn ProjectTrait>
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Default)]
+pub struct Config {
+    // GraphQL endpoint, it differs for GitHub Enterprise Server
+    pub url: Option<String>,
+    pub api_key: String,
+    // A user or an organization
+    pub owner: String,
+    pub number: i64,
+    pub status_field: Option<String>,
+    pub due_field: Option<String>,
+    pub columns: Columns,
+}
+
+pub struct Provider {
+    name: String,
+    color: Color,
+    config: Config,
+    client: Client,
+    board: Option<structs::ProjectV2>,
+    tasks: Vec<Task>,
+    last_filter: Option<filter::Filter>,
+}
+
+impl Provider {
+    pub fn new(name: &str, config: Config, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            color: *color,
+            client: Client::new(config.url.as_deref().unwrap_or(DEFAULT_URL), &config.api_key),
+            config,
+            board: None,
+            tasks: Vec::new(),
+            last_filter: None,
+        }
+    }
+
+    fn status_field_name(&self) -> &str {
+        self.config.status_field.as_deref().unwrap_or(DEFAULT_STATUS_FIELD)
+    }
+
+    async fn board(&mut self) -> Result<structs::ProjectV2, Box<dyn Error>> {
+        if let Some(b) = &self.board {
+            return Ok(b.clone());
+        }
+
+        let b = self.client.project(&self.config.owner, self.config.number).await?;
+        self.board = Some(b.clone());
+        Ok(b)
+    }
+
+    fn iterations(board: &structs::ProjectV2) -> Vec<structs::Iteration> {
+        board
+            .fields
+            .nodes
+            .iter()
+            .filter(|f| f.data_type.as_deref() == Some(ITERATION_TYPE))
+            .filter_map(|f| f.configuration.as_ref())
+            .flat_map(|c| c.iterations.iter().chain(c.completed_iterations.iter()).cloned())
+            .collect()
+    }
+
+    async fn load(&mut self) -> Result<Vec<Task>, Box<dyn Error>> {
+        let board = self.board().await?;
+        let iterations = Self::iterations(&board);
+        let status_field = self.status_field_name().to_string();
+
+        let mut result = Vec::new();
+        for item in self.client.items(&board.id).await? {
+            let mut t = Task {
+                item,
+                state: State::Uncompleted,
+                due_field: self.config.due_field.clone(),
+                iteration: None,
+                board_url: board.url.to_string(),
+                provider: self.name(),
+            };
+            t.state = self
+                .config
+                .columns
+                .state(t.value(&status_field).and_then(|v| v.name.as_deref()));
+            t.iteration = t
+                .item
+                .field_values
+                .nodes
+                .iter()
+                .find_map(|v| v.iteration_id.as_ref())
+                .and_then(|id| iterations.iter().find(|i| i.id == *id).cloned());
+            result.push(t);
+        }
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        if self.last_filter.as_ref().is_some_and(|last| last != f) {
+            self.tasks.clear();
+        }
+
+        if self.tasks.is_empty() {
+            self.tasks = self.load().await?;
+        }
+
+        let project = project.map(|p| p.id());
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for t in &self.tasks {
+            if project
+                .as_ref()
+                .is_some_and(|p| t.iteration.as_ref().is_none_or(|i| i.id != *p))
+            {
+                continue;
+            }
+            if f.states.contains(&filter::FilterState::from(&t.state())) && f.due.contains(&due_group(t)) {
+                result.push(Box::new(t.clone()));
+            }
+        }
+
+        self.last_filter = Some(f.clone());
+
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        let board = self.board().await?;
+
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for iteration in Self::iterations(&board) {
+            result.push(Box::new(Project {
+                iteration,
+                provider: self.name(),
+            }));
+        }
+        Ok(result)
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let t: &Task = match task.as_any().downcast_ref::<Task>() {
+            Some(t) => t,
+            None => panic!("Wrong casting!"),
+        };
+
+        let column = match self.config.columns.column(&state) {
+            Some(c) => c.to_string(),
+            None => {
+                return Err(Box::<dyn Error>::from(format!(
+                    "there is no column for the state {state:?}"
+                )));
+            }
+        };
+
+        let board = self.board().await?;
+        let status_field = self.status_field_name();
+        let field = board
+            .fields
+            .nodes
+            .iter()
+            .find(|f| {
+                f.data_type.as_deref() == Some(SINGLE_SELECT_TYPE)
+                    && f.name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(status_field))
+            })
+            .ok_or_else(|| Box::<dyn Error>::from(format!("the board doesn't have the field {status_field}")))?;
+        let field_id = field.id.clone().unwrap_or_default();
+        let option_id = field
+            .options
+            .iter()
+            .flatten()
+            .find(|o| o.name.eq_ignore_ascii_case(&column))
+            .map(|o| o.id.to_string())
+            .ok_or_else(|| {
+                Box::<dyn Error>::from(format!("the field {status_field} doesn't have the column {column}"))
+            })?;
+
+        self.client
+            .set_option(&board.id, &t.item.id, &field_id, &option_id)
+            .await?;
+
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn reload(&mut self) {
+        self.board = None;
+        self.tasks.clear();
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    #[test]
+    fn columns_test() {
+        let columns = Columns {
+            todo: vec!["Backlog".to_string(), "Todo".to_string()],
+            in_progress: vec!["In Progress".to_string(), "Review".to_string()],
+            done: vec!["Done".to_string()],
+        };
+
+        struct Case<'a> {
+            name: &'a str,
+            column: Option<&'a str>,
+            expected: State,
+        }
+        let cases: &[Case] = &[
+            Case {
+                name: "no status",
+                column: None,
+                expected: State::Uncompleted,
+            },
+            Case {
+                name: "todo",
+                column: Some("todo"),
+                expected: State::Uncompleted,
+            },
+            Case {
+                name: "in progress",
+                column: Some("Review"),
+                expected: State::InProgress,
+            },
+            Case {
+                name: "done",
+                column: Some("Done"),
+                expected: State::Completed,
+            },
+            Case {
+                name: "unknown column",
+                column: Some("Won't do"),
+                expected: State::Unknown('?'),
+            },
+        ];
+
+        for c in cases {
+            assert_eq!(columns.state(c.column), c.expected, "Test '{}' was failed", c.name);
+        }
+
+        assert_eq!(columns.column(&State::Uncompleted), Some("Backlog"));
+        assert_eq!(columns.column(&State::Unknown('?')), None);
+    }
+
+    #[tokio::test]
+    async fn board_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        server
+            .mock("POST", "/graphql")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"variables": {"owner": "acme", "number": 3}}),
+            ))
+            .with_body(
+                serde_json::json!({"data": {"repositoryOwner": {"projectV2": {
+                    "id": "PVT_1",
+                                        "url": "https://github.com/orgs/acme/projects/3",
+                    "fields": {"nodes": [
+                        {"id": "F_TITLE", "name": "Title", "dataType": "TITLE"},
+                        {"id": "F_STATUS", "name": "Status", "dataType": "SINGLE_SELECT", "options": [
+                            {"id": "O_TODO", "name": "Todo"},
+                            {"id": "O_PROGRESS", "name": "In Progress"},
+                            {"id": "O_DONE", "name": "Done"},
+                        ]},
+                        {"id": "F_DUE", "name": "Deadline", "dataType": "DATE"},
+                        {"id": "F_SPRINT", "name": "Sprint", "dataType": "ITERATION", "configuration": {
+                            "iterations": [{"id": "I_2", "title": "Sprint 2", "startDate": "2025-01-20", "duration": 14}],
+                            "completedIterations": [{"id": "I_1", "title": "Sprint 1", "startDate": "2025-01-06", "duration": 14}],
+                        }},
+                    ]},
+                }}}})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/graphql")
+            .match_body(Matcher::PartialJson(serde_json::json!({"variables": {"id": "PVT_1"}})))
+            .with_body(
+                serde_json::json!({"data": {"node": {"items": {
+                    "pageInfo": {"hasNextPage": false, "endCursor": null},
+                    "nodes": [{
+                        "id": "PVTI_1",
+                        "content": {"__typename": "DraftIssue", "title": "Write the docs", "body": ""},
+                        "fieldValues": {"nodes": [
+                            {},
+                            {"name": "In Progress", "field": {"name": "Status"}},
+                            {"date": "2025-01-27", "field": {"name": "Deadline"}},
+                            {"iterationId": "I_2", "field": {"name": "Sprint"}},
+                        ]},
+                    }],
+                }}}})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let move_card = server
+            .mock("POST", "/graphql")
+            .match_body(Matcher::PartialJson(serde_json::json!({"variables": {
+                "project": "PVT_1",
+                "item": "PVTI_1",
+                "field": "F_STATUS",
+                "option": "O_DONE",
+            }})))
+            .with_body(
+                serde_json::json!({"data": {"updateProjectV2ItemFieldValue": {"projectV2Item": {"id": "PVTI_1"}}}})
+                    .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let config = Config {
+            url: Some(format!("{}/graphql", server.url())),
+            api_key: "token".to_string(),
+            owner: "acme".to_string(),
+            number: 3,
+            ..Config::default()
+        };
+        let mut p = Provider::new("Board", config, &Color::Blue);
+
+        let projects = p.projects().await.unwrap();
+        assert_eq!(
+            projects.iter().map(|p| p.name()).collect::<Vec<String>>(),
+            vec!["Sprint 2", "Sprint 1"]
+        );
+
+        let f = filter::Filter {
+            states: vec![filter::FilterState::InProgress],
+            due: vec![filter::Due::Overdue],
+            labels: Vec::new(),
+        };
+        let tasks = p.tasks(Some(projects[0].clone_boxed()), &f).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+
+        let t = &tasks[0];
+        assert_eq!(t.text(), "Write the docs");
+        assert_eq!(t.state(), State::InProgress);
+        assert_eq!(
+            t.due().map(|d| d.format("%Y-%m-%d").to_string()).as_deref(),
+            Some("2025-01-27")
+        );
+        assert_eq!(t.place(), "https://github.com/orgs/acme/projects/3");
+        assert_eq!(t.project().unwrap().id(), "I_2");
+
+        assert!(p.tasks(Some(projects[1].clone_boxed()), &f).await.unwrap().is_empty());
+
+        p.change_task_state(t.as_ref(), State::Completed).await.unwrap();
+        move_card.assert_async().await;
+    }
+}
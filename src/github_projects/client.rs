@@ -0,0 +1,252 @@
+// SPDX-License-Identifier: MIT
+
+use super::structs::{Item, ItemsData, ProjectData, ProjectV2, Response};
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+use std::error::Error;
+
+pub const DEFAULT_URL: &str = "https://api.github.com/graphql";
+
+const PROJECT_QUERY: &str = r#"
+query($owner: String!, $number: Int!) {
+  repositoryOwner(login: $owner) {
+    ... on ProjectV2Owner {
+      projectV2(number: $number) {
+        id
+        url
+        fields(first: 50) {
+          nodes {
+            ... on ProjectV2FieldCommon { id name dataType }
+            ... on ProjectV2SingleSelectField { options { id name } }
+            ... on ProjectV2IterationField {
+              configuration {
+                iterations { id title startDate duration }
+                completedIterations { id title startDate duration }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+const ITEMS_QUERY: &str = r#"
+query($id: ID!, $cursor: String) {
+  node(id: $id) {
+    ... on ProjectV2 {
+      items(first: 100, after: $cursor) {
+        pageInfo { hasNextPage endCursor }
+        nodes {
+          id
+          createdAt
+          updatedAt
+          content {
+            __typename
+            ... on DraftIssue { title body }
+            ... on Issue { title body url }
+            ... on PullRequest { title body url }
+          }
+          fieldValues(first: 30) {
+            nodes {
+              ... on ProjectV2ItemFieldSingleSelectValue {
+                name
+                field { ... on ProjectV2FieldCommon { name } }
+              }
+              ... on ProjectV2ItemFieldDateValue {
+                date
+                field { ... on ProjectV2FieldCommon { name } }
+              }
+              ... on ProjectV2ItemFieldIterationValue {
+                iterationId
+                field { ... on ProjectV2FieldCommon { name } }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+const SET_OPTION_MUTATION: &str = r#"
+mutation($project: ID!, $item: ID!, $field: ID!, $option: String!) {
+  updateProjectV2ItemFieldValue(
+    input: {projectId: $project, itemId: $item, fieldId: $field, value: {singleSelectOptionId: $option}}
+  ) {
+    projectV2Item { id }
+  }
+}"#;
+
+pub struct Client {
+    url: String,
+    default_header: HeaderMap,
+    client: reqwest::Client,
+}
+
+impl Client {
+    pub fn new(url: &str, api_key: &str) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert("Authorization", format!("Bearer {api_key}").parse().unwrap());
+        headers.insert("User-Agent", "Tatuin".parse().unwrap());
+        Self {
+            url: url.to_string(),
+            default_header: headers,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn query<T: DeserializeOwned>(&self, query: &str, variables: serde_json::Value) -> Result<T, Box<dyn Error>> {
+        let resp = self
+            .client
+            .post(&self.url)
+            .headers(self.default_header.clone())
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await?;
+        match resp.status() {
+            s if s.is_success() => {}
+            StatusCode::UNAUTHORIZED => return Err(Box::<dyn Error>::from("the token is invalid or expired")),
+            s => return Err(Box::<dyn Error>::from(format!("wrong status: {}", s.as_str()))),
+        }
+
+        // GraphQL reports the errors with the 200 status
+        let resp = resp.json::<Response<T>>().await?;
+        if !resp.errors.is_empty() {
+            let messages: Vec<String> = resp.errors.into_iter().map(|e| e.message).collect();
+            return Err(Box::<dyn Error>::from(messages.join(", ")));
+        }
+
+        resp.data
+            .ok_or_else(|| Box::<dyn Error>::from("the response doesn't have data"))
+    }
+
+    pub async fn project(&self, owner: &str, number: i64) -> Result<ProjectV2, Box<dyn Error>> {
+        let data: ProjectData = self
+            .query(PROJECT_QUERY, serde_json::json!({ "owner": owner, "number": number }))
+            .await?;
+        data.repository_owner
+            .and_then(|o| o.project_v2)
+            .ok_or_else(|| Box::<dyn Error>::from(format!("the project {owner}/{number} isn't found")))
+    }
+
+    pub async fn items(&self, project_id: &str) -> Result<Vec<Item>, Box<dyn Error>> {
+        let mut result = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let data: ItemsData = self
+                .query(ITEMS_QUERY, serde_json::json!({ "id": project_id, "cursor": cursor }))
+                .await?;
+            let items = match data.node {
+                Some(n) => n.items,
+                None => return Err(Box::<dyn Error>::from(format!("the project {project_id} isn't found"))),
+            };
+
+            result.extend(items.nodes);
+            if !items.page_info.has_next_page {
+                break;
+            }
+            cursor = items.page_info.end_cursor;
+        }
+
+        Ok(result)
+    }
+
+    pub async fn set_option(
+        &self,
+        project_id: &str,
+        item_id: &str,
+        field_id: &str,
+        option_id: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let _: serde_json::Value = self
+            .query(
+                SET_OPTION_MUTATION,
+                serde_json::json!({
+                    "project": project_id,
+                    "item": item_id,
+                    "field": field_id,
+                    "option": option_id,
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    #[tokio::test]
+    async fn items_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        let item = |id: &str| serde_json::json!({"id": id, "content": null, "fieldValues": {"nodes": [{}]}});
+        let first = server
+            .mock("POST", "/graphql")
+            .match_header("Authorization", "Bearer token")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"variables": {"id": "PVT_1", "cursor": null}}),
+            ))
+            .with_body(
+                serde_json::json!({"data": {"node": {"items": {
+                    "pageInfo": {"hasNextPage": true, "endCursor": "c1"},
+                    "nodes": [item("PVTI_1"), item("PVTI_2")],
+                }}}})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let second = server
+            .mock("POST", "/graphql")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"variables": {"id": "PVT_1", "cursor": "c1"}}),
+            ))
+            .with_body(
+                serde_json::json!({"data": {"node": {"items": {
+                    "pageInfo": {"hasNextPage": false, "endCursor": null},
+                    "nodes": [item("PVTI_3")],
+                }}}})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let c = Client::new(&format!("{}/graphql", server.url()), "token");
+        let items = c.items("PVT_1").await.unwrap();
+        assert_eq!(
+            items.iter().map(|i| i.id.as_str()).collect::<Vec<&str>>(),
+            vec!["PVTI_1", "PVTI_2", "PVTI_3"]
+        );
+
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn errors_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        server
+            .mock("POST", "/graphql")
+            .with_body(
+                serde_json::json!({"data": {"repositoryOwner": null}, "errors": [
+                    {"message": "Could not resolve to a ProjectOwner with the login of 'nobody'."}
+                ]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let c = Client::new(&format!("{}/graphql", server.url()), "token");
+        let err = c.project("nobody", 1).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Could not resolve to a ProjectOwner with the login of 'nobody'."
+        );
+    }
+}
@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: MIT
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Response<T> {
+    pub data: Option<T>,
+    #[serde(default)]
+    pub errors: Vec<GraphQLError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQLError {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Nodes<T> {
+    pub nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectData {
+    pub repository_owner: Option<Owner>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Owner {
+    pub project_v2: Option<ProjectV2>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProjectV2 {
+    pub id: String,
+    pub url: String,
+    pub fields: Nodes<Field>,
+}
+
+// Fields of all types are returned, the fragments of the unknown ones are empty
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Field {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    // SINGLE_SELECT, DATE, ITERATION, TEXT, etc.
+    pub data_type: Option<String>,
+    pub options: Option<Vec<FieldOption>>,
+    pub configuration: Option<IterationConfiguration>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FieldOption {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IterationConfiguration {
+    pub iterations: Vec<Iteration>,
+    pub completed_iterations: Vec<Iteration>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Iteration {
+    pub id: String,
+    pub title: String,
+    pub start_date: String,
+    pub duration: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ItemsData {
+    pub node: Option<ItemsNode>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ItemsNode {
+    pub items: ItemConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemConnection {
+    pub page_info: PageInfo,
+    pub nodes: Vec<Item>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    pub id: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub content: Option<Content>,
+    pub field_values: Nodes<FieldValue>,
+}
+
+// A draft issue, an issue or a pull request
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Content {
+    #[serde(rename = "__typename")]
+    pub typename: String,
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldValue {
+    pub field: Option<FieldRef>,
+    // Single select values
+    pub name: Option<String>,
+    // Date values
+    pub date: Option<String>,
+    // Iteration values
+    pub iteration_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FieldRef {
+    pub name: Option<String>,
+}
@@ -1,8 +1,10 @@
 // SPDX-License-Identifier: MIT
 
-use super::structs::Todo;
+use super::structs::{Issue, MergeRequest, Todo, User};
 use crate::filter::FilterState;
+use reqwest::StatusCode;
 use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
 use std::error::Error;
 
 pub struct Client {
@@ -16,7 +18,7 @@ impl Client {
         let mut headers = HeaderMap::new();
         headers.insert("Authorization", format!("Bearer {api_key}").parse().unwrap());
         Self {
-            base_url: format!("{base_url}/api/v4"),
+            base_url: format!("{}/api/v4", base_url.trim_end_matches('/')),
             default_header: headers,
             client: reqwest::Client::new(),
         }
@@ -68,4 +70,184 @@ impl Client {
             .await?;
         Ok(())
     }
+
+    async fn get_all<T: DeserializeOwned>(&self, path: &str, query: &str) -> Result<Vec<T>, Box<dyn Error>> {
+        let mut result = Vec::new();
+
+        const PER_PAGE: i8 = 100;
+        let mut page = 1;
+
+        loop {
+            let resp = self
+                .client
+                .get(format!(
+                    "{}/{path}?page={page}&per_page={PER_PAGE}&{query}",
+                    self.base_url
+                ))
+                .headers(self.default_header.clone())
+                .send()
+                .await?;
+            check_status(resp.status())?;
+
+            let mut resp = resp.json::<Vec<T>>().await?;
+            if resp.is_empty() {
+                break;
+            }
+
+            result.append(&mut resp);
+            page += 1;
+        }
+
+        Ok(result)
+    }
+
+    pub async fn user(&self) -> Result<User, Box<dyn Error>> {
+        let resp = self
+            .client
+            .get(format!("{}/user", self.base_url))
+            .headers(self.default_header.clone())
+            .send()
+            .await?;
+        check_status(resp.status())?;
+        Ok(resp.json::<User>().await?)
+    }
+
+    pub async fn assigned_issues(&self, states: &[FilterState]) -> Result<Vec<Issue>, Box<dyn Error>> {
+        let has_open = states.contains(&FilterState::Uncompleted);
+        let has_closed = states.contains(&FilterState::Completed);
+        let state_query = match (has_open, has_closed) {
+            (true, false) => "state=opened",
+            (false, true) => "state=closed",
+            (true, true) => "state=all",
+            (false, false) => return Ok(Vec::new()),
+        };
+
+        self.get_all("issues", format!("scope=assigned_to_me&{state_query}").as_str())
+            .await
+    }
+
+    // Open merge requests of all projects where the user is a reviewer
+    pub async fn review_requests(&self, reviewer_id: i64) -> Result<Vec<MergeRequest>, Box<dyn Error>> {
+        self.get_all(
+            "merge_requests",
+            format!("scope=all&state=opened&reviewer_id={reviewer_id}").as_str(),
+        )
+        .await
+    }
+
+    // The event is either close or reopen
+    pub async fn change_issue_state(&self, project_id: i64, iid: i64, event: &str) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .client
+            .put(format!("{}/projects/{project_id}/issues/{iid}", self.base_url))
+            .headers(self.default_header.clone())
+            .json(&serde_json::json!({ "state_event": event }))
+            .send()
+            .await?;
+        check_status(resp.status())
+    }
+}
+
+fn check_status(status: StatusCode) -> Result<(), Box<dyn Error>> {
+    match status {
+        s if s.is_success() => Ok(()),
+        StatusCode::UNAUTHORIZED => Err(Box::<dyn Error>::from("the token is invalid or expired")),
+        StatusCode::FORBIDDEN => Err(Box::<dyn Error>::from(
+            "the token doesn't have permission for the action",
+        )),
+        s => Err(Box::<dyn Error>::from(format!("wrong status: {}", s.as_str()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn issue_json(iid: i64) -> serde_json::Value {
+        serde_json::json!({
+            "id": 100 + iid,
+            "iid": iid,
+            "project_id": 7,
+            "title": format!("Issue {iid}"),
+            "description": null,
+            "state": "opened",
+            "created_at": "2025-01-20T10:00:00.000Z",
+            "updated_at": "2025-01-20T10:00:00.000Z",
+            "closed_at": null,
+            "labels": ["bug"],
+            "milestone": {
+                "id": 1,
+                "iid": 1,
+                "group_id": 3,
+                "title": "v1",
+                "description": null,
+                "state": "active",
+                "created_at": "2025-01-01T10:00:00.000Z",
+                "updated_at": "2025-01-01T10:00:00.000Z",
+                "due_date": null,
+            },
+            "due_date": "2025-01-27",
+            "web_url": format!("https://gitlab.com/group/project/-/issues/{iid}"),
+            "references": {"full": format!("group/project#{iid}")},
+        })
+    }
+
+    #[tokio::test]
+    async fn assigned_issues_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        let first = server
+            .mock("GET", "/api/v4/issues")
+            .match_header("Authorization", "Bearer secret")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("scope".into(), "assigned_to_me".into()),
+                Matcher::UrlEncoded("state".into(), "all".into()),
+                Matcher::UrlEncoded("page".into(), "1".into()),
+            ]))
+            .with_body(serde_json::json!([issue_json(1), issue_json(2)]).to_string())
+            .create_async()
+            .await;
+        let last = server
+            .mock("GET", "/api/v4/issues")
+            .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let c = Client::new(&server.url(), "secret");
+        let issues = c
+            .assigned_issues(&[FilterState::Uncompleted, FilterState::Completed])
+            .await
+            .unwrap();
+        assert_eq!(issues.iter().map(|i| i.iid).collect::<Vec<i64>>(), vec![1, 2]);
+        assert_eq!(issues[0].milestone.as_ref().unwrap().project_id, None);
+
+        first.assert_async().await;
+        last.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn change_issue_state_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        let close = server
+            .mock("PUT", "/api/v4/projects/7/issues/1")
+            .match_body(Matcher::Json(serde_json::json!({"state_event": "close"})))
+            .with_body(issue_json(1).to_string())
+            .create_async()
+            .await;
+        server
+            .mock("PUT", "/api/v4/projects/7/issues/2")
+            .with_status(403)
+            .create_async()
+            .await;
+
+        let c = Client::new(&server.url(), "secret");
+        c.change_issue_state(7, 1, "close").await.unwrap();
+        close.assert_async().await;
+
+        let err = c.change_issue_state(7, 2, "close").await.unwrap_err();
+        assert_eq!(err.to_string(), "the token doesn't have permission for the action");
+    }
 }
@@ -76,13 +76,61 @@ pub struct Assignee {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Milestone {
+    pub id: i64,
+    pub iid: i64,
+    // Group milestones don't belong to a project
+    pub project_id: Option<i64>,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub due_date: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Issue {
     pub id: i64,
     pub iid: i64,
     pub project_id: i64,
     pub title: String,
-    pub description: String,
+    pub description: Option<String>,
     pub state: String,
     pub created_at: String,
     pub updated_at: String,
-    pub due_date: String,
+    pub closed_at: Option<String>,
+    pub labels: Vec<String>,
+    pub milestone: Option<Milestone>,
+    pub due_date: Option<String>,
+    pub web_url: String,
+    pub references: References,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeRequest {
+    pub id: i64,
+    pub iid: i64,
+    pub project_id: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub labels: Vec<String>,
+    pub milestone: Option<Milestone>,
+    pub draft: bool,
+    pub web_url: String,
+    pub references: References,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct References {
+    // group/project#1 for issues and group/project!1 for merge requests
+    pub full: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: i64,
+    pub username: String,
 }
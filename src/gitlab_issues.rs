@@ -0,0 +1,432 @@
+// SPDX-License-Identifier: MIT
+
+// Issues assigned to the user and merge requests waiting for the user's review
+
+use crate::filter;
+use crate::gitlab::client::Client;
+use crate::gitlab::structs;
+use crate::project::Project as ProjectTrait;
+use crate::provider::Provider as ProviderTrait;
+use crate::task::{DateTimeUtc, State, Task as TaskTrait, due_group};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use itertools::Itertools;
+use ratatui::style::Color;
+use std::any::Any;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "GitLab Issues";
+
+const REVIEW_REQUESTED_LABEL: &str = "review_requested";
+
+#[derive(Clone)]
+pub struct Project {
+    id: i64,
+    path: String,
+    provider: String,
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.path.to_string()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        self.path.to_string()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        None
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
+
+fn str_to_date(s: &str) -> Option<DateTimeUtc> {
+    if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let dt = d.and_hms_opt(0, 0, 0)?;
+        return Some(DateTimeUtc::from_naive_utc_and_offset(dt, Utc));
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(DateTimeUtc::from(dt));
+    }
+
+    None
+}
+
+// group/subgroup/project#1 -> group/subgroup/project
+fn project_path(reference: &str) -> String {
+    match reference.rfind(['#', '!']) {
+        Some(idx) => reference[..idx].to_string(),
+        None => reference.to_string(),
+    }
+}
+
+#[derive(Clone)]
+enum Item {
+    Issue(Box<structs::Issue>),
+    MergeRequest(Box<structs::MergeRequest>),
+}
+
+#[derive(Clone)]
+pub struct Task {
+    item: Item,
+    provider: String,
+}
+
+impl Task {
+    fn project_id(&self) -> i64 {
+        match &self.item {
+            Item::Issue(i) => i.project_id,
+            Item::MergeRequest(mr) => mr.project_id,
+        }
+    }
+
+    fn milestone(&self) -> Option<&structs::Milestone> {
+        match &self.item {
+            Item::Issue(i) => i.milestone.as_ref(),
+            Item::MergeRequest(mr) => mr.milestone.as_ref(),
+        }
+    }
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        match &self.item {
+            Item::Issue(i) => format!("issue-{}", i.id),
+            Item::MergeRequest(mr) => format!("mr-{}", mr.id),
+        }
+    }
+
+    fn text(&self) -> String {
+        match &self.item {
+            Item::Issue(i) => i.title.to_string(),
+            Item::MergeRequest(mr) => mr.title.to_string(),
+        }
+    }
+
+    fn description(&self) -> Option<String> {
+        match &self.item {
+            Item::Issue(i) => i.description.clone(),
+            Item::MergeRequest(mr) => mr.description.clone(),
+        }
+        .filter(|d| !d.is_empty())
+    }
+
+    fn state(&self) -> State {
+        let state = match &self.item {
+            Item::Issue(i) => i.state.as_str(),
+            Item::MergeRequest(mr) => mr.state.as_str(),
+        };
+        match state {
+            "opened" => State::Uncompleted,
+            "closed" | "merged" => State::Completed,
+            _ => State::Unknown('?'),
+        }
+    }
+
+    fn created_at(&self) -> Option<DateTimeUtc> {
+        match &self.item {
+            Item::Issue(i) => str_to_date(&i.created_at),
+            Item::MergeRequest(mr) => str_to_date(&mr.created_at),
+        }
+    }
+
+    fn updated_at(&self) -> Option<DateTimeUtc> {
+        match &self.item {
+            Item::Issue(i) => str_to_date(&i.updated_at),
+            Item::MergeRequest(mr) => str_to_date(&mr.updated_at),
+        }
+    }
+
+    fn completed_at(&self) -> Option<DateTimeUtc> {
+        match &self.item {
+            Item::Issue(i) => i.closed_at.as_deref().and_then(str_to_date),
+            Item::MergeRequest(_) => None,
+        }
+    }
+
+    // The own due date of an issue is more precise than the milestone's one
+    fn due(&self) -> Option<DateTimeUtc> {
+        let due = match &self.item {
+            Item::Issue(i) => i.due_date.as_deref(),
+            Item::MergeRequest(_) => None,
+        };
+        due.or(self.milestone().and_then(|m| m.due_date.as_deref()))
+            .and_then(str_to_date)
+    }
+
+    fn place(&self) -> String {
+        match &self.item {
+            Item::Issue(i) => i.web_url.to_string(),
+            Item::MergeRequest(mr) => mr.web_url.to_string(),
+        }
+    }
+
+    fn labels(&self) -> Vec<String> {
+        match &self.item {
+            Item::Issue(i) => i.labels.clone(),
+            Item::MergeRequest(mr) => {
+                let mut labels = mr.labels.clone();
+                labels.push(REVIEW_REQUESTED_LABEL.to_string());
+                labels
+            }
+        }
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        let reference = match &self.item {
+            Item::Issue(i) => &i.references.full,
+            Item::MergeRequest(mr) => &mr.references.full,
+        };
+        Some(Box::new(Project {
+            id: self.project_id(),
+            path: project_path(reference),
+            provider: self.provider.to_string(),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+pub struct Provider {
+    name: String,
+    color: Color,
+    client: Client,
+    user_id: Option<i64>,
+    tasks: Vec<Task>,
+    last_filter: Option<filter::Filter>,
+}
+
+impl Provider {
+    pub fn new(name: &str, base_url: &str, api_key: &str, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            color: *color,
+            client: Client::new(base_url, api_key),
+            user_id: None,
+            tasks: Vec::new(),
+            last_filter: None,
+        }
+    }
+
+    async fn load(&mut self, states: &[filter::FilterState]) -> Result<Vec<Task>, Box<dyn Error>> {
+        let mut items: Vec<Item> = self
+            .client
+            .assigned_issues(states)
+            .await?
+            .into_iter()
+            .map(|i| Item::Issue(Box::new(i)))
+            .collect();
+
+        // Only open merge requests wait for a review
+        if states.contains(&filter::FilterState::Uncompleted) {
+            let user_id = match self.user_id {
+                Some(id) => id,
+                None => {
+                    let id = self.client.user().await?.id;
+                    self.user_id = Some(id);
+                    id
+                }
+            };
+
+            for mr in self.client.review_requests(user_id).await? {
+                items.push(Item::MergeRequest(Box::new(mr)));
+            }
+        }
+
+        Ok(items
+            .into_iter()
+            .map(|item| Task {
+                item,
+                provider: self.name(),
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        if self.last_filter.as_ref().is_some_and(|last| last != f) {
+            self.tasks.clear();
+        }
+
+        if self.tasks.is_empty() {
+            self.tasks = self.load(&f.states).await?;
+        }
+
+        let project = project.map(|p| p.id());
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for t in &self.tasks {
+            if project.as_ref().is_some_and(|p| *p != t.project_id().to_string()) {
+                continue;
+            }
+            if f.states.contains(&filter::FilterState::from(&t.state())) && f.due.contains(&due_group(t)) {
+                result.push(Box::new(t.clone()));
+            }
+        }
+
+        self.last_filter = Some(f.clone());
+
+        Ok(result)
+    }
+
+    // Projects of the open issues and merge requests
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for t in self
+            .load(&[filter::FilterState::Uncompleted])
+            .await?
+            .iter()
+            .unique_by(|t| t.project_id())
+        {
+            if let Some(p) = t.project() {
+                result.push(p);
+            }
+        }
+        Ok(result)
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let t: &Task = match task.as_any().downcast_ref::<Task>() {
+            Some(t) => t,
+            None => panic!("Wrong casting!"),
+        };
+
+        let issue = match &t.item {
+            Item::Issue(i) => i,
+            Item::MergeRequest(_) => {
+                return Err(Box::<dyn Error>::from(
+                    "the review request is completed by reviewing the merge request",
+                ));
+            }
+        };
+
+        let event = match state {
+            State::Completed => "close",
+            State::Uncompleted => "reopen",
+            State::InProgress | State::Unknown(_) => return Err(Box::<dyn Error>::from("wrong state")),
+        };
+
+        self.client
+            .change_issue_state(issue.project_id, issue.iid, event)
+            .await?;
+
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn reload(&mut self) {
+        self.tasks.clear();
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_path_test() {
+        struct Case<'a> {
+            name: &'a str,
+            reference: &'a str,
+            expected: &'a str,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "issue",
+                reference: "group/project#12",
+                expected: "group/project",
+            },
+            Case {
+                name: "merge request of a subgroup",
+                reference: "group/subgroup/project!3",
+                expected: "group/subgroup/project",
+            },
+            Case {
+                name: "no number",
+                reference: "group/project",
+                expected: "group/project",
+            },
+        ];
+
+        for c in CASES {
+            assert_eq!(project_path(c.reference), c.expected, "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn due_test() {
+        let milestone = structs::Milestone {
+            due_date: Some("2025-02-01".to_string()),
+            ..structs::Milestone::default()
+        };
+        let issue = |due_date: Option<&str>| Task {
+            item: Item::Issue(Box::new(structs::Issue {
+                due_date: due_date.map(|d| d.to_string()),
+                milestone: Some(milestone.clone()),
+                ..structs::Issue::default()
+            })),
+            provider: String::new(),
+        };
+        let mr = Task {
+            item: Item::MergeRequest(Box::new(structs::MergeRequest {
+                milestone: Some(milestone.clone()),
+                ..structs::MergeRequest::default()
+            })),
+            provider: String::new(),
+        };
+
+        let date = |t: &Task| t.due().map(|d| d.format("%Y-%m-%d").to_string());
+        assert_eq!(date(&issue(Some("2025-01-27"))).as_deref(), Some("2025-01-27"));
+        assert_eq!(date(&issue(None)).as_deref(), Some("2025-02-01"));
+        assert_eq!(date(&mr).as_deref(), Some("2025-02-01"));
+        assert!(mr.labels().contains(&REVIEW_REQUESTED_LABEL.to_string()));
+    }
+}
@@ -108,6 +108,10 @@ impl TaskTrait for Task {
         }
     }
 
+    fn labels(&self) -> Vec<String> {
+        self.todo.target.labels.clone()
+    }
+
     fn provider(&self) -> String {
         self.provider.to_string()
     }
@@ -211,6 +215,11 @@ impl ProviderTrait for Provider {
         }
     }
 
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        // GitLab doesn't allow to delete TODOs, so marking as done is the closest action
+        self.change_task_state(task, State::Completed).await
+    }
+
     async fn reload(&mut self) {
         self.tasks.clear();
     }
@@ -0,0 +1,504 @@
+// SPDX-License-Identifier: MIT
+
+mod client;
+mod structs;
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::provider::{DuePatch, NewTask, Provider as ProviderTrait, TaskPatch};
+use crate::task::{DateTimeUtc, Priority, State, Task as TaskTrait, due_group};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use client::Client;
+use ratatui::style::Color;
+use std::any::Any;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "Jira";
+pub const DEFAULT_JQL: &str = "assignee = currentUser() AND resolution = Unresolved";
+const DEFAULT_ISSUE_TYPE: &str = "Task";
+
+// Status categories of Jira, every status of a workflow belongs to one of them
+const CATEGORY_TODO: &str = "new";
+const CATEGORY_IN_PROGRESS: &str = "indeterminate";
+const CATEGORY_DONE: &str = "done";
+
+fn str_to_date(s: &str) -> Option<DateTimeUtc> {
+    if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let dt = d.and_hms_opt(0, 0, 0)?;
+        return Some(DateTimeUtc::from_naive_utc_and_offset(dt, Utc));
+    }
+
+    // 2025-01-20T10:00:00.000+0000
+    if let Ok(dt) = DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z") {
+        return Some(DateTimeUtc::from(dt));
+    }
+
+    None
+}
+
+fn priority_from_name(name: &str) -> Priority {
+    match name.to_lowercase().as_str() {
+        "highest" | "blocker" | "critical" => Priority::Highest,
+        "high" | "major" => Priority::High,
+        "medium" => Priority::Medium,
+        "low" | "minor" => Priority::Low,
+        "lowest" | "trivial" => Priority::Lowest,
+        _ => Priority::Normal,
+    }
+}
+
+fn priority_to_name(p: &Priority) -> &'static str {
+    match p {
+        Priority::Highest => "Highest",
+        Priority::High => "High",
+        Priority::Medium | Priority::Normal => "Medium",
+        Priority::Low => "Low",
+        Priority::Lowest => "Lowest",
+    }
+}
+
+fn state_from_category(category: &str) -> State {
+    match category {
+        CATEGORY_TODO => State::Uncompleted,
+        CATEGORY_IN_PROGRESS => State::InProgress,
+        CATEGORY_DONE => State::Completed,
+        _ => State::Unknown('?'),
+    }
+}
+
+// The transition is chosen by its name if it's set in the settings, otherwise the first one
+// which moves the issue to a status of the category is used
+fn select_transition<'a>(
+    transitions: &'a [structs::Transition],
+    name: Option<&str>,
+    category: &str,
+) -> Result<&'a structs::Transition, Box<dyn Error>> {
+    match name {
+        Some(n) => transitions
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(n))
+            .ok_or_else(|| Box::<dyn Error>::from(format!("the transition '{n}' isn't available for the issue"))),
+        None => transitions
+            .iter()
+            .find(|t| t.to.status_category.key == category)
+            .ok_or_else(|| {
+                Box::<dyn Error>::from(format!(
+                    "there is no transition to a status of the category '{category}' for the issue"
+                ))
+            }),
+    }
+}
+
+#[derive(Clone)]
+pub struct Task {
+    issue: structs::Issue,
+    base_url: String,
+    provider: String,
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        self.issue.id.to_string()
+    }
+
+    fn text(&self) -> String {
+        self.issue.fields.summary.to_string()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.issue.fields.description.clone().filter(|d| !d.is_empty())
+    }
+
+    fn priority(&self) -> Priority {
+        self.issue
+            .fields
+            .priority
+            .as_ref()
+            .map(|p| priority_from_name(&p.name))
+            .unwrap_or_default()
+    }
+
+    fn state(&self) -> State {
+        state_from_category(&self.issue.fields.status.status_category.key)
+    }
+
+    fn created_at(&self) -> Option<DateTimeUtc> {
+        self.issue.fields.created.as_deref().and_then(str_to_date)
+    }
+
+    fn updated_at(&self) -> Option<DateTimeUtc> {
+        self.issue.fields.updated.as_deref().and_then(str_to_date)
+    }
+
+    fn completed_at(&self) -> Option<DateTimeUtc> {
+        self.issue.fields.resolutiondate.as_deref().and_then(str_to_date)
+    }
+
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.issue.fields.duedate.as_deref().and_then(str_to_date)
+    }
+
+    fn place(&self) -> String {
+        format!("{}/browse/{}", self.base_url.trim_end_matches('/'), self.issue.key)
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.issue.fields.labels.clone()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.issue.fields.parent.as_ref().map(|p| p.id.to_string())
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        let mut p = self.issue.fields.project.clone();
+        p.provider = self.provider.to_string();
+        Some(Box::new(p))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+// Settings of the provider, the transitions are chosen by the status category when their names are unset
+#[derive(Default)]
+pub struct Config {
+    pub base_url: String,
+    // Email for Jira Cloud, personal access tokens of Jira Server and Data Center don't need it
+    pub username: Option<String>,
+    pub api_key: String,
+    pub jql: Option<String>,
+    pub issue_type: Option<String>,
+    pub todo_transition: Option<String>,
+    pub in_progress_transition: Option<String>,
+    pub done_transition: Option<String>,
+}
+
+pub struct Provider {
+    name: String,
+    color: Color,
+    jql: String,
+    issue_type: String,
+    config: Config,
+    client: Client,
+    tasks: Vec<Task>,
+    last_filter: Option<filter::Filter>,
+}
+
+impl Provider {
+    pub fn new(name: &str, config: Config, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            color: *color,
+            jql: config.jql.as_deref().unwrap_or(DEFAULT_JQL).to_string(),
+            issue_type: config.issue_type.as_deref().unwrap_or(DEFAULT_ISSUE_TYPE).to_string(),
+            client: Client::new(&config.base_url, config.username.as_deref(), &config.api_key),
+            config,
+            tasks: Vec::new(),
+            last_filter: None,
+        }
+    }
+}
+
+fn cast(t: &dyn TaskTrait) -> &Task {
+    match t.as_any().downcast_ref::<Task>() {
+        Some(t) => t,
+        None => panic!("Wrong casting!"),
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        if self.last_filter.as_ref().is_some_and(|last| last != f) {
+            self.tasks.clear();
+        }
+
+        if self.tasks.is_empty() {
+            for issue in self.client.search(&self.jql).await? {
+                self.tasks.push(Task {
+                    issue,
+                    base_url: self.config.base_url.to_string(),
+                    provider: self.name(),
+                })
+            }
+        }
+
+        let project = project.map(|p| p.id());
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for t in &self.tasks {
+            if project.as_ref().is_some_and(|p| *p != t.issue.fields.project.id) {
+                continue;
+            }
+            if f.states.contains(&filter::FilterState::from(&t.state())) && f.due.contains(&due_group(t)) {
+                result.push(Box::new(t.clone()));
+            }
+        }
+
+        self.last_filter = Some(f.clone());
+
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for mut p in self.client.projects().await? {
+            p.provider = self.name();
+            result.push(Box::new(p));
+        }
+        Ok(result)
+    }
+
+    async fn create_task(&mut self, project: Option<Box<dyn ProjectTrait>>, t: &NewTask) -> Result<(), Box<dyn Error>> {
+        let project = match project {
+            Some(p) => p,
+            None => return Err(Box::<dyn Error>::from("A project should be chosen to add the issue")),
+        };
+
+        // The issue is assigned to the current user to be found by the default JQL
+        let me = self.client.myself().await?;
+        let assignee = match (me.account_id, me.name) {
+            (Some(id), _) => serde_json::json!({ "accountId": id }),
+            (None, Some(name)) => serde_json::json!({ "name": name }),
+            (None, None) => serde_json::Value::Null,
+        };
+
+        let mut fields = serde_json::json!({
+            "project": { "id": project.id() },
+            "issuetype": { "name": self.issue_type },
+            "summary": t.text,
+            "assignee": assignee,
+        });
+        if let Some(d) = &t.description {
+            fields["description"] = serde_json::json!(d);
+        }
+        if let Some(d) = &t.due {
+            fields["duedate"] = serde_json::json!(d.format("%Y-%m-%d").to_string());
+        }
+        if t.priority != Priority::Normal {
+            fields["priority"] = serde_json::json!({ "name": priority_to_name(&t.priority) });
+        }
+
+        self.client.create_issue(&fields).await?;
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let t = cast(task);
+
+        let (name, category) = match &state {
+            State::Uncompleted => (&self.config.todo_transition, CATEGORY_TODO),
+            State::InProgress => (&self.config.in_progress_transition, CATEGORY_IN_PROGRESS),
+            State::Completed => (&self.config.done_transition, CATEGORY_DONE),
+            State::Unknown(_) => {
+                return Err(Box::<dyn Error>::from(format!(
+                    "Jira issues can't be moved to the state {state:?}"
+                )));
+            }
+        };
+
+        let transitions = self.client.transitions(&t.issue.key).await?;
+        let transition = select_transition(&transitions, name.as_deref(), category)?;
+        self.client.transition(&t.issue.key, &transition.id).await?;
+
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn update_task(&mut self, patch: &TaskPatch) -> Result<(), Box<dyn Error>> {
+        let t = cast(patch.task.as_ref());
+
+        let mut fields = serde_json::json!({});
+        if let Some(text) = &patch.text {
+            fields["summary"] = serde_json::json!(text);
+        }
+        if let Some(d) = &patch.description {
+            fields["description"] = serde_json::json!(d);
+        }
+        match &patch.due {
+            Some(DuePatch::Date(d)) => fields["duedate"] = serde_json::json!(d.format("%Y-%m-%d").to_string()),
+            Some(DuePatch::NoDate) => fields["duedate"] = serde_json::Value::Null,
+            None => {}
+        }
+        if let Some(p) = &patch.priority {
+            fields["priority"] = serde_json::json!({ "name": priority_to_name(p) });
+        }
+
+        self.client.update_issue(&t.issue.key, &fields).await?;
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        let t = cast(task);
+        self.client.delete_issue(&t.issue.key).await?;
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn reload(&mut self) {
+        self.tasks.clear();
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transition(id: &str, name: &str, category: &str) -> structs::Transition {
+        structs::Transition {
+            id: id.to_string(),
+            name: name.to_string(),
+            to: structs::Status {
+                status_category: structs::StatusCategory {
+                    key: category.to_string(),
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn select_transition_test() {
+        let transitions = vec![
+            transition("11", "Start progress", CATEGORY_IN_PROGRESS),
+            transition("21", "Resolve", CATEGORY_DONE),
+            transition("31", "Close", CATEGORY_DONE),
+        ];
+
+        struct Case<'a> {
+            name: &'a str,
+            transition: Option<&'a str>,
+            category: &'a str,
+            expected: Option<&'a str>,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "by category",
+                transition: None,
+                category: CATEGORY_DONE,
+                expected: Some("21"),
+            },
+            Case {
+                name: "by name",
+                transition: Some("close"),
+                category: CATEGORY_DONE,
+                expected: Some("31"),
+            },
+            Case {
+                name: "unknown name",
+                transition: Some("Done"),
+                category: CATEGORY_DONE,
+                expected: None,
+            },
+            Case {
+                name: "no transition to the category",
+                transition: None,
+                category: CATEGORY_TODO,
+                expected: None,
+            },
+        ];
+
+        for c in CASES {
+            let result = select_transition(&transitions, c.transition, c.category)
+                .ok()
+                .map(|t| t.id.as_str());
+            assert_eq!(result, c.expected, "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[tokio::test]
+    async fn complete_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        server
+            .mock("GET", "/rest/api/2/search")
+            .match_query(mockito::Matcher::Any)
+            .with_body(
+                serde_json::json!({"startAt": 0, "total": 1, "issues": [{
+                    "id": "10001",
+                    "key": "TT-1",
+                    "fields": {
+                        "summary": "Fix the bug",
+                        "status": {"name": "In Review", "statusCategory": {"key": "indeterminate"}},
+                        "priority": {"name": "Major"},
+                        "duedate": "2025-01-27",
+                        "project": {"id": "1", "key": "TT", "name": "Tatuin"},
+                        "labels": ["backend"],
+                        "parent": {"id": "10000"},
+                    }
+                }]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/rest/api/2/issue/TT-1/transitions")
+            .with_body(
+                serde_json::json!({"transitions": [
+                    {"id": "11", "name": "Back to work", "to": {"name": "In Progress", "statusCategory": {"key": "indeterminate"}}},
+                    {"id": "31", "name": "Done", "to": {"name": "Done", "statusCategory": {"key": "done"}}},
+                ]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let transition = server
+            .mock("POST", "/rest/api/2/issue/TT-1/transitions")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"transition": {"id": "31"}})))
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let config = Config {
+            base_url: server.url(),
+            api_key: "token".to_string(),
+            ..Config::default()
+        };
+        let mut p = Provider::new("Jira", config, &Color::Red);
+        let f = filter::Filter {
+            states: vec![filter::FilterState::InProgress],
+            due: vec![filter::Due::Overdue],
+            labels: Vec::new(),
+        };
+        let tasks = p.tasks(None, &f).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+
+        let t = &tasks[0];
+        assert_eq!(t.text(), "Fix the bug");
+        assert_eq!(t.state(), State::InProgress);
+        assert_eq!(t.priority(), Priority::High);
+        assert_eq!(t.place(), format!("{}/browse/TT-1", server.url()));
+        assert_eq!(t.parent_id(), Some("10000".to_string()));
+        assert_eq!(t.project().unwrap().name(), "Tatuin");
+
+        p.change_task_state(t.as_ref(), State::Completed).await.unwrap();
+        transition.assert_async().await;
+    }
+}
@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: MIT
+
+use super::structs::{Issue, Project, SearchResult, Transition, Transitions, User};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::Deserialize;
+use std::error::Error;
+
+const FIELDS: &str = "summary,description,status,priority,duedate,project,labels,created,updated,resolutiondate,parent";
+const MAX_RESULTS: i64 = 100;
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct ErrorResponse {
+    #[serde(default)]
+    error_messages: Vec<String>,
+    #[serde(default)]
+    errors: std::collections::HashMap<String, String>,
+}
+
+pub struct Client {
+    base_url: String,
+    username: Option<String>,
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl Client {
+    // Jira Cloud uses an email with an API token, Jira Server and Data Center use a personal access token
+    pub fn new(base_url: &str, username: Option<&str>, api_key: &str) -> Self {
+        Self {
+            base_url: format!("{}/rest/api/2", base_url.trim_end_matches('/')),
+            username: username.map(|u| u.to_string()),
+            api_key: api_key.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let req = self
+            .client
+            .request(method, format!("{}{path}", self.base_url))
+            .header("Accept", "application/json");
+        match &self.username {
+            Some(u) => req.basic_auth(u, Some(&self.api_key)),
+            None => req.bearer_auth(&self.api_key),
+        }
+    }
+
+    pub async fn search(&self, jql: &str) -> Result<Vec<Issue>, Box<dyn Error>> {
+        let mut result = Vec::new();
+        let mut start_at = 0;
+
+        loop {
+            let resp = self
+                .request(Method::GET, "/search")
+                .query(&[("jql", jql), ("fields", FIELDS)])
+                .query(&[("startAt", start_at), ("maxResults", MAX_RESULTS)])
+                .send()
+                .await?;
+            if matches!(resp.status(), StatusCode::GONE | StatusCode::NOT_FOUND) {
+                // Jira Cloud has replaced /search with /search/jql
+                return self.search_jql(jql).await;
+            }
+
+            let resp = check_status(resp).await?;
+            let resp = resp.json::<SearchResult>().await?;
+            let count = resp.issues.len() as i64;
+            result.extend(resp.issues);
+
+            start_at = resp.start_at + count;
+            if count == 0 || start_at >= resp.total {
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn search_jql(&self, jql: &str) -> Result<Vec<Issue>, Box<dyn Error>> {
+        let mut result = Vec::new();
+        let mut token: Option<String> = None;
+
+        loop {
+            let mut req = self
+                .request(Method::GET, "/search/jql")
+                .query(&[("jql", jql), ("fields", FIELDS)])
+                .query(&[("maxResults", MAX_RESULTS)]);
+            if let Some(t) = &token {
+                req = req.query(&[("nextPageToken", t)]);
+            }
+
+            let resp = check_status(req.send().await?).await?;
+            let resp = resp.json::<SearchResult>().await?;
+            result.extend(resp.issues);
+
+            token = resp.next_page_token;
+            if token.is_none() || resp.is_last == Some(true) {
+                break;
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub async fn projects(&self) -> Result<Vec<Project>, Box<dyn Error>> {
+        let resp = self.request(Method::GET, "/project").send().await?;
+        let resp = check_status(resp).await?;
+        Ok(resp.json::<Vec<Project>>().await?)
+    }
+
+    pub async fn myself(&self) -> Result<User, Box<dyn Error>> {
+        let resp = self.request(Method::GET, "/myself").send().await?;
+        let resp = check_status(resp).await?;
+        Ok(resp.json::<User>().await?)
+    }
+
+    pub async fn transitions(&self, key: &str) -> Result<Vec<Transition>, Box<dyn Error>> {
+        let resp = self
+            .request(Method::GET, format!("/issue/{key}/transitions").as_str())
+            .send()
+            .await?;
+        let resp = check_status(resp).await?;
+        Ok(resp.json::<Transitions>().await?.transitions)
+    }
+
+    pub async fn transition(&self, key: &str, transition_id: &str) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .request(Method::POST, format!("/issue/{key}/transitions").as_str())
+            .json(&serde_json::json!({ "transition": { "id": transition_id } }))
+            .send()
+            .await?;
+        check_status(resp).await?;
+        Ok(())
+    }
+
+    pub async fn create_issue(&self, fields: &serde_json::Value) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .request(Method::POST, "/issue")
+            .json(&serde_json::json!({ "fields": fields }))
+            .send()
+            .await?;
+        check_status(resp).await?;
+        Ok(())
+    }
+
+    pub async fn update_issue(&self, key: &str, fields: &serde_json::Value) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .request(Method::PUT, format!("/issue/{key}").as_str())
+            .json(&serde_json::json!({ "fields": fields }))
+            .send()
+            .await?;
+        check_status(resp).await?;
+        Ok(())
+    }
+
+    pub async fn delete_issue(&self, key: &str) -> Result<(), Box<dyn Error>> {
+        let resp = self
+            .request(Method::DELETE, format!("/issue/{key}").as_str())
+            .send()
+            .await?;
+        check_status(resp).await?;
+        Ok(())
+    }
+}
+
+// Jira explains the errors in the body like {"errorMessages": [...], "errors": {"field": "..."}}
+async fn check_status(resp: Response) -> Result<Response, Box<dyn Error>> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+
+    if status == StatusCode::UNAUTHORIZED {
+        return Err(Box::<dyn Error>::from("the token is invalid or expired"));
+    }
+
+    let body = resp.json::<ErrorResponse>().await.unwrap_or_default();
+    let mut messages = body.error_messages;
+    messages.extend(body.errors.iter().map(|(k, v)| format!("{k}: {v}")));
+    if messages.is_empty() {
+        return Err(Box::<dyn Error>::from(format!("wrong status: {}", status.as_str())));
+    }
+
+    Err(Box::<dyn Error>::from(format!(
+        "wrong status: {}: {}",
+        status.as_str(),
+        messages.join(", ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn issue_json(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "key": format!("TT-{id}"),
+            "fields": {
+                "summary": format!("Issue {id}"),
+                "status": {"name": "To Do", "statusCategory": {"key": "new"}},
+                "project": {"id": "1", "key": "TT", "name": "Tatuin"},
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn search_test() {
+        let mut server = mockito::Server::new_async().await;
+        let jql = "assignee = currentUser() AND resolution = Unresolved";
+
+        let first = server
+            .mock("GET", "/rest/api/2/search")
+            .match_header("Authorization", "Bearer token")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("jql".into(), jql.into()),
+                Matcher::UrlEncoded("startAt".into(), "0".into()),
+            ]))
+            .with_body(
+                serde_json::json!({"startAt": 0, "total": 3, "issues": [issue_json("1"), issue_json("2")]}).to_string(),
+            )
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/rest/api/2/search")
+            .match_query(Matcher::UrlEncoded("startAt".into(), "2".into()))
+            .with_body(serde_json::json!({"startAt": 2, "total": 3, "issues": [issue_json("3")]}).to_string())
+            .create_async()
+            .await;
+
+        let c = Client::new(&server.url(), None, "token");
+        let issues = c.search(jql).await.unwrap();
+        assert_eq!(
+            issues.iter().map(|i| i.key.as_str()).collect::<Vec<&str>>(),
+            vec!["TT-1", "TT-2", "TT-3"]
+        );
+
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn search_jql_fallback_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        server
+            .mock("GET", "/rest/api/2/search")
+            .match_query(Matcher::Any)
+            .with_status(410)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/rest/api/2/search/jql")
+            .match_query(Matcher::Regex("^jql=[^&]*&fields=[^&]*&maxResults=100$".into()))
+            .with_body(serde_json::json!({"issues": [issue_json("1")], "nextPageToken": "next"}).to_string())
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/rest/api/2/search/jql")
+            .match_query(Matcher::UrlEncoded("nextPageToken".into(), "next".into()))
+            .with_body(serde_json::json!({"issues": [issue_json("2")], "isLast": true}).to_string())
+            .create_async()
+            .await;
+
+        let c = Client::new(&server.url(), Some("me@example.com"), "token");
+        let issues = c.search("project = TT").await.unwrap();
+        assert_eq!(issues.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn error_message_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        server
+            .mock("POST", "/rest/api/2/issue/TT-1/transitions")
+            .with_status(400)
+            .with_body(r#"{"errorMessages": [], "errors": {"resolution": "Field is required"}}"#)
+            .create_async()
+            .await;
+
+        let c = Client::new(&server.url(), None, "token");
+        let err = c.transition("TT-1", "31").await.unwrap_err();
+        assert_eq!(err.to_string(), "wrong status: 400: resolution: Field is required");
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: MIT
+
+use crate::project::Project as ProjectTrait;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    pub issues: Vec<Issue>,
+    // Paging of /search
+    #[serde(default)]
+    pub start_at: i64,
+    #[serde(default)]
+    pub total: i64,
+    // Paging of /search/jql
+    pub next_page_token: Option<String>,
+    pub is_last: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Issue {
+    pub id: String,
+    pub key: String,
+    pub fields: Fields,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Fields {
+    pub summary: String,
+    pub description: Option<String>,
+    pub status: Status,
+    pub priority: Option<Priority>,
+    pub duedate: Option<String>,
+    pub project: Project,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub created: Option<String>,
+    pub updated: Option<String>,
+    pub resolutiondate: Option<String>,
+    pub parent: Option<Parent>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Status {
+    pub status_category: StatusCategory,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StatusCategory {
+    // new, indeterminate, done or undefined
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Priority {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Parent {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Project {
+    pub id: String,
+    pub key: String,
+    pub name: String,
+
+    #[serde(skip)]
+    pub provider: String,
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        self.key.to_string()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        None
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Transitions {
+    pub transitions: Vec<Transition>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Transition {
+    pub id: String,
+    pub name: String,
+    pub to: Status,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    // Jira Cloud identifies users by accountId, Jira Server and Data Center by name
+    pub account_id: Option<String>,
+    pub name: Option<String>,
+}
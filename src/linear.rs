@@ -0,0 +1,548 @@
+// SPDX-License-Identifier: MIT
+
+// Issues assigned to the user in Linear, teams and their projects are shown as projects
+
+mod client;
+mod structs;
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::provider::{DuePatch, Provider as ProviderTrait, TaskPatch};
+use crate::task::{DateTimeUtc, Priority, State, Task as TaskTrait, due_group};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use client::Client;
+use ratatui::style::Color;
+use std::any::Any;
+use std::collections::HashMap;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "Linear";
+pub const DEFAULT_URL: &str = client::DEFAULT_URL;
+
+fn str_to_date(s: &str) -> Option<DateTimeUtc> {
+    if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let dt = d.and_hms_opt(0, 0, 0)?;
+        return Some(DateTimeUtc::from_naive_utc_and_offset(dt, Utc));
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(DateTimeUtc::from(dt));
+    }
+
+    None
+}
+
+fn priority_from_linear(p: i64) -> Priority {
+    match p {
+        1 => Priority::Highest,
+        2 => Priority::High,
+        3 => Priority::Medium,
+        4 => Priority::Low,
+        _ => Priority::Normal,
+    }
+}
+
+fn priority_to_linear(p: &Priority) -> i64 {
+    match p {
+        Priority::Highest => 1,
+        Priority::High => 2,
+        Priority::Medium => 3,
+        Priority::Low | Priority::Lowest => 4,
+        Priority::Normal => 0,
+    }
+}
+
+fn state_from_type(t: &str) -> State {
+    match t {
+        "triage" | "backlog" | "unstarted" => State::Uncompleted,
+        "started" => State::InProgress,
+        "completed" => State::Completed,
+        "canceled" => State::Unknown('-'),
+        _ => State::Unknown('?'),
+    }
+}
+
+fn filter_state_to_types(s: &filter::FilterState) -> &'static [&'static str] {
+    match s {
+        filter::FilterState::Uncompleted => &["triage", "backlog", "unstarted"],
+        filter::FilterState::InProgress => &["started"],
+        filter::FilterState::Completed => &["completed"],
+        filter::FilterState::Unknown => &["canceled"],
+    }
+}
+
+// The first state of the team's workflow with the type of the target state
+fn select_state<'a>(states: &'a [structs::WorkflowState], target: &State) -> Option<&'a structs::WorkflowState> {
+    let state_type = match target {
+        State::Uncompleted => "unstarted",
+        State::InProgress => "started",
+        State::Completed => "completed",
+        State::Unknown('-') => "canceled",
+        State::Unknown(_) => return None,
+    };
+
+    states
+        .iter()
+        .filter(|s| s.type_field == state_type)
+        .min_by(|a, b| a.position.total_cmp(&b.position))
+}
+
+#[derive(Clone)]
+pub struct Project {
+    id: String,
+    name: String,
+    description: String,
+    parent_id: Option<String>,
+    provider: String,
+}
+
+impl Project {
+    fn from_team(t: &structs::Team, provider: &str) -> Self {
+        Self {
+            id: t.id.to_string(),
+            name: t.name.to_string(),
+            description: t.key.to_string(),
+            parent_id: None,
+            provider: provider.to_string(),
+        }
+    }
+
+    // Projects can be shared between teams, the first team is used as the parent
+    fn from_project(p: &structs::Project, provider: &str) -> Self {
+        Self {
+            id: p.id.to_string(),
+            name: p.name.to_string(),
+            description: p.description.clone().unwrap_or_default(),
+            parent_id: p.teams.nodes.first().map(|t| t.id.to_string()),
+            provider: provider.to_string(),
+        }
+    }
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        self.description.to_string()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.parent_id.clone()
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct Task {
+    issue: structs::Issue,
+    provider: String,
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        self.issue.id.to_string()
+    }
+
+    fn text(&self) -> String {
+        self.issue.title.to_string()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.issue.description.clone().filter(|d| !d.is_empty())
+    }
+
+    fn priority(&self) -> Priority {
+        priority_from_linear(self.issue.priority)
+    }
+
+    fn state(&self) -> State {
+        state_from_type(&self.issue.state.type_field)
+    }
+
+    fn created_at(&self) -> Option<DateTimeUtc> {
+        self.issue.created_at.as_deref().and_then(str_to_date)
+    }
+
+    fn updated_at(&self) -> Option<DateTimeUtc> {
+        self.issue.updated_at.as_deref().and_then(str_to_date)
+    }
+
+    fn completed_at(&self) -> Option<DateTimeUtc> {
+        self.issue.completed_at.as_deref().and_then(str_to_date)
+    }
+
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.issue.due_date.as_deref().and_then(str_to_date)
+    }
+
+    fn place(&self) -> String {
+        self.issue.url.to_string()
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.issue.labels.nodes.iter().map(|l| l.name.to_string()).collect()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.issue.parent.as_ref().map(|p| p.id.to_string())
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        let p = match &self.issue.project {
+            Some(p) => Project {
+                parent_id: Some(self.issue.team.id.to_string()),
+                ..Project::from_project(p, &self.provider)
+            },
+            None => Project::from_team(&self.issue.team, &self.provider),
+        };
+        Some(Box::new(p))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+fn cast(t: &dyn TaskTrait) -> &Task {
+    match t.as_any().downcast_ref::<Task>() {
+        Some(t) => t,
+        None => panic!("Wrong casting!"),
+    }
+}
+
+pub struct Provider {
+    name: String,
+    color: Color,
+    client: Client,
+    // Workflow states of the teams by their ids
+    team_states: HashMap<String, Vec<structs::WorkflowState>>,
+    tasks: Vec<Task>,
+    last_filter: Option<filter::Filter>,
+}
+
+impl Provider {
+    pub fn new(name: &str, url: Option<&str>, api_key: &str, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            color: *color,
+            client: Client::new(url.unwrap_or(DEFAULT_URL), api_key),
+            team_states: HashMap::new(),
+            tasks: Vec::new(),
+            last_filter: None,
+        }
+    }
+
+    async fn team_states(&mut self, team_id: &str) -> Result<&[structs::WorkflowState], Box<dyn Error>> {
+        if !self.team_states.contains_key(team_id) {
+            let states = self.client.team_states(team_id).await?;
+            self.team_states.insert(team_id.to_string(), states);
+        }
+        Ok(self.team_states.get(team_id).map(|s| s.as_slice()).unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        if self.last_filter.as_ref().is_some_and(|last| last != f) {
+            self.tasks.clear();
+        }
+
+        if self.tasks.is_empty() {
+            let types: Vec<&str> = f.states.iter().flat_map(filter_state_to_types).copied().collect();
+            if !types.is_empty() {
+                for issue in self.client.assigned_issues(&types).await? {
+                    self.tasks.push(Task {
+                        issue,
+                        provider: self.name(),
+                    })
+                }
+            }
+        }
+
+        // A team shows the issues of its projects too
+        let project = project.map(|p| p.id());
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for t in &self.tasks {
+            if project
+                .as_ref()
+                .is_some_and(|p| *p != t.issue.team.id && t.issue.project.as_ref().is_none_or(|tp| tp.id != *p))
+            {
+                continue;
+            }
+            if f.states.contains(&filter::FilterState::from(&t.state())) && f.due.contains(&due_group(t)) {
+                result.push(Box::new(t.clone()));
+            }
+        }
+
+        self.last_filter = Some(f.clone());
+
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        let (teams, projects) = self.client.teams_and_projects().await?;
+
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for t in &teams {
+            result.push(Box::new(Project::from_team(t, &self.name)));
+        }
+        for p in &projects {
+            result.push(Box::new(Project::from_project(p, &self.name)));
+        }
+        Ok(result)
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let t = cast(task);
+
+        let states = self.team_states(&t.issue.team.id).await?;
+        let target = match select_state(states, &state) {
+            Some(s) => s.id.to_string(),
+            None => {
+                return Err(Box::<dyn Error>::from(format!(
+                    "the team {} doesn't have a workflow state for {state:?}",
+                    t.issue.team.name
+                )));
+            }
+        };
+
+        self.client
+            .update_issue(&t.issue.id, &serde_json::json!({ "stateId": target }))
+            .await?;
+
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn update_task(&mut self, patch: &TaskPatch) -> Result<(), Box<dyn Error>> {
+        let t = cast(patch.task.as_ref());
+
+        let mut input = serde_json::json!({});
+        if let Some(text) = &patch.text {
+            input["title"] = serde_json::json!(text);
+        }
+        if let Some(d) = &patch.description {
+            input["description"] = serde_json::json!(d);
+        }
+        match &patch.due {
+            Some(DuePatch::Date(d)) => input["dueDate"] = serde_json::json!(d.format("%Y-%m-%d").to_string()),
+            Some(DuePatch::NoDate) => input["dueDate"] = serde_json::Value::Null,
+            None => {}
+        }
+        if let Some(p) = &patch.priority {
+            input["priority"] = serde_json::json!(priority_to_linear(p));
+        }
+
+        self.client.update_issue(&t.issue.id, &input).await?;
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn reload(&mut self) {
+        self.team_states.clear();
+        self.tasks.clear();
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    #[test]
+    fn priority_test() {
+        struct Case<'a> {
+            name: &'a str,
+            linear: i64,
+            expected: Priority,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "no priority",
+                linear: 0,
+                expected: Priority::Normal,
+            },
+            Case {
+                name: "urgent",
+                linear: 1,
+                expected: Priority::Highest,
+            },
+            Case {
+                name: "high",
+                linear: 2,
+                expected: Priority::High,
+            },
+            Case {
+                name: "medium",
+                linear: 3,
+                expected: Priority::Medium,
+            },
+            Case {
+                name: "low",
+                linear: 4,
+                expected: Priority::Low,
+            },
+        ];
+
+        for c in CASES {
+            let p = priority_from_linear(c.linear);
+            assert_eq!(p, c.expected, "Test '{}' was failed", c.name);
+            assert_eq!(priority_to_linear(&p), c.linear, "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn select_state_test() {
+        let state = |id: &str, type_field: &str, position: f64| structs::WorkflowState {
+            id: id.to_string(),
+            type_field: type_field.to_string(),
+            position,
+        };
+        let states = vec![
+            state("backlog", "backlog", 0.0),
+            state("todo", "unstarted", 1.0),
+            state("review", "started", 3.0),
+            state("in_progress", "started", 2.0),
+            state("done", "completed", 4.0),
+        ];
+
+        let id = |target: State| select_state(&states, &target).map(|s| s.id.as_str());
+        assert_eq!(id(State::Uncompleted), Some("todo"));
+        assert_eq!(id(State::InProgress), Some("in_progress"));
+        assert_eq!(id(State::Completed), Some("done"));
+        assert_eq!(id(State::Unknown('-')), None);
+    }
+
+    #[tokio::test]
+    async fn complete_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        server
+            .mock("POST", "/graphql")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"variables": {"types": ["triage", "backlog", "unstarted", "started"]}}),
+            ))
+            .with_body(
+                serde_json::json!({"data": {"viewer": {"assignedIssues": {
+                    "pageInfo": {"hasNextPage": false, "endCursor": null},
+                    "nodes": [{
+                        "id": "issue-1",
+                        "title": "Fix the login",
+                        "description": null,
+                        "priority": 2,
+                        "dueDate": "2025-01-27",
+                        "url": "https://linear.app/acme/issue/ENG-1",
+                        "labels": {"nodes": [{"name": "Bug"}]},
+                        "state": {"id": "s-progress", "type": "started"},
+                        "team": {"id": "team-1", "name": "Engineering", "key": "ENG"},
+                        "project": {"id": "project-1", "name": "Auth", "description": null},
+                        "parent": null,
+                    }],
+                }}}})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/graphql")
+            .match_body(Matcher::PartialJson(serde_json::json!({"variables": {"id": "team-1"}})))
+            .with_body(
+                serde_json::json!({"data": {"team": {"states": {"nodes": [
+                    {"id": "s-todo", "type": "unstarted", "position": 1},
+                    {"id": "s-progress", "type": "started", "position": 2},
+                    {"id": "s-done", "type": "completed", "position": 3},
+                ]}}}})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let complete = server
+            .mock("POST", "/graphql")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"variables": {"id": "issue-1", "input": {"stateId": "s-done"}}}),
+            ))
+            .with_body(serde_json::json!({"data": {"issueUpdate": {"success": true}}}).to_string())
+            .create_async()
+            .await;
+
+        let mut p = Provider::new(
+            "Linear",
+            Some(&format!("{}/graphql", server.url())),
+            "key",
+            &Color::Cyan,
+        );
+        let f = filter::Filter {
+            states: vec![filter::FilterState::Uncompleted, filter::FilterState::InProgress],
+            due: vec![filter::Due::Overdue],
+            labels: Vec::new(),
+        };
+
+        let team = Project {
+            id: "team-1".to_string(),
+            name: "Engineering".to_string(),
+            description: String::new(),
+            parent_id: None,
+            provider: "Linear".to_string(),
+        };
+        let tasks = p.tasks(Some(team.clone_boxed()), &f).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+
+        let t = &tasks[0];
+        assert_eq!(t.state(), State::InProgress);
+        assert_eq!(t.priority(), Priority::High);
+        assert_eq!(t.labels(), vec!["Bug"]);
+        let project = t.project().unwrap();
+        assert_eq!(project.name(), "Auth");
+        assert_eq!(project.parent_id(), Some("team-1".to_string()));
+
+        p.change_task_state(t.as_ref(), State::Completed).await.unwrap();
+        complete.assert_async().await;
+    }
+}
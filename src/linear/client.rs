@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: MIT
+
+use super::structs::{
+    AssignedIssuesData, Issue, IssueUpdateData, Project, Response, Team, TeamStatesData, TeamsData, WorkflowState,
+};
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+use std::error::Error;
+
+pub const DEFAULT_URL: &str = "https://api.linear.app/graphql";
+
+const ASSIGNED_ISSUES_QUERY: &str = r#"
+query($types: [String!], $cursor: String) {
+  viewer {
+    assignedIssues(first: 100, after: $cursor, filter: {state: {type: {in: $types}}}) {
+      pageInfo { hasNextPage endCursor }
+      nodes {
+        id
+        title
+        description
+        priority
+        dueDate
+        url
+        createdAt
+        updatedAt
+        completedAt
+        labels { nodes { name } }
+        state { id type }
+        team { id name key }
+        project { id name description }
+        parent { id }
+      }
+    }
+  }
+}"#;
+
+const TEAMS_QUERY: &str = r#"
+query {
+  teams(first: 100) { nodes { id name key } }
+  projects(first: 100) { nodes { id name description teams { nodes { id name key } } } }
+}"#;
+
+const TEAM_STATES_QUERY: &str = r#"
+query($id: String!) {
+  team(id: $id) {
+    states { nodes { id type position } }
+  }
+}"#;
+
+const ISSUE_UPDATE_MUTATION: &str = r#"
+mutation($id: String!, $input: IssueUpdateInput!) {
+  issueUpdate(id: $id, input: $input) { success }
+}"#;
+
+pub struct Client {
+    url: String,
+    default_header: HeaderMap,
+    client: reqwest::Client,
+}
+
+impl Client {
+    // Personal API keys are passed as is, without the Bearer prefix
+    pub fn new(url: &str, api_key: &str) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert("Authorization", api_key.parse().unwrap());
+        Self {
+            url: url.to_string(),
+            default_header: headers,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn query<T: DeserializeOwned>(&self, query: &str, variables: serde_json::Value) -> Result<T, Box<dyn Error>> {
+        let resp = self
+            .client
+            .post(&self.url)
+            .headers(self.default_header.clone())
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await?;
+        match resp.status() {
+            s if s.is_success() => {}
+            StatusCode::UNAUTHORIZED => return Err(Box::<dyn Error>::from("the token is invalid or expired")),
+            // Linear answers with 400 on query errors and explains them in the body
+            StatusCode::BAD_REQUEST => {}
+            s => return Err(Box::<dyn Error>::from(format!("wrong status: {}", s.as_str()))),
+        }
+
+        let resp = resp.json::<Response<T>>().await?;
+        if !resp.errors.is_empty() {
+            let messages: Vec<String> = resp.errors.into_iter().map(|e| e.message).collect();
+            return Err(Box::<dyn Error>::from(messages.join(", ")));
+        }
+
+        resp.data
+            .ok_or_else(|| Box::<dyn Error>::from("the response doesn't have data"))
+    }
+
+    // Issues of the authenticated user with the workflow state types
+    pub async fn assigned_issues(&self, types: &[&str]) -> Result<Vec<Issue>, Box<dyn Error>> {
+        let mut result = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let data: AssignedIssuesData = self
+                .query(
+                    ASSIGNED_ISSUES_QUERY,
+                    serde_json::json!({ "types": types, "cursor": cursor }),
+                )
+                .await?;
+            let issues = data.viewer.assigned_issues;
+
+            result.extend(issues.nodes);
+            if !issues.page_info.has_next_page {
+                break;
+            }
+            cursor = issues.page_info.end_cursor;
+        }
+
+        Ok(result)
+    }
+
+    pub async fn teams_and_projects(&self) -> Result<(Vec<Team>, Vec<Project>), Box<dyn Error>> {
+        let data: TeamsData = self.query(TEAMS_QUERY, serde_json::json!({})).await?;
+        Ok((data.teams.nodes, data.projects.nodes))
+    }
+
+    pub async fn team_states(&self, team_id: &str) -> Result<Vec<WorkflowState>, Box<dyn Error>> {
+        let data: TeamStatesData = self
+            .query(TEAM_STATES_QUERY, serde_json::json!({ "id": team_id }))
+            .await?;
+        Ok(data.team.states.nodes)
+    }
+
+    pub async fn update_issue(&self, id: &str, input: &serde_json::Value) -> Result<(), Box<dyn Error>> {
+        let data: IssueUpdateData = self
+            .query(ISSUE_UPDATE_MUTATION, serde_json::json!({ "id": id, "input": input }))
+            .await?;
+        if !data.issue_update.success {
+            return Err(Box::<dyn Error>::from(format!("the issue {id} wasn't updated")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn errors_test() {
+        let mut server = mockito::Server::new_async().await;
+
+        server
+            .mock("POST", "/graphql")
+            .match_header("Authorization", "lin_api_key")
+            .with_status(400)
+            .with_body(r#"{"errors": [{"message": "Entity not found: Team"}]}"#)
+            .create_async()
+            .await;
+
+        let c = Client::new(&format!("{}/graphql", server.url()), "lin_api_key");
+        let err = c.team_states("unknown").await.unwrap_err();
+        assert_eq!(err.to_string(), "Entity not found: Team");
+    }
+}
@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: MIT
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Response<T> {
+    pub data: Option<T>,
+    #[serde(default)]
+    pub errors: Vec<GraphQLError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQLError {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Nodes<T> {
+    pub nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueConnection {
+    pub page_info: PageInfo,
+    pub nodes: Vec<Issue>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignedIssuesData {
+    pub viewer: Viewer,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Viewer {
+    pub assigned_issues: IssueConnection,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Issue {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    // 0 - no priority, 1 - urgent, 2 - high, 3 - medium, 4 - low
+    pub priority: i64,
+    pub due_date: Option<String>,
+    pub url: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub labels: Nodes<Label>,
+    pub state: WorkflowState,
+    pub team: Team,
+    pub project: Option<Project>,
+    pub parent: Option<Parent>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Parent {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WorkflowState {
+    pub id: String,
+    // triage, backlog, unstarted, started, completed or canceled
+    #[serde(rename = "type")]
+    pub type_field: String,
+    #[serde(default)]
+    pub position: f64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Team {
+    pub id: String,
+    pub name: String,
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub teams: Nodes<Team>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamsData {
+    pub teams: Nodes<Team>,
+    pub projects: Nodes<Project>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamStatesData {
+    pub team: TeamStates,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamStates {
+    pub states: Nodes<WorkflowState>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueUpdateData {
+    pub issue_update: Success,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Success {
+    pub success: bool,
+}
@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: MIT
+
+use super::page::{File, Workflow};
+use super::project::{JOURNALS_DIR, PAGES_DIR};
+use super::task::{Task, priority_to_char};
+use crate::filter;
+use crate::provider::DuePatch;
+use crate::task::{Priority, State, due_group};
+use chrono::Local;
+use itertools::Itertools;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+const CONFIG_FILE: &str = "logseq/config.edn";
+
+pub struct TaskPatch<'a> {
+    pub task: &'a Task,
+    pub state: Option<State>,
+    pub text: Option<String>,
+    pub due: Option<DuePatch>,
+    pub priority: Option<Priority>,
+    pub delete: bool,
+}
+
+impl TaskPatch<'_> {
+    fn has_edits(&self) -> bool {
+        self.text.is_some() || self.due.is_some() || self.priority.is_some()
+    }
+
+    fn patched_task(&self) -> Task {
+        let mut t = self.task.clone();
+        if let Some(text) = &self.text {
+            t.text = text.to_string();
+        }
+        match &self.due {
+            Some(DuePatch::Date(d)) => t.deadline = Some(*d),
+            Some(DuePatch::NoDate) => {
+                t.deadline = None;
+                t.scheduled = None;
+            }
+            None => {}
+        }
+        if let Some(p) = &self.priority {
+            t.priority = priority_to_char(p);
+        }
+        t
+    }
+}
+
+pub struct PatchError {
+    pub task: Task,
+    pub error: String,
+}
+
+pub struct Client {
+    path: String,
+}
+
+impl Client {
+    pub fn new(path: &str) -> Self {
+        Self { path: path.to_string() }
+    }
+
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    fn workflow(&self) -> Workflow {
+        fs::read_to_string(Path::new(&self.path).join(CONFIG_FILE))
+            .map(|c| Workflow::from_config(&c))
+            .unwrap_or_default()
+    }
+
+    pub fn all_supported_files(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut result = Vec::new();
+        for dir in [PAGES_DIR, JOURNALS_DIR] {
+            let p = Path::new(&self.path).join(dir);
+            if p.is_dir() {
+                result.append(&mut supported_files(&p)?);
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn tasks(&self, f: &filter::Filter) -> Result<Vec<Task>, Box<dyn Error>> {
+        let mut result = Vec::new();
+
+        for file_path in self.all_supported_files()? {
+            let mut file = File::new(&file_path);
+            if file.open().is_err() {
+                continue;
+            }
+
+            for mut t in file.tasks() {
+                if accept_filter(&t, f) {
+                    t.root_path = self.path.to_string();
+                    result.push(t);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    // Without a page the task is added to today's journal like Logseq does
+    pub fn create_task(&self, page: Option<&str>, t: &Task) -> Result<(), Box<dyn Error>> {
+        let file_path = match page {
+            Some(p) => Path::new(self.path.as_str()).join(p),
+            None => Path::new(self.path.as_str())
+                .join(JOURNALS_DIR)
+                .join(Local::now().format("%Y_%m_%d.md").to_string()),
+        };
+        let file_path = file_path.to_str().unwrap_or_default();
+        if !file_path.ends_with(".md") {
+            return Err(Box::<dyn Error>::from(format!("{file_path} is not a markdown page")));
+        }
+
+        let mut f = File::new(file_path);
+        f.open_or_create()?;
+        f.add_task(t, self.workflow())?;
+        f.flush()
+    }
+
+    fn apply_patch(&self, f: &mut File, p: &TaskPatch, workflow: Workflow) -> Result<(), Box<dyn Error>> {
+        if p.delete {
+            return f.delete_task(p.task);
+        }
+
+        if p.has_edits() {
+            let new_task = p.patched_task();
+            f.update_task(p.task, &new_task)?;
+            if let Some(s) = &p.state {
+                // The block was rewritten, so the state is changed for the updated one
+                let t = Task {
+                    raw: f.raw(p.task.line),
+                    ..new_task
+                };
+                f.change_state(&t, s, workflow)?;
+            }
+            return Ok(());
+        }
+
+        match &p.state {
+            Some(s) => f.change_state(p.task, s, workflow),
+            None => Ok(()),
+        }
+    }
+
+    pub fn patch_tasks<'a>(&self, patches: &'a [TaskPatch<'a>]) -> Vec<PatchError> {
+        let mut errors = Vec::new();
+        let workflow = self.workflow();
+
+        for file in patches.iter().map(|p| p.task.file_path.as_str()).unique() {
+            let mut file_patches = patches
+                .iter()
+                .filter(|p| p.task.file_path == file)
+                .collect::<Vec<&TaskPatch>>();
+            // Changes can add or remove lines, so the tasks are patched from the end of the file
+            file_patches.sort_by_key(|p| std::cmp::Reverse(p.task.line));
+
+            let mut f = File::new(file);
+            if let Err(e) = f.open() {
+                errors.extend(file_patches.iter().map(|p| PatchError {
+                    task: p.task.clone(),
+                    error: e.to_string(),
+                }));
+                continue;
+            }
+
+            for p in &file_patches {
+                if let Err(e) = self.apply_patch(&mut f, p, workflow) {
+                    errors.push(PatchError {
+                        task: p.task.clone(),
+                        error: e.to_string(),
+                    });
+                }
+            }
+
+            if let Err(e) = f.flush() {
+                errors.extend(file_patches.iter().map(|p| PatchError {
+                    task: p.task.clone(),
+                    error: e.to_string(),
+                }));
+            }
+        }
+
+        errors
+    }
+}
+
+fn supported_files(p: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut result: Vec<String> = Vec::new();
+
+    for e in fs::read_dir(p)? {
+        let entry = e?;
+        let path = entry.path();
+        let name = path.file_name().unwrap_or_default().to_str().unwrap_or_default();
+        if path.is_file() && name.ends_with(".md") {
+            if let Some(p) = path.to_str() {
+                result.push(String::from(p));
+            }
+        } else if path.is_dir() {
+            let mut files = supported_files(path.as_path())?;
+            result.append(&mut files);
+        }
+    }
+
+    Ok(result)
+}
+
+fn accept_filter(t: &Task, f: &filter::Filter) -> bool {
+    f.states.contains(&filter::FilterState::from(&t.state)) && f.due.contains(&due_group(t))
+}
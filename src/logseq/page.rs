@@ -0,0 +1,529 @@
+// SPDX-License-Identifier: MIT
+
+use super::task::Task;
+use crate::task::{DateTimeUtc, State, Task as TaskTrait};
+use chrono::{Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use regex::Regex;
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::sync::LazyLock;
+
+static BLOCK_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\s*)-(?:\s+(.*?))?\s*$").unwrap());
+static TASK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(TODO|DOING|DONE|LATER|NOW|WAITING|WAIT|CANCELED|CANCELLED|IN-PROGRESS)(?:\s+(.*))?$").unwrap()
+});
+static PRIORITY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(^|\s)\[#([A-C])\](\s|$)").unwrap());
+static TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(^|\s)#(?:\[\[([^\]]+)\]\]|([\p{L}\p{N}_/\-.]+))").unwrap());
+static PLANNING_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*(DEADLINE|SCHEDULED):\s*<([^>]*)>").unwrap());
+static TIMESTAMP_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{4}-\d{2}-\d{2})(?:\s+[^\s\d]+)?(?:\s+(\d{1,2}:\d{2}))?").unwrap());
+static PROPERTY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*[\w\-]+::").unwrap());
+
+const DEADLINE: &str = "DEADLINE";
+const SCHEDULED: &str = "SCHEDULED";
+
+// Logseq has two sets of markers for the same states: TODO/DOING and LATER/NOW,
+// the preferred one is set by :preferred-workflow in logseq/config.edn
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Workflow {
+    #[default]
+    Todo,
+    Now,
+}
+
+impl Workflow {
+    pub fn from_config(content: &str) -> Self {
+        static WORKFLOW_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r":preferred-workflow\s+:(\w+)").unwrap());
+
+        match WORKFLOW_RE.captures(content) {
+            Some(caps) if &caps[1] == "now" => Workflow::Now,
+            _ => Workflow::Todo,
+        }
+    }
+
+    fn from_marker(marker: &str) -> Option<Self> {
+        match marker {
+            "LATER" | "NOW" => Some(Workflow::Now),
+            "TODO" | "DOING" => Some(Workflow::Todo),
+            _ => None,
+        }
+    }
+}
+
+fn marker_to_state(marker: &str) -> State {
+    match marker {
+        "TODO" | "LATER" => State::Uncompleted,
+        "DOING" | "NOW" | "IN-PROGRESS" => State::InProgress,
+        "DONE" => State::Completed,
+        "WAITING" | "WAIT" => State::Unknown('w'),
+        _ => State::Unknown('-'),
+    }
+}
+
+fn state_to_marker(s: &State, workflow: Workflow) -> Result<&'static str, Box<dyn Error>> {
+    match (s, workflow) {
+        (State::Uncompleted, Workflow::Todo) => Ok("TODO"),
+        (State::Uncompleted, Workflow::Now) => Ok("LATER"),
+        (State::InProgress, Workflow::Todo) => Ok("DOING"),
+        (State::InProgress, Workflow::Now) => Ok("NOW"),
+        (State::Completed, _) => Ok("DONE"),
+        (State::Unknown('w'), _) => Ok("WAITING"),
+        (State::Unknown('-'), _) => Ok("CANCELED"),
+        (State::Unknown(_), _) => Err(Box::<dyn Error>::from(format!(
+            "Logseq tasks can't be in the state {s:?}"
+        ))),
+    }
+}
+
+fn parse_timestamp(s: &str) -> Option<DateTimeUtc> {
+    let caps = TIMESTAMP_RE.captures(s)?;
+    let d = NaiveDate::parse_from_str(&caps[1], "%Y-%m-%d").ok()?;
+
+    match caps.get(2) {
+        Some(t) => {
+            let t = NaiveTime::parse_from_str(t.as_str(), "%H:%M").ok()?;
+            Local
+                .from_local_datetime(&d.and_time(t))
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc))
+        }
+        None => Some(DateTimeUtc::from_naive_utc_and_offset(d.and_time(NaiveTime::MIN), Utc)),
+    }
+}
+
+fn date_timestamp(d: &DateTimeUtc) -> String {
+    d.format("<%Y-%m-%d %a>").to_string()
+}
+
+fn extract_tags(text: &str) -> Vec<String> {
+    TAG_RE
+        .captures_iter(text)
+        .filter_map(|caps| caps.get(2).or(caps.get(3)))
+        .map(|t| t.as_str().to_string())
+        .collect()
+}
+
+fn block_to_string(t: &Task) -> String {
+    let mut parts = vec![format!("{}-", t.indent), t.marker.to_string()];
+    if let Some(p) = t.priority {
+        parts.push(format!("[#{p}]"));
+    }
+    if !t.text.is_empty() {
+        parts.push(t.text.to_string());
+    }
+    parts.join(" ")
+}
+
+fn is_block(l: &str) -> bool {
+    BLOCK_RE.is_match(l)
+}
+
+pub struct File {
+    file_path: String,
+    lines: Vec<String>,
+}
+
+impl File {
+    pub fn new(file_path: &str) -> Self {
+        Self {
+            file_path: file_path.to_string(),
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn open(&mut self) -> Result<(), std::io::Error> {
+        let content = fs::read_to_string(self.file_path.as_str())?;
+        self.lines = content.split('\n').map(|l| l.to_string()).collect();
+        Ok(())
+    }
+
+    // A journal page doesn't exist until something is written to it
+    pub fn open_or_create(&mut self) -> Result<(), std::io::Error> {
+        match self.open() {
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                self.lines = vec![String::new()];
+                Ok(())
+            }
+            r => r,
+        }
+    }
+
+    pub fn flush(&mut self) -> Result<(), Box<dyn Error>> {
+        fs::write(self.file_path.as_str(), self.lines.join("\n"))?;
+        Ok(())
+    }
+
+    pub fn raw(&self, line: usize) -> String {
+        self.lines.get(line).cloned().unwrap_or_default()
+    }
+
+    // Index of the line right after the block, i.e. of the next bullet
+    fn block_end(&self, line: usize) -> usize {
+        self.lines
+            .iter()
+            .enumerate()
+            .skip(line + 1)
+            .find(|(_, l)| is_block(l))
+            .map(|(i, _)| i)
+            .unwrap_or(self.lines.len())
+    }
+
+    fn parse_block(&self, i: usize) -> Option<Task> {
+        let caps = BLOCK_RE.captures(&self.lines[i])?;
+        let task_caps = TASK_RE.captures(caps.get(2)?.as_str())?;
+
+        let marker = task_caps[1].to_string();
+        let mut rest = task_caps.get(2).map(|m| m.as_str()).unwrap_or_default().to_string();
+
+        let mut priority = None;
+        if let Some(p) = PRIORITY_RE.captures(&rest) {
+            priority = p[2].chars().next();
+            rest = PRIORITY_RE.replace(&rest, " ").to_string();
+        }
+        // The tags stay in the text, so editing the task doesn't move them
+        let tags = extract_tags(&rest);
+
+        let mut t = Task {
+            file_path: self.file_path.to_string(),
+            line: i,
+            raw: self.lines[i].to_string(),
+            indent: caps[1].to_string(),
+            state: marker_to_state(&marker),
+            marker,
+            priority,
+            text: rest.trim().to_string(),
+            tags,
+            ..Default::default()
+        };
+
+        let mut description = Vec::new();
+        let mut in_drawer = false;
+        for l in &self.lines[i + 1..self.block_end(i)] {
+            let trimmed = l.trim();
+            if in_drawer {
+                in_drawer = !trimmed.eq_ignore_ascii_case(":END:");
+                continue;
+            }
+            if trimmed.len() > 2 && trimmed.starts_with(':') && trimmed.ends_with(':') && !trimmed.contains(' ') {
+                in_drawer = true;
+                continue;
+            }
+            if let Some(p) = PLANNING_RE.captures(l) {
+                match &p[1] {
+                    DEADLINE => t.deadline = parse_timestamp(&p[2]),
+                    _ => t.scheduled = parse_timestamp(&p[2]),
+                }
+                continue;
+            }
+            if PROPERTY_RE.is_match(l) {
+                continue;
+            }
+            description.push(trimmed);
+        }
+
+        let description = description.join("\n").trim().to_string();
+        if !description.is_empty() {
+            t.description = Some(description);
+        }
+
+        Some(t)
+    }
+
+    pub fn tasks(&self) -> Vec<Task> {
+        let mut result: Vec<Task> = Vec::new();
+        // Indents and ids of the blocks which contain the current one, None for the blocks that aren't tasks
+        let mut parents: Vec<(usize, Option<String>)> = Vec::new();
+
+        for i in 0..self.lines.len() {
+            let indent = match BLOCK_RE.captures(&self.lines[i]) {
+                Some(caps) => caps[1].len(),
+                None => continue,
+            };
+            parents.retain(|(l, _)| *l < indent);
+
+            match self.parse_block(i) {
+                Some(mut t) => {
+                    t.parent_id = parents.iter().rev().find_map(|(_, id)| id.clone());
+                    parents.push((indent, Some(t.id())));
+                    result.push(t);
+                }
+                None => parents.push((indent, None)),
+            }
+        }
+
+        result
+    }
+
+    fn check_task_is_actual(&self, t: &Task) -> Result<(), Box<dyn Error>> {
+        if self.lines.get(t.line) != Some(&t.raw) {
+            return Err(Box::<dyn Error>::from("Task has been changed since last loading"));
+        }
+        Ok(())
+    }
+
+    // Sets or removes the DEADLINE or SCHEDULED line of the block
+    fn set_planning(&mut self, t: &Task, key: &str, value: Option<&DateTimeUtc>) {
+        let end = self.block_end(t.line);
+        let idx = (t.line + 1..end).find(|i| PLANNING_RE.captures(&self.lines[*i]).is_some_and(|c| &c[1] == key));
+
+        match (idx, value) {
+            (Some(i), Some(d)) => {
+                let indent = self.lines[i]
+                    .chars()
+                    .take_while(|c| c.is_whitespace())
+                    .collect::<String>();
+                self.lines[i] = format!("{indent}{key}: {}", date_timestamp(d));
+            }
+            (Some(i), None) => {
+                self.lines.remove(i);
+            }
+            (None, Some(d)) => self
+                .lines
+                .insert(t.line + 1, format!("{}  {key}: {}", t.indent, date_timestamp(d))),
+            (None, None) => {}
+        }
+    }
+
+    pub fn change_state(&mut self, t: &Task, s: &State, default_workflow: Workflow) -> Result<(), Box<dyn Error>> {
+        self.check_task_is_actual(t)?;
+
+        let workflow = Workflow::from_marker(&t.marker).unwrap_or(default_workflow);
+        let marker = state_to_marker(s, workflow)?;
+
+        // Only the marker is replaced, the rest of the block is kept as it's written
+        let start = BLOCK_RE
+            .captures(&t.raw)
+            .and_then(|caps| caps.get(2))
+            .map(|m| m.start())
+            .ok_or("The block has no marker")?;
+        self.lines[t.line] = [&t.raw[..start], marker, &t.raw[start + t.marker.len()..]].join("");
+
+        Ok(())
+    }
+
+    pub fn update_task(&mut self, t: &Task, new_task: &Task) -> Result<(), Box<dyn Error>> {
+        self.check_task_is_actual(t)?;
+
+        self.lines[t.line] = block_to_string(new_task);
+        if new_task.deadline != t.deadline || new_task.scheduled != t.scheduled {
+            self.set_planning(t, DEADLINE, new_task.deadline.as_ref());
+            if new_task.scheduled.is_none() {
+                self.set_planning(t, SCHEDULED, None);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Deletes the block with its properties, the child blocks are kept
+    pub fn delete_task(&mut self, t: &Task) -> Result<(), Box<dyn Error>> {
+        self.check_task_is_actual(t)?;
+
+        let end = self.block_end(t.line);
+        self.lines.drain(t.line..end);
+        if self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        Ok(())
+    }
+
+    pub fn add_task(&mut self, t: &Task, workflow: Workflow) -> Result<(), Box<dyn Error>> {
+        let t = Task {
+            marker: state_to_marker(&State::Uncompleted, workflow)?.to_string(),
+            ..t.clone()
+        };
+
+        // An empty journal page contains the single "-" block which is replaced by the task
+        if self.lines.iter().all(|l| l.trim().is_empty() || l.trim() == "-") {
+            self.lines = vec![String::new()];
+        }
+
+        let mut idx = self.lines.len();
+        if self.lines.last().is_some_and(|l| l.is_empty()) {
+            idx -= 1;
+        }
+
+        let mut block = vec![block_to_string(&t)];
+        if let Some(d) = &t.deadline {
+            block.push(format!("  {DEADLINE}: {}", date_timestamp(d)));
+        }
+        if let Some(d) = &t.description {
+            block.extend(d.lines().map(|l| format!("  {l}")));
+        }
+        self.lines.splice(idx..idx, block);
+
+        if self.lines.last().is_none_or(|l| !l.is_empty()) {
+            self.lines.push(String::new());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logseq::task::priority_to_char;
+    use crate::task::Priority;
+
+    fn file(content: &str) -> File {
+        File {
+            file_path: "/graph/pages/work.md".to_string(),
+            lines: content.split('\n').map(|l| l.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parse_test() {
+        let f = file(
+            "title:: Work
+
+- Project notes #work
+\t- TODO [#A] Write the report #urgent #[[long tag]]
+\t  DEADLINE: <2025-01-27 Mon>
+\t  SCHEDULED: <2025-01-25 Sat 10:00>
+\t  id:: 6512a8d2-4d4b-4c83-9f84-1c1d1a7d7b9a
+\t  :LOGBOOK:
+\t  CLOCK: [2025-01-24 Fri 10:00]
+\t  :END:
+\t  Some notes
+\t\t- NOW Collect data
+\t\t- DONE Ask for access
+- LATER Call Bob
+- WAITING Answer
+- CANCELED Old stuff
+- Just a block with TODO inside",
+        );
+
+        let tasks = f.tasks();
+        assert_eq!(tasks.len(), 6);
+
+        let report = &tasks[0];
+        assert_eq!(report.text(), "Write the report #urgent #[[long tag]]");
+        assert_eq!(report.state(), State::Uncompleted);
+        assert_eq!(report.priority(), Priority::High);
+        assert_eq!(report.labels(), vec!["urgent", "long tag"]);
+        assert_eq!(report.due().unwrap().format("%Y-%m-%d").to_string(), "2025-01-27");
+        assert!(report.scheduled.is_some());
+        assert_eq!(report.description(), Some("Some notes".to_string()));
+        assert_eq!(report.parent_id(), None);
+        assert_eq!(report.place(), "/graph/pages/work.md:4");
+
+        assert_eq!(tasks[1].state(), State::InProgress);
+        assert_eq!(tasks[1].parent_id(), Some(report.id()));
+        assert_eq!(tasks[2].state(), State::Completed);
+        assert_eq!(tasks[2].parent_id(), Some(report.id()));
+        assert_eq!(tasks[3].state(), State::Uncompleted);
+        assert_eq!(tasks[3].parent_id(), None);
+        assert_eq!(tasks[4].state(), State::Unknown('w'));
+        assert_eq!(tasks[5].state(), State::Unknown('-'));
+    }
+
+    #[test]
+    fn change_state_test() {
+        struct Case<'a> {
+            name: &'a str,
+            line: &'a str,
+            state: State,
+            workflow: Workflow,
+            expected: &'a str,
+        }
+        let cases: &[Case] = &[
+            Case {
+                name: "complete TODO",
+                line: "- TODO [#B] Task #tag",
+                state: State::Completed,
+                workflow: Workflow::Todo,
+                expected: "- DONE [#B] Task #tag",
+            },
+            Case {
+                name: "start LATER",
+                line: "\t- LATER Task",
+                state: State::InProgress,
+                workflow: Workflow::Todo,
+                expected: "\t- NOW Task",
+            },
+            Case {
+                name: "reopen DONE with the now workflow",
+                line: "- DONE Task",
+                state: State::Uncompleted,
+                workflow: Workflow::Now,
+                expected: "- LATER Task",
+            },
+            Case {
+                name: "reopen DONE with the todo workflow",
+                line: "- DONE Task",
+                state: State::Uncompleted,
+                workflow: Workflow::Todo,
+                expected: "- TODO Task",
+            },
+            Case {
+                name: "tag in the middle of the sentence",
+                line: "- TODO Call #mom  about  the [[trip]] ",
+                state: State::Completed,
+                workflow: Workflow::Todo,
+                expected: "- DONE Call #mom  about  the [[trip]] ",
+            },
+        ];
+
+        for c in cases {
+            let mut f = file(c.line);
+            let tasks = f.tasks();
+            f.change_state(&tasks[0], &c.state, c.workflow).unwrap();
+            assert_eq!(f.lines.join("\n"), c.expected, "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn update_delete_and_add_test() {
+        let mut f = file("- TODO [#C] Task #tag\n  SCHEDULED: <2025-01-25 Sat>\n  Notes\n\t- TODO Subtask\n");
+        let tasks = f.tasks();
+
+        let due = DateTimeUtc::from_naive_utc_and_offset(
+            NaiveDate::from_ymd_opt(2025, 2, 3).unwrap().and_time(NaiveTime::MIN),
+            Utc,
+        );
+        let new_task = Task {
+            text: "New text #tag".to_string(),
+            priority: priority_to_char(&Priority::Highest),
+            deadline: Some(due),
+            scheduled: None,
+            ..tasks[0].clone()
+        };
+        f.update_task(&tasks[0], &new_task).unwrap();
+        assert_eq!(
+            f.lines.join("\n"),
+            "- TODO [#A] New text #tag\n  DEADLINE: <2025-02-03 Mon>\n  Notes\n\t- TODO Subtask\n"
+        );
+
+        let tasks = f.tasks();
+        f.delete_task(&tasks[0]).unwrap();
+        assert_eq!(f.lines.join("\n"), "\t- TODO Subtask\n");
+
+        let mut f = file("- TODO Call #mom  about  the [[trip]]\n");
+        let tasks = f.tasks();
+        let new_task = Task {
+            priority: priority_to_char(&Priority::Highest),
+            deadline: Some(due),
+            ..tasks[0].clone()
+        };
+        f.update_task(&tasks[0], &new_task).unwrap();
+        assert_eq!(
+            f.lines.join("\n"),
+            "- TODO [#A] Call #mom  about  the [[trip]]\n  DEADLINE: <2025-02-03 Mon>\n"
+        );
+
+        let mut f = file("-\n");
+        f.add_task(
+            &Task {
+                text: "Added".to_string(),
+                deadline: Some(due),
+                ..Default::default()
+            },
+            Workflow::Now,
+        )
+        .unwrap();
+        assert_eq!(f.lines.join("\n"), "- LATER Added\n  DEADLINE: <2025-02-03 Mon>\n");
+    }
+}
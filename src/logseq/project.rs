@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: MIT
+
+use crate::project::Project as ProjectTrait;
+use std::path::Path;
+
+pub const JOURNALS_DIR: &str = "journals";
+pub const PAGES_DIR: &str = "pages";
+
+#[derive(Clone)]
+pub struct Project {
+    provider: String,
+    root_path: String,
+    file_path: String,
+}
+
+impl Project {
+    pub fn new(provider: &str, root_path: &str, file_path: &str) -> Self {
+        Self {
+            provider: provider.to_string(),
+            root_path: root_path.to_string(),
+            file_path: file_path.to_string(),
+        }
+    }
+
+    fn is_journal(&self) -> bool {
+        self.id().starts_with(format!("{JOURNALS_DIR}/").as_str())
+    }
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.file_path
+            .strip_prefix(self.root_path.as_str())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    // Journals are named by their date (2025_01_27.md), namespaced pages use "___" or "%2F" instead of "/"
+    fn name(&self) -> String {
+        let stem = Path::new(&self.file_path)
+            .file_stem()
+            .unwrap_or_default()
+            .to_str()
+            .unwrap_or_default();
+
+        if self.is_journal() {
+            stem.replace('_', "-")
+        } else {
+            stem.replace("___", "/").replace("%2F", "/")
+        }
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        String::new()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        None
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
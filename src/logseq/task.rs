@@ -0,0 +1,130 @@
+// SPDX-License-Identifier: MIT
+
+use super::project::Project;
+use crate::project::Project as ProjectTrait;
+use crate::task::{DateTimeUtc, Priority, State, Task as TaskTrait};
+use sha256::digest;
+use std::any::Any;
+
+const PRIORITIES: [(char, Priority); 3] = [('A', Priority::High), ('B', Priority::Medium), ('C', Priority::Low)];
+
+pub fn priority_from_char(c: char) -> Priority {
+    PRIORITIES
+        .iter()
+        .find(|(x, _)| *x == c)
+        .map(|(_, p)| p.clone())
+        .unwrap_or_default()
+}
+
+pub fn priority_to_char(p: &Priority) -> Option<char> {
+    match p {
+        Priority::Highest | Priority::High => Some('A'),
+        Priority::Medium => Some('B'),
+        Priority::Low | Priority::Lowest => Some('C'),
+        Priority::Normal => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Task {
+    pub root_path: String,
+    pub provider: String,
+
+    pub file_path: String,
+    // Index of the block's first line in the file
+    pub line: usize,
+    pub raw: String,
+
+    // Leading whitespaces of the block's bullet
+    pub indent: String,
+    pub marker: String,
+    pub state: State,
+    pub priority: Option<char>,
+    pub text: String,
+    pub tags: Vec<String>,
+    pub deadline: Option<DateTimeUtc>,
+    pub scheduled: Option<DateTimeUtc>,
+    pub description: Option<String>,
+    pub parent_id: Option<String>,
+}
+
+impl Default for Task {
+    fn default() -> Self {
+        Self {
+            root_path: String::new(),
+            provider: String::new(),
+            file_path: String::new(),
+            line: 0,
+            raw: String::new(),
+            indent: String::new(),
+            marker: String::new(),
+            state: State::Uncompleted,
+            priority: None,
+            text: String::new(),
+            tags: Vec::new(),
+            deadline: None,
+            scheduled: None,
+            description: None,
+            parent_id: None,
+        }
+    }
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        digest(format!("{}:{}:{}", self.file_path, self.line, self.raw))
+    }
+
+    fn text(&self) -> String {
+        self.text.to_string()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.description.clone()
+    }
+
+    fn priority(&self) -> Priority {
+        self.priority.map(priority_from_char).unwrap_or_default()
+    }
+
+    fn state(&self) -> State {
+        self.state.clone()
+    }
+
+    // DEADLINE is the due date, SCHEDULED is used for the tasks without a deadline
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.deadline.or(self.scheduled)
+    }
+
+    fn place(&self) -> String {
+        format!(
+            "{}:{}",
+            self.file_path.strip_prefix(self.root_path.as_str()).unwrap_or_default(),
+            self.line + 1,
+        )
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.tags.clone()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.parent_id.clone()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        Some(Box::new(Project::new(&self.provider, &self.root_path, &self.file_path)))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
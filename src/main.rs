@@ -1,23 +1,40 @@
 // SPDX-License-Identifier: MIT
 
+mod cache;
+mod caldav;
+mod code_todo;
+mod external;
 mod filter;
+mod gitea;
 mod github;
 mod github_issues;
+mod github_notifications;
+mod github_projects;
 mod gitlab;
+mod gitlab_issues;
 mod gitlab_todo;
+mod jira;
+mod linear;
+mod logseq;
+mod markdown;
 mod obsidian;
+mod orgmode;
 mod project;
 mod provider;
 mod settings;
 mod state;
 mod task;
+mod taskwarrior;
 mod todoist;
+mod todotxt;
 mod ui;
 mod wizard;
 use clap::{Parser, Subcommand};
 use color_eyre::owo_colors::OwoColorize;
 use ratatui::style::Color;
 use settings::Settings;
+use std::collections::HashMap;
+use std::time::Duration;
 use ui::style;
 
 #[derive(Parser, Debug)]
@@ -42,12 +59,33 @@ enum Commands {
 
         #[arg(short, long)]
         provider: Option<String>,
+
+        #[arg(short, long)]
+        label: Option<Vec<String>>,
     },
     Projects {
         #[arg(short, long)]
         provider: Option<String>,
     },
     AddProvider {},
+    Add {
+        #[arg(short, long)]
+        provider: String,
+
+        #[arg(long, help("Project id or name (a note path for Obsidian)"))]
+        project: Option<String>,
+
+        #[arg(short, long, value_parser = task::date_from_str, help("YYYY-MM-DD, today or tomorrow"))]
+        due: Option<task::DateTimeUtc>,
+
+        #[arg(long)]
+        priority: Option<task::Priority>,
+
+        #[arg(long)]
+        description: Option<String>,
+
+        text: String,
+    },
 }
 
 fn print_boxed_tasks(tasks: &[Box<dyn task::Task>]) {
@@ -81,10 +119,12 @@ fn due_to_filter(due: &Option<Vec<filter::Due>>) -> Vec<filter::Due> {
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
+    let xdg_dirs = xdg::BaseDirectories::with_prefix("tatuin");
+    let cache = cache::Cache::new(xdg_dirs.get_cache_home());
+
     let mut cfg = if let Some(p) = cli.settings_file {
         Settings::new(p.as_str())
     } else {
-        let xdg_dirs = xdg::BaseDirectories::with_prefix("tatuin");
         let config_path = xdg_dirs
             .place_config_file("settings.toml")
             .expect("cannot create configuration directory");
@@ -92,6 +132,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     let mut providers: Vec<Box<dyn provider::Provider>> = Vec::new();
+    let mut timeouts: HashMap<String, Duration> = HashMap::new();
 
     let mut it = style::PROVIDER_COLORS.iter();
     let mut color = || -> &Color {
@@ -112,6 +153,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
+        if let Some(v) = config.get("timeout") {
+            match v.parse::<u64>() {
+                Ok(secs) => {
+                    timeouts.insert(name.to_string(), Duration::from_secs(secs));
+                }
+                Err(e) => println!("Wrong timeout '{v}' for provider {name}: {e}"),
+            }
+        }
+
         match config.get("type").unwrap().as_str() {
             obsidian::PROVIDER_NAME => {
                 let mut path = config.get("path").unwrap().to_string();
@@ -121,21 +171,191 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                 providers.push(Box::new(obsidian::Provider::new(name, path.as_str(), color())));
             }
+            markdown::PROVIDER_NAME => {
+                let mut path = config.get("path").unwrap().to_string();
+                if !path.ends_with('/') {
+                    path.push('/');
+                }
+                let globs = |key: &str| -> Vec<String> {
+                    config
+                        .get(key)
+                        .map(|v| {
+                            v.split(',')
+                                .map(|g| g.trim().to_string())
+                                .filter(|g| !g.is_empty())
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                };
+                let mut include = globs("include");
+                if include.is_empty() {
+                    include.push(markdown::DEFAULT_INCLUDE.to_string());
+                }
+
+                match markdown::Provider::new(name, path.as_str(), &include, &globs("exclude"), color()) {
+                    Ok(p) => providers.push(Box::new(p)),
+                    Err(e) => println!("Wrong configuration for provider {name}: {e}"),
+                }
+            }
+            orgmode::PROVIDER_NAME => {
+                let mut path = config.get("path").unwrap().to_string();
+                if !path.ends_with('/') {
+                    path.push('/');
+                }
+
+                match orgmode::Provider::new(name, path.as_str(), config.get("keywords").map(|k| k.as_str()), color()) {
+                    Ok(p) => providers.push(Box::new(p)),
+                    Err(e) => println!("Wrong configuration for provider {name}: {e}"),
+                }
+            }
+            logseq::PROVIDER_NAME => {
+                let mut path = config.get("path").unwrap().to_string();
+                if !path.ends_with('/') {
+                    path.push('/');
+                }
+
+                providers.push(Box::new(logseq::Provider::new(name, path.as_str(), color())));
+            }
             todoist::PROVIDER_NAME => providers.push(Box::new(todoist::Provider::new(
                 name,
                 config.get("api_key").unwrap().as_str(),
                 color(),
             ))),
+            gitlab_issues::PROVIDER_NAME => providers.push(Box::new(gitlab_issues::Provider::new(
+                name,
+                config.get("base_url").unwrap().as_str(),
+                config.get("api_key").unwrap().as_str(),
+                color(),
+            ))),
             gitlab_todo::PROVIDER_NAME => providers.push(Box::new(gitlab_todo::Provider::new(
                 name,
                 config.get("base_url").unwrap().as_str(),
                 config.get("api_key").unwrap().as_str(),
                 color(),
             ))),
-            github_issues::PROVIDER_NAME => providers.push(Box::new(github_issues::Provider::new(
+            jira::PROVIDER_NAME => {
+                let get = |key: &str| config.get(key).filter(|v| !v.is_empty()).map(|v| v.to_string());
+                let c = jira::Config {
+                    base_url: config.get("base_url").unwrap().to_string(),
+                    username: get("username"),
+                    api_key: config.get("api_key").unwrap().to_string(),
+                    jql: get("jql"),
+                    issue_type: get("issue_type"),
+                    todo_transition: get("todo_transition"),
+                    in_progress_transition: get("in_progress_transition"),
+                    done_transition: get("done_transition"),
+                };
+                providers.push(Box::new(jira::Provider::new(name, c, color())));
+            }
+            github_notifications::PROVIDER_NAME => providers.push(Box::new(github_notifications::Provider::new(
+                name,
+                config.get("api_key").unwrap().as_str(),
+                config.get("mark_as_done").is_some_and(|v| v == "true"),
+                color(),
+            ))),
+            github_projects::PROVIDER_NAME => {
+                let get = |key: &str| config.get(key).filter(|v| !v.is_empty()).map(|v| v.to_string());
+                let columns = |key: &str, default: Vec<String>| match config.get(key) {
+                    Some(v) => v
+                        .split(',')
+                        .map(|c| c.trim().to_string())
+                        .filter(|c| !c.is_empty())
+                        .collect(),
+                    None => default,
+                };
+                let default_columns = github_projects::Columns::default();
+
+                match config.get("number").unwrap().parse::<i64>() {
+                    Ok(number) => {
+                        let c = github_projects::Config {
+                            url: get("url"),
+                            api_key: config.get("api_key").unwrap().to_string(),
+                            owner: config.get("owner").unwrap().to_string(),
+                            number,
+                            status_field: get("status_field"),
+                            due_field: get("due_field"),
+                            columns: github_projects::Columns {
+                                todo: columns("todo_columns", default_columns.todo),
+                                in_progress: columns("in_progress_columns", default_columns.in_progress),
+                                done: columns("done_columns", default_columns.done),
+                            },
+                        };
+                        providers.push(Box::new(github_projects::Provider::new(name, c, color())));
+                    }
+                    Err(e) => println!("Wrong configuration for provider {name}: {e}"),
+                }
+            }
+            gitea::PROVIDER_NAME => providers.push(Box::new(gitea::Provider::new(
+                name,
+                config.get("base_url").unwrap().as_str(),
+                config.get("api_key").unwrap().as_str(),
+                color(),
+            ))),
+            linear::PROVIDER_NAME => providers.push(Box::new(linear::Provider::new(
                 name,
+                config.get("url").map(|u| u.as_str()),
                 config.get("api_key").unwrap().as_str(),
-                config.get("repository").unwrap().as_str(),
+                color(),
+            ))),
+            github_issues::PROVIDER_NAME => {
+                match github_issues::Provider::new(
+                    name,
+                    config.get("api_key").unwrap().as_str(),
+                    config.get("repository").map(|r| r.as_str()).unwrap_or_default(),
+                    config.get("query").map(|q| q.as_str()),
+                    config.get("in_progress_label").map(|l| l.as_str()),
+                    config.get("exclude_pull_requests").is_some_and(|v| v == "true"),
+                    color(),
+                ) {
+                    Ok(p) => providers.push(Box::new(p)),
+                    Err(e) => println!("Wrong configuration for provider {name}: {e}"),
+                }
+            }
+            todotxt::PROVIDER_NAME => {
+                let todo_path = config.get("path").unwrap().to_string();
+                let done_path = match config.get("done_path") {
+                    Some(p) => p.to_string(),
+                    None => std::path::Path::new(&todo_path)
+                        .with_file_name("done.txt")
+                        .to_str()
+                        .unwrap_or_default()
+                        .to_string(),
+                };
+                let archive = config.get("archive").is_some_and(|a| a == "true");
+
+                providers.push(Box::new(todotxt::Provider::new(
+                    name,
+                    todo_path.as_str(),
+                    done_path.as_str(),
+                    archive,
+                    color(),
+                )));
+            }
+            taskwarrior::PROVIDER_NAME => providers.push(Box::new(taskwarrior::Provider::new(
+                name,
+                config.get("command").map(|c| c.as_str()).unwrap_or("task"),
+                config.get("data_location").map(|d| d.as_str()),
+                color(),
+            ))),
+            code_todo::PROVIDER_NAME => {
+                let mut path = config.get("path").unwrap().to_string();
+                if !path.ends_with('/') {
+                    path.push('/');
+                }
+
+                providers.push(Box::new(code_todo::Provider::new(name, path.as_str(), color())));
+            }
+            caldav::PROVIDER_NAME => providers.push(Box::new(caldav::Provider::new(
+                name,
+                config.get("url").unwrap().as_str(),
+                config.get("username").map(|s| s.as_str()).unwrap_or_default(),
+                config.get("password").map(|s| s.as_str()).unwrap_or_default(),
+                color(),
+            ))),
+            external::PROVIDER_NAME => providers.push(Box::new(external::Provider::new(
+                name,
+                config.get("command").unwrap().as_str(),
+                timeouts.get(name).copied().unwrap_or(provider::DEFAULT_TIMEOUT),
                 color(),
             ))),
             _ => println!("Unknown provider configuration for section: {name}"),
@@ -150,10 +370,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(Commands::Providers {}) => {
             println!("Available providers: {}", wizard::AVAILABLE_PROVIDERS.join(", "));
         }
-        Some(Commands::Tasks { state, due, provider }) => {
+        Some(Commands::Tasks {
+            state,
+            due,
+            provider,
+            label,
+        }) => {
             let f = filter::Filter {
                 states: state_to_filter(state),
                 due: due_to_filter(due),
+                labels: label.clone().unwrap_or_default(),
             };
 
             let mut tasks = Vec::new();
@@ -164,8 +390,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
 
-                tasks.append(&mut p.tasks(None, &f).await?);
+                let timeout = timeouts.get(&p.name()).copied().unwrap_or(provider::DEFAULT_TIMEOUT);
+                match tokio::time::timeout(timeout, p.tasks(None, &f)).await {
+                    Ok(t) => tasks.append(&mut t?),
+                    Err(_) => {
+                        return Err(Box::<dyn std::error::Error>::from(format!(
+                            "Provider {} has no response in {} seconds",
+                            p.name(),
+                            timeout.as_secs()
+                        )));
+                    }
+                }
             }
+            tasks.retain(|t| f.accept_labels(&t.labels()));
             print_boxed_tasks(&tasks);
         }
         Some(Commands::Projects { provider }) => {
@@ -183,6 +420,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             print_projects(&projects);
         }
+        Some(Commands::Add {
+            provider,
+            project,
+            due,
+            priority,
+            description,
+            text,
+        }) => {
+            let p = match providers.iter_mut().find(|p| p.name() == *provider) {
+                Some(p) => p,
+                None => {
+                    return Err(Box::<dyn std::error::Error>::from(format!(
+                        "Unknown provider {provider}"
+                    )));
+                }
+            };
+
+            let project = match project {
+                Some(name) => {
+                    let projects = p.projects().await?;
+                    match projects.iter().find(|pr| pr.id() == *name || pr.name() == *name) {
+                        Some(pr) => Some(pr.clone_boxed()),
+                        None => return Err(Box::<dyn std::error::Error>::from(format!("Unknown project {name}"))),
+                    }
+                }
+                None => None,
+            };
+
+            let t = provider::NewTask {
+                text: text.to_string(),
+                description: description.clone(),
+                due: *due,
+                priority: priority.clone().unwrap_or_default(),
+            };
+            p.create_task(project, &t).await?;
+            println!("The task has been added to {provider}");
+        }
         Some(Commands::AddProvider {}) => {
             let w = wizard::AddProvider {};
             w.run(&mut cfg)?
@@ -190,7 +464,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         _ => {
             color_eyre::install()?;
             let terminal = ratatui::init();
-            let _app_result = ui::App::new(providers, Box::new(cfg)).run(terminal).await;
+            let _app_result = ui::App::new(providers, &timeouts, cache, Box::new(cfg))
+                .run(terminal)
+                .await;
             ratatui::restore();
         }
     };
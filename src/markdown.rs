@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: MIT
+
+// A provider for "- [ ]" checklists in the markdown files of any directory, e.g. TODO.md
+// and docs/*.md of a code repository. The files are parsed the same way as Obsidian notes.
+
+mod client;
+mod project;
+mod task;
+
+use crate::filter;
+use crate::obsidian::client::TaskPatch as MdTaskPatch;
+use crate::obsidian::task::{State as MdState, Task as MdTask};
+use crate::project::Project as ProjectTrait;
+use crate::provider::{NewTask, PatchError, Provider as ProviderTrait, TaskPatch};
+use crate::task::{State, Task as TaskTrait};
+use async_trait::async_trait;
+use ratatui::style::Color;
+use std::error::Error;
+
+pub use client::DEFAULT_INCLUDE;
+
+pub const PROVIDER_NAME: &str = "Markdown";
+
+pub struct Provider {
+    name: String,
+    c: client::Client,
+    color: Color,
+}
+
+impl Provider {
+    pub fn new(
+        name: &str,
+        path: &str,
+        include: &[String],
+        exclude: &[String],
+        color: &Color,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            name: name.to_string(),
+            c: client::Client::new(path, include, exclude)?,
+            color: *color,
+        })
+    }
+}
+
+fn cast(t: &dyn TaskTrait) -> &MdTask {
+    match t.as_any().downcast_ref::<task::Task>() {
+        Some(t) => &t.task,
+        None => panic!("Wrong casting!"),
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        let project = project.map(|p| p.id());
+
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for mut t in self.c.tasks(f).await? {
+            let p = self.c.project(&self.name, &t.file_path);
+            if project.as_ref().is_some_and(|id| *id != p.id()) {
+                continue;
+            }
+
+            t.set_provider(self.name());
+            result.push(Box::new(task::Task { task: t, project: p }));
+        }
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for f in self.c.files() {
+            result.push(Box::new(self.c.project(&self.name, &f)));
+        }
+        Ok(result)
+    }
+
+    async fn create_task(&mut self, project: Option<Box<dyn ProjectTrait>>, t: &NewTask) -> Result<(), Box<dyn Error>> {
+        let p = match project {
+            Some(p) => p,
+            None => return Err(Box::<dyn Error>::from("A file should be chosen to add the task")),
+        };
+
+        if t.description.is_some() {
+            return Err(Box::<dyn Error>::from("Markdown tasks don't have a description"));
+        }
+
+        let task = MdTask {
+            state: MdState::Uncompleted,
+            text: t.text.to_string(),
+            due: t.due,
+            priority: t.priority.clone(),
+            ..Default::default()
+        };
+
+        self.c.create_task(p.id().as_str(), &task).await
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let mut patch = TaskPatch::new(task.clone_boxed());
+        patch.state = Some(state);
+
+        match self.patch_tasks(&[patch]).await.pop() {
+            Some(e) => Err(Box::<dyn Error>::from(e.error)),
+            None => Ok(()),
+        }
+    }
+
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        let mut patch = TaskPatch::new(task.clone_boxed());
+        patch.delete = true;
+
+        match self.patch_tasks(&[patch]).await.pop() {
+            Some(e) => Err(Box::<dyn Error>::from(e.error)),
+            None => Ok(()),
+        }
+    }
+
+    async fn patch_tasks(&mut self, patches: &[TaskPatch]) -> Vec<PatchError> {
+        let mut client_patches = Vec::new();
+        let mut errors = Vec::new();
+        for p in patches.iter() {
+            if p.description.is_some() && !p.delete {
+                errors.push(PatchError {
+                    task: p.task.clone_boxed(),
+                    error: "Markdown tasks don't have a description".to_string(),
+                });
+                continue;
+            }
+
+            client_patches.push(MdTaskPatch {
+                task: cast(p.task.as_ref()),
+                state: p.state.clone().map(|s| s.into()),
+                text: p.text.clone(),
+                due: p.due.clone(),
+                priority: p.priority.clone(),
+                delete: p.delete,
+            });
+        }
+
+        for e in self.c.patch_tasks(&client_patches).await {
+            // Errors are reported for the original tasks to keep their projects
+            let task = match patches.iter().find(|p| *cast(p.task.as_ref()) == e.task) {
+                Some(p) => p.task.clone_boxed(),
+                None => e.task.clone_boxed(),
+            };
+            errors.push(PatchError { task, error: e.error })
+        }
+
+        errors
+    }
+
+    async fn reload(&mut self) {
+        // The files are read on every request
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: MIT
+
+use super::project::Project;
+use crate::filter;
+use crate::obsidian::client::{Client as MdClient, PatchError, TaskPatch};
+use crate::obsidian::task::Task;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use std::error::Error;
+use std::path::Path;
+
+pub const DEFAULT_INCLUDE: &str = "**/*.md";
+
+fn glob_set(globs: &[String]) -> Result<GlobSet, Box<dyn Error>> {
+    let mut builder = GlobSetBuilder::new();
+    for g in globs {
+        // "*" doesn't match "/", so "docs/*.md" doesn't include the nested directories
+        let glob = GlobBuilder::new(g)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| Box::<dyn Error>::from(format!("Wrong glob '{g}': {e}")))?;
+        builder.add(glob);
+    }
+    Ok(builder.build()?)
+}
+
+// Returns the name of the git repository which contains the path and the path inside the repository,
+// the directory name is used for the paths out of a repository
+fn repository(root: &Path) -> (String, String) {
+    let dir_name = |p: &Path| {
+        p.file_name()
+            .unwrap_or_default()
+            .to_str()
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let root = root.canonicalize().unwrap_or(root.to_path_buf());
+    match root.ancestors().find(|p| p.join(".git").exists()) {
+        Some(repo) => {
+            let mut prefix = root
+                .strip_prefix(repo)
+                .unwrap_or(Path::new(""))
+                .to_str()
+                .unwrap_or_default()
+                .to_string();
+            if !prefix.is_empty() {
+                prefix.push('/');
+            }
+            (dir_name(repo), prefix)
+        }
+        None => (dir_name(&root), String::new()),
+    }
+}
+
+pub struct Client {
+    path: String,
+    include: GlobSet,
+    exclude: GlobSet,
+    repo: String,
+    repo_prefix: String,
+    c: MdClient,
+}
+
+impl Client {
+    pub fn new(path: &str, include: &[String], exclude: &[String]) -> Result<Self, Box<dyn Error>> {
+        let (repo, repo_prefix) = repository(Path::new(path));
+
+        Ok(Self {
+            path: path.to_string(),
+            include: glob_set(include)?,
+            exclude: glob_set(exclude)?,
+            repo,
+            repo_prefix,
+            c: MdClient::new(path),
+        })
+    }
+
+    // Markdown files matching the globs, the files ignored by git are skipped
+    pub fn files(&self) -> Vec<String> {
+        let mut result = Vec::new();
+
+        for entry in WalkBuilder::new(&self.path).build().flatten() {
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+
+            let p = match entry.path().to_str() {
+                Some(p) => p,
+                None => continue,
+            };
+            let relative = self.relative_path(p);
+            if self.include.is_match(relative) && !self.exclude.is_match(relative) {
+                result.push(p.to_string());
+            }
+        }
+
+        result.sort();
+        result
+    }
+
+    fn relative_path<'a>(&self, file_path: &'a str) -> &'a str {
+        file_path.strip_prefix(self.path.as_str()).unwrap_or(file_path)
+    }
+
+    pub fn project(&self, provider: &str, file_path: &str) -> Project {
+        Project::new(provider, &self.repo, &self.repo_prefix, self.relative_path(file_path))
+    }
+
+    pub async fn tasks(&self, f: &filter::Filter) -> Result<Vec<Task>, Box<dyn Error>> {
+        self.c.tasks_from_files(self.files(), f).await
+    }
+
+    pub async fn create_task(&self, file_name: &str, t: &Task) -> Result<(), Box<dyn Error>> {
+        self.c.create_task(file_name, t).await
+    }
+
+    pub async fn patch_tasks<'a>(&mut self, patches: &'a [TaskPatch<'a>]) -> Vec<PatchError> {
+        self.c.patch_tasks(patches).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn files_test() {
+        let root = std::env::temp_dir().join(format!("tatuin-markdown-{}", std::process::id()));
+        for dir in ["docs/internal", "target", ".git"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for f in [
+            "TODO.md",
+            "README.txt",
+            "docs/roadmap.md",
+            "docs/internal/notes.md",
+            "target/generated.md",
+        ] {
+            fs::write(root.join(f), "- [ ] task\n").unwrap();
+        }
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+
+        let mut path = root.to_str().unwrap().to_string();
+        path.push('/');
+
+        struct Case<'a> {
+            name: &'a str,
+            include: &'a [&'a str],
+            exclude: &'a [&'a str],
+            expected: &'a [&'a str],
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "default include",
+                include: &[DEFAULT_INCLUDE],
+                exclude: &[],
+                expected: &["TODO.md", "docs/internal/notes.md", "docs/roadmap.md"],
+            },
+            Case {
+                name: "exclude a directory",
+                include: &[DEFAULT_INCLUDE],
+                exclude: &["docs/internal/**"],
+                expected: &["TODO.md", "docs/roadmap.md"],
+            },
+            Case {
+                name: "several includes",
+                include: &["TODO.md", "docs/*.md"],
+                exclude: &[],
+                expected: &["TODO.md", "docs/roadmap.md"],
+            },
+        ];
+
+        for c in CASES {
+            let to_vec = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+            let client = Client::new(&path, &to_vec(c.include), &to_vec(c.exclude)).unwrap();
+            let files = client.files();
+            let files = files.iter().map(|f| client.relative_path(f)).collect::<Vec<&str>>();
+            assert_eq!(files, c.expected, "Test '{}' was failed", c.name);
+
+            let project = client.project("md", &format!("{path}docs/roadmap.md"));
+            assert_eq!(
+                crate::project::Project::name(&project),
+                format!("tatuin-markdown-{}/docs/roadmap", std::process::id()),
+                "Test '{}' was failed",
+                c.name
+            );
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: MIT
+
+use crate::project::Project as ProjectTrait;
+
+#[derive(Debug, Clone)]
+pub struct Project {
+    provider: String,
+    // Name of the git repository (or of the root directory out of a repository)
+    repo: String,
+    // Path of the root directory inside the repository, empty or ending with "/"
+    repo_prefix: String,
+    // Path of the file relative to the root directory
+    file_path: String,
+}
+
+impl Project {
+    pub fn new(provider: &str, repo: &str, repo_prefix: &str, file_path: &str) -> Self {
+        Self {
+            provider: provider.to_string(),
+            repo: repo.to_string(),
+            repo_prefix: repo_prefix.to_string(),
+            file_path: file_path.to_string(),
+        }
+    }
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.file_path.to_string()
+    }
+
+    fn name(&self) -> String {
+        let path = self.file_path.strip_suffix(".md").unwrap_or(&self.file_path);
+        format!("{}/{}{path}", self.repo, self.repo_prefix)
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        String::new()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        None
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
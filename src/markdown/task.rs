@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: MIT
+
+use super::project::Project;
+use crate::obsidian::task::Task as MdTask;
+use crate::project::Project as ProjectTrait;
+use crate::task::{DateTimeUtc, Priority, State, Task as TaskTrait};
+use std::any::Any;
+
+// A checklist item parsed by the Obsidian parser, only the project is different
+#[derive(Clone)]
+pub struct Task {
+    pub task: MdTask,
+    pub project: Project,
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        self.task.id()
+    }
+
+    fn text(&self) -> String {
+        self.task.text()
+    }
+
+    fn description(&self) -> Option<String> {
+        self.task.description()
+    }
+
+    fn priority(&self) -> Priority {
+        self.task.priority()
+    }
+
+    fn state(&self) -> State {
+        self.task.state()
+    }
+
+    fn completed_at(&self) -> Option<DateTimeUtc> {
+        self.task.completed_at()
+    }
+
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.task.due()
+    }
+
+    fn place(&self) -> String {
+        self.task.place()
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.task.labels()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.task.parent_id()
+    }
+
+    fn recurrence(&self) -> Option<String> {
+        self.task.recurrence()
+    }
+
+    fn provider(&self) -> String {
+        self.task.provider()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        Some(Box::new(self.project.clone()))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
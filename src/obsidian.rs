@@ -1,13 +1,14 @@
 // SPDX-License-Identifier: MIT
 
-mod client;
+pub mod client;
 mod md_file;
 mod project;
-mod task;
+mod recurrence;
+pub mod task;
 
 use crate::filter;
 use crate::project::Project as ProjectTrait;
-use crate::provider::{PatchError, Provider as ProviderTrait, TaskPatch};
+use crate::provider::{NewTask, PatchError, Provider as ProviderTrait, TaskPatch};
 use crate::task::{State, Task as TaskTrait};
 use async_trait::async_trait;
 use ratatui::style::Color;
@@ -56,7 +57,32 @@ impl ProviderTrait for Provider {
     }
 
     async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
-        Ok(Vec::new())
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for f in self.c.all_supported_files()? {
+            result.push(Box::new(project::Project::new(&self.name, self.c.path(), &f)));
+        }
+        Ok(result)
+    }
+
+    async fn create_task(&mut self, project: Option<Box<dyn ProjectTrait>>, t: &NewTask) -> Result<(), Box<dyn Error>> {
+        let p = match project {
+            Some(p) => p,
+            None => return Err(Box::<dyn Error>::from("A note should be chosen to add the task")),
+        };
+
+        if t.description.is_some() {
+            return Err(Box::<dyn Error>::from("Obsidian tasks don't have a description"));
+        }
+
+        let task = task::Task {
+            state: task::State::Uncompleted,
+            text: t.text.to_string(),
+            due: t.due,
+            priority: t.priority.clone(),
+            ..Default::default()
+        };
+
+        self.c.create_task(p.id().as_str(), &task).await
     }
 
     async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
@@ -68,24 +94,34 @@ impl ProviderTrait for Provider {
         self.c.change_state(t, state.into()).await
     }
 
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        let t: &task::Task = match task.as_any().downcast_ref::<task::Task>() {
+            Some(t) => t,
+            None => panic!("Wrong casting!"),
+        };
+
+        self.c.delete_task(t).await
+    }
+
     async fn patch_tasks(&mut self, patches: &[TaskPatch]) -> Vec<PatchError> {
         let mut client_patches = Vec::new();
         let mut errors = Vec::new();
         for p in patches.iter() {
-            let st = match &p.state {
-                Some(s) => s,
-                None => {
-                    errors.push(PatchError {
-                        task: p.task.clone_boxed(),
-                        error: "The only state changing is implemented".to_string(),
-                    });
-                    continue;
-                }
-            };
+            if p.description.is_some() && !p.delete {
+                errors.push(PatchError {
+                    task: p.task.clone_boxed(),
+                    error: "Obsidian tasks don't have a description".to_string(),
+                });
+                continue;
+            }
             match p.task.as_any().downcast_ref::<task::Task>() {
                 Some(t) => client_patches.push(client::TaskPatch {
                     task: t,
-                    state: st.clone().into(),
+                    state: p.state.clone().map(|s| s.into()),
+                    text: p.text.clone(),
+                    due: p.due.clone(),
+                    priority: p.priority.clone(),
+                    delete: p.delete,
                 }),
                 None => panic!("Wrong casting!"),
             };
@@ -3,7 +3,8 @@
 use crate::filter;
 use crate::obsidian::md_file;
 use crate::obsidian::task::{State, Task};
-use crate::task::due_group;
+use crate::provider::DuePatch;
+use crate::task::{Priority, due_group};
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::error::Error;
@@ -16,7 +17,36 @@ const SIMULTANEOUS_JOB_COUNT: usize = 10;
 
 pub struct TaskPatch<'a> {
     pub task: &'a Task,
-    pub state: State,
+    pub state: Option<State>,
+    pub text: Option<String>,
+    pub due: Option<DuePatch>,
+    pub priority: Option<Priority>,
+    pub delete: bool,
+}
+
+impl TaskPatch<'_> {
+    fn is_state_only(&self) -> bool {
+        self.text.is_none() && self.due.is_none() && self.priority.is_none()
+    }
+
+    fn patched_task(&self) -> Task {
+        let mut t = self.task.clone();
+        if let Some(s) = &self.state {
+            t.state = s.clone();
+        }
+        if let Some(text) = &self.text {
+            t.text = text.to_string();
+        }
+        match &self.due {
+            Some(DuePatch::Date(d)) => t.due = Some(*d),
+            Some(DuePatch::NoDate) => t.due = None,
+            None => {}
+        }
+        if let Some(p) = &self.priority {
+            t.priority = p.clone();
+        }
+        t
+    }
 }
 
 pub struct PatchError {
@@ -35,13 +65,21 @@ impl Client {
         }
     }
 
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
     pub fn all_supported_files(&self) -> Result<Vec<String>, Box<dyn Error>> {
         supported_files(Path::new(self.path.as_str()))
     }
 
     pub async fn tasks(&self, f: &filter::Filter) -> Result<Vec<Task>, Box<dyn Error>> {
         let files = self.all_supported_files()?;
+        self.tasks_from_files(files, f).await
+    }
 
+    // Parses the given markdown files, the files don't have to be in an Obsidian vault
+    pub async fn tasks_from_files(&self, files: Vec<String>, f: &filter::Filter) -> Result<Vec<Task>, Box<dyn Error>> {
         let mut tasks: Vec<Task> = Vec::new();
 
         let semaphore = Arc::new(Semaphore::new(SIMULTANEOUS_JOB_COUNT));
@@ -93,6 +131,26 @@ impl Client {
         f.flush()
     }
 
+    pub async fn delete_task(&self, t: &Task) -> Result<(), Box<dyn Error>> {
+        let mut f = md_file::File::new(&t.file_path);
+        f.open()?;
+        f.delete_task(t).await?;
+        f.flush()
+    }
+
+    pub async fn create_task(&self, file_name: &str, t: &Task) -> Result<(), Box<dyn Error>> {
+        let file_path = Path::new(self.path.as_str()).join(file_name);
+        let file_path = file_path.to_str().unwrap_or_default();
+        if !file_path.ends_with(".md") {
+            return Err(Box::<dyn Error>::from(format!("{file_name} is not a markdown file")));
+        }
+
+        let mut f = md_file::File::new(file_path);
+        f.open()?;
+        f.add_task(t).await?;
+        f.flush()
+    }
+
     pub async fn patch_tasks<'a>(&mut self, patches: &'a [TaskPatch<'a>]) -> Vec<PatchError> {
         let mut errors = Vec::new();
 
@@ -121,7 +179,15 @@ impl Client {
                 .collect::<Vec<&'a TaskPatch>>();
             file_patches.sort_by_key(|p| std::cmp::Reverse(p.task.start_pos));
             for p in file_patches {
-                if let Err(e) = f.change_state(p.task, p.state.clone()).await {
+                let result = if p.delete {
+                    f.delete_task(p.task).await
+                } else {
+                    match &p.state {
+                        Some(s) if p.is_state_only() => f.change_state(p.task, s.clone()).await,
+                        _ => f.update_task(p.task, &p.patched_task()).await,
+                    }
+                };
+                if let Err(e) = result {
                     errors.push(PatchError {
                         task: p.task.clone(),
                         error: e.to_string(),
@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: MIT
 
+use crate::obsidian::recurrence;
 use crate::obsidian::task::{State, Task};
-use crate::task::{DateTimeUtc, Priority};
+use crate::task::{DateTimeUtc, Priority, Task as TaskTrait};
 use chrono::{NaiveDate, Utc};
 use regex::Regex;
 use std::error::Error;
@@ -9,8 +10,19 @@ use std::fs;
 use std::sync::LazyLock;
 
 static TASK_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*-\ \[(.)\]\ (.*)$").unwrap());
+static TAG_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(^|\s)#([\p{L}\p{N}_/\-]+)").unwrap());
 const DUE_EMOJI: char = '📅';
 const COMPLETED_EMOJI: char = '✅';
+const RECURRENCE_EMOJI: char = '🔁';
+// Other emojis of the Tasks plugin: the recurrence rule lasts until one of them
+const SIGNIFIER_EMOJIS: [char; 11] = ['📅', '✅', '🔁', '⏳', '🛫', '➕', '⏬', '🔽', '🔼', '⏫', '🔺'];
+const PRIORITY_SYMBOLS: [(char, Priority); 5] = [
+    ('⏬', Priority::Lowest),
+    ('🔽', Priority::Low),
+    ('🔼', Priority::Medium),
+    ('⏫', Priority::High),
+    ('🔺', Priority::Highest),
+];
 
 pub struct File {
     file_path: String,
@@ -47,12 +59,30 @@ impl File {
         Ok(())
     }
 
+    pub async fn update_task(&mut self, t: &Task, new_task: &Task) -> Result<(), Box<dyn Error>> {
+        self.content = self.update_task_in_content(t, new_task, self.content.as_str())?;
+        Ok(())
+    }
+
+    pub async fn delete_task(&mut self, t: &Task) -> Result<(), Box<dyn Error>> {
+        self.content = self.delete_task_from_content(t, self.content.as_str())?;
+        Ok(())
+    }
+
+    pub async fn add_task(&mut self, t: &Task) -> Result<(), Box<dyn Error>> {
+        self.content = add_task_to_content(t, self.content.as_str());
+        Ok(())
+    }
+
     fn try_parse_task(&self, line: &str, pos: usize) -> Option<Task> {
         if let Some(caps) = TASK_RE.captures(line) {
             let text = String::from(&caps[2]);
             let (text, due) = extract_date_after_emoji(text.as_str(), DUE_EMOJI);
             let (text, completed_at) = extract_date_after_emoji(text.as_str(), COMPLETED_EMOJI);
             let (text, priority) = parse_priority(text.as_str());
+            // The tags stay in the text, so editing the task doesn't move them
+            let labels = extract_tags(text.as_str());
+            let (text, recurrence) = extract_recurrence(text.as_str());
             return Some(Task {
                 file_path: self.file_path.to_string(),
                 start_pos: pos,
@@ -70,6 +100,8 @@ impl File {
                 due,
                 priority,
                 completed_at,
+                labels,
+                recurrence,
                 ..Default::default()
             });
         }
@@ -83,10 +115,19 @@ impl File {
         let mut result: Vec<Task> = Vec::new();
 
         let mut pos: usize = 0;
+        // Indentation and id of the tasks that can be a parent of the next line
+        let mut parents: Vec<(usize, String)> = Vec::new();
 
         for l in content.split(SPLIT_TERMINATOR) {
-            if let Some(t) = self.try_parse_task(l, pos) {
-                result.push(t);
+            if !l.trim().is_empty() {
+                let indent = l.chars().take_while(|c| c.is_whitespace()).count();
+                parents.retain(|(i, _)| *i < indent);
+
+                if let Some(mut t) = self.try_parse_task(l, pos) {
+                    t.parent_id = parents.last().map(|(_, id)| id.clone());
+                    parents.push((indent, t.id()));
+                    result.push(t);
+                }
             }
 
             pos += l.chars().count() + SPLIT_TERMINATOR.len();
@@ -95,7 +136,7 @@ impl File {
         Ok(result)
     }
 
-    fn change_state_in_content(&self, t: &Task, s: State, content: &str) -> Result<String, Box<dyn Error>> {
+    fn check_task_is_actual(&self, t: &Task, content: &str) -> Result<(), Box<dyn Error>> {
         let line = content
             .chars()
             .skip(t.start_pos)
@@ -117,6 +158,61 @@ impl File {
             }
         }
 
+        Ok(())
+    }
+
+    fn update_task_in_content(&self, t: &Task, new_task: &Task, content: &str) -> Result<String, Box<dyn Error>> {
+        self.check_task_is_actual(t, content)?;
+
+        let indent = content
+            .chars()
+            .skip(t.start_pos)
+            .take_while(|c| c.is_whitespace())
+            .collect::<String>();
+
+        let mut new_task = new_task.clone();
+        if new_task.state != State::Completed {
+            new_task.completed_at = None;
+        } else if t.state != State::Completed {
+            new_task.completed_at = Some(chrono::Utc::now());
+        }
+
+        let result = [
+            content.chars().take(t.start_pos).collect::<String>(),
+            indent,
+            task_to_string(&new_task),
+            content.chars().skip(t.end_pos).collect::<String>(),
+        ]
+        .join("");
+
+        if new_task.state == State::Completed && t.state != State::Completed {
+            return Ok(insert_next_occurrence(&new_task, t.start_pos, result));
+        }
+
+        Ok(result)
+    }
+
+    fn delete_task_from_content(&self, t: &Task, content: &str) -> Result<String, Box<dyn Error>> {
+        self.check_task_is_actual(t, content)?;
+
+        let (start, end) = if content.chars().nth(t.end_pos) == Some('\n') {
+            (t.start_pos, t.end_pos + 1)
+        } else if t.start_pos > 0 {
+            (t.start_pos - 1, t.end_pos)
+        } else {
+            (t.start_pos, t.end_pos)
+        };
+
+        Ok([
+            content.chars().take(start).collect::<String>(),
+            content.chars().skip(end).collect::<String>(),
+        ]
+        .join(""))
+    }
+
+    fn change_state_in_content(&self, t: &Task, s: State, content: &str) -> Result<String, Box<dyn Error>> {
+        self.check_task_is_actual(t, content)?;
+
         let mut pos_found = false;
         let mut found = false;
         let result: String = content
@@ -137,12 +233,18 @@ impl File {
             .collect();
 
         if s == State::Completed {
-            Ok([
+            let result = [
                 result.chars().take(t.end_pos).collect::<String>(),
                 format!(" {COMPLETED_EMOJI} {}", chrono::Utc::now().format("%Y-%m-%d")),
                 result.chars().skip(t.end_pos).collect::<String>(),
             ]
-            .join(""))
+            .join("");
+
+            if t.state != State::Completed {
+                return Ok(insert_next_occurrence(t, t.start_pos, result));
+            }
+
+            Ok(result)
         } else {
             let task: String = result.chars().skip(t.start_pos).take(t.end_pos - t.start_pos).collect();
             let (task, _) = extract_date_after_emoji(task.as_str(), COMPLETED_EMOJI);
@@ -157,6 +259,80 @@ impl File {
     }
 }
 
+// Inserts the next occurrence of a recurring task above it like the Tasks plugin does.
+// The task is completed anyway if the rule isn't supported, only the next occurrence is skipped.
+fn insert_next_occurrence(t: &Task, pos: usize, content: String) -> String {
+    let rule = match &t.recurrence {
+        Some(r) => r,
+        None => return content,
+    };
+
+    let base = match t.due {
+        Some(d) if !recurrence::is_when_done(rule) => d.date_naive(),
+        _ => chrono::Local::now().date_naive(),
+    };
+    let next = match recurrence::next_date(rule, base) {
+        Ok(d) => d,
+        Err(_) => return content,
+    };
+
+    let next_task = Task {
+        state: State::Uncompleted,
+        due: next
+            .and_hms_opt(0, 0, 0)
+            .map(|dt| DateTimeUtc::from_naive_utc_and_offset(dt, Utc)),
+        completed_at: None,
+        ..t.clone()
+    };
+
+    let indent = content
+        .chars()
+        .skip(pos)
+        .take_while(|c| c.is_whitespace())
+        .collect::<String>();
+
+    [
+        content.chars().take(pos).collect::<String>(),
+        indent,
+        task_to_string(&next_task),
+        "\n".to_string(),
+        content.chars().skip(pos).collect::<String>(),
+    ]
+    .join("")
+}
+
+fn add_task_to_content(t: &Task, content: &str) -> String {
+    let mut result = content.to_string();
+    if !result.is_empty() && !result.ends_with('\n') {
+        result.push('\n');
+    }
+    result.push_str(task_to_string(t).as_str());
+    result.push('\n');
+    result
+}
+
+fn task_to_string(t: &Task) -> String {
+    let mut result = format!("- [{}] {}", char::from(t.state.clone()), t.text);
+
+    if let Some((symbol, _)) = PRIORITY_SYMBOLS.iter().find(|(_, p)| *p == t.priority) {
+        result.push_str(format!(" {symbol}").as_str());
+    }
+
+    if let Some(r) = &t.recurrence {
+        result.push_str(format!(" {RECURRENCE_EMOJI} {r}").as_str());
+    }
+
+    if let Some(d) = t.due {
+        result.push_str(format!(" {DUE_EMOJI} {}", d.format("%Y-%m-%d")).as_str());
+    }
+
+    if let Some(d) = t.completed_at {
+        result.push_str(format!(" {COMPLETED_EMOJI} {}", d.format("%Y-%m-%d")).as_str());
+    }
+
+    result
+}
+
 fn extract_date_after_emoji(text: &str, emoji: char) -> (String, Option<DateTimeUtc>) {
     let start = format!(" {emoji} ");
     let idx = text.rfind(start.as_str());
@@ -186,16 +362,46 @@ fn extract_date_after_emoji(text: &str, emoji: char) -> (String, Option<DateTime
     (text.to_string(), None)
 }
 
+fn extract_recurrence(text: &str) -> (String, Option<String>) {
+    let start = format!(" {RECURRENCE_EMOJI} ");
+    let idx = match text.find(start.as_str()) {
+        Some(idx) => idx,
+        None => return (text.to_string(), None),
+    };
+
+    let rule_start = idx + start.len();
+    let rule_end = text[rule_start..]
+        .find(|c| SIGNIFIER_EMOJIS.contains(&c))
+        .map(|i| rule_start + i)
+        .unwrap_or(text.len());
+
+    let rule = text[rule_start..rule_end].trim();
+    if rule.is_empty() {
+        return (text.to_string(), None);
+    }
+
+    let rest = &text[rule_end..];
+    let text = if rest.is_empty() {
+        text[..idx].to_string()
+    } else {
+        [&text[..idx], rest].join(" ")
+    };
+
+    (text, Some(rule.to_string()))
+}
+
+fn extract_tags(text: &str) -> Vec<String> {
+    TAG_RE
+        .captures_iter(text)
+        .map(|caps| caps[2].to_string())
+        // Obsidian doesn't treat numbers like #123 as tags
+        .filter(|tag| !tag.chars().all(|c| c.is_ascii_digit()))
+        .collect()
+}
+
 fn parse_priority(text: &str) -> (String, Priority) {
-    let symbols = vec![
-        ('⏬', Priority::Lowest),
-        ('🔽', Priority::Low),
-        ('🔼', Priority::Medium),
-        ('⏫', Priority::High),
-        ('🔺', Priority::Highest),
-    ];
     let mut symbol_indexes = Vec::new();
-    for (s, p) in symbols {
+    for (s, p) in PRIORITY_SYMBOLS {
         if let Some(idx) = text.chars().position(|c| c == s) {
             if idx != 0
                 && text.chars().nth(idx - 1).unwrap_or(' ') == ' '
@@ -299,6 +505,68 @@ some another text
         }
     }
 
+    #[test]
+    fn parse_parents_test() {
+        struct Case<'a> {
+            name: &'a str,
+            file_content: &'a str,
+            parents: &'a [Option<usize>],
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "flat list",
+                file_content: "- [ ] Task 1
+- [ ] Task 2
+",
+                parents: &[None, None],
+            },
+            Case {
+                name: "nested tasks",
+                file_content: "- [ ] Task 1
+    - [ ] Task 1.1
+        - [ ] Task 1.1.1
+    - [ ] Task 1.2
+- [ ] Task 2
+	- [ ] Task 2.1
+",
+                parents: &[None, Some(0), Some(1), Some(0), None, Some(4)],
+            },
+            Case {
+                name: "nested task under not a task",
+                file_content: "- [ ] Task 1
+- Not a task
+    - [ ] Task 2
+",
+                parents: &[None, None],
+            },
+            Case {
+                name: "text between tasks breaks the hierarchy",
+                file_content: "- [ ] Task 1
+# Header
+    - [ ] Task 2
+
+    - [ ] Task 3
+",
+                parents: &[None, None, None],
+            },
+        ];
+
+        let p = File::new("");
+
+        for c in CASES {
+            let tasks = p.tasks_from_content(c.file_content).unwrap();
+            let parents = tasks
+                .iter()
+                .map(|t| {
+                    t.parent_id
+                        .as_ref()
+                        .and_then(|id| tasks.iter().position(|p| p.id() == *id))
+                })
+                .collect::<Vec<Option<usize>>>();
+            assert_eq!(parents, c.parents, "Test '{}' was failed", c.name);
+        }
+    }
+
     #[test]
     fn check_all_fields_parsed_test() {
         let text = format!("- [x] Some text ⏫ {DUE_EMOJI} 2025-01-01 {COMPLETED_EMOJI} 2025-01-01");
@@ -559,6 +827,332 @@ Some another text";
         assert_eq!(27, tasks[0].end_pos);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn update_task_in_content_test() {
+        struct Case<'a> {
+            name: &'a str,
+            file_content_before: &'a str,
+            change: fn(&mut Task),
+            file_content_after: String,
+        }
+        let completed_string = format!("✅ {}", chrono::Utc::now().format("%Y-%m-%d"));
+        let cases: &[Case] = &[
+            Case {
+                name: "change text",
+                file_content_before: "some text\n- [ ] Some text ⏫ 📅 2025-01-27\nsome another text",
+                change: |t| t.text = "Another text".to_string(),
+                file_content_after: "some text\n- [ ] Another text ⏫ 📅 2025-01-27\nsome another text".to_string(),
+            },
+            Case {
+                name: "change due of the task with tags in the middle",
+                file_content_before: "- [ ] Call #mom about the #trip plans 📅 2025-01-27",
+                change: |t| t.due = None,
+                file_content_after: "- [ ] Call #mom about the #trip plans".to_string(),
+            },
+            Case {
+                name: "change due and keep the indentation",
+                file_content_before: "    - [ ] Some text 📅 2025-01-27",
+                change: |t| {
+                    t.due = Some(DateTimeUtc::from_naive_utc_and_offset(
+                        NaiveDate::parse_from_str("2025-02-01", "%Y-%m-%d")
+                            .unwrap()
+                            .and_hms_opt(0, 0, 0)
+                            .unwrap(),
+                        Utc,
+                    ))
+                },
+                file_content_after: "    - [ ] Some text 📅 2025-02-01".to_string(),
+            },
+            Case {
+                name: "remove due",
+                file_content_before: "- [ ] Some text 📅 2025-01-27",
+                change: |t| t.due = None,
+                file_content_after: "- [ ] Some text".to_string(),
+            },
+            Case {
+                name: "change priority",
+                file_content_before: "- [ ] Some text ⏫ 📅 2025-01-27",
+                change: |t| t.priority = Priority::Lowest,
+                file_content_after: "- [ ] Some text ⏬ 📅 2025-01-27".to_string(),
+            },
+            Case {
+                name: "remove priority",
+                file_content_before: "- [ ] Some text 🔺",
+                change: |t| t.priority = Priority::Normal,
+                file_content_after: "- [ ] Some text".to_string(),
+            },
+            Case {
+                name: "change priority and state",
+                file_content_before: "- [ ] Some text",
+                change: |t| {
+                    t.priority = Priority::High;
+                    t.state = State::Completed;
+                },
+                file_content_after: format!("- [x] Some text ⏫ {completed_string}"),
+            },
+            Case {
+                name: "cyrillic",
+                file_content_before: "какой-то текст\n- [x] Текст задачи ✅ 2025-01-01\nдлинный текст",
+                change: |t| t.text = "Другой текст".to_string(),
+                file_content_after: "какой-то текст\n- [x] Другой текст ✅ 2025-01-01\nдлинный текст".to_string(),
+            },
+        ];
+
+        let p = File::new("");
+
+        for c in cases {
+            let tasks = p.tasks_from_content(c.file_content_before).unwrap();
+            assert_eq!(1, tasks.len(), "Test '{}' was failed", c.name);
+            let mut new_task = tasks[0].clone();
+            (c.change)(&mut new_task);
+            let r = p.update_task_in_content(&tasks[0], &new_task, c.file_content_before);
+            assert!(r.is_ok(), "{}: {}", c.name, r.unwrap_err());
+            assert_eq!(c.file_content_after, r.unwrap(), "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn update_changed_task_in_content_test() {
+        let p = File::new("");
+        let tasks = p.tasks_from_content("- [ ] Some text").unwrap();
+        let mut new_task = tasks[0].clone();
+        new_task.text = "Another text".to_string();
+
+        let r = p.update_task_in_content(&tasks[0], &new_task, "- [ ] Changed text");
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn delete_task_from_content_test() {
+        struct Case<'a> {
+            name: &'a str,
+            file_content_before: &'a str,
+            file_content_after: &'a str,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "content contain the single task and nothing else",
+                file_content_before: "- [ ] Some text",
+                file_content_after: "",
+            },
+            Case {
+                name: "task in the middle",
+                file_content_before: "some text\n- [ ] Some text\nsome another text\n",
+                file_content_after: "some text\nsome another text\n",
+            },
+            Case {
+                name: "task at the end without new line",
+                file_content_before: "some text\n- [x] Some text ✅ 2025-01-01",
+                file_content_after: "some text",
+            },
+            Case {
+                name: "content contain cyrillic",
+                file_content_before: "какой-то текст\n    - [ ] Текст задачи\nдлинный текст в конце\n",
+                file_content_after: "какой-то текст\nдлинный текст в конце\n",
+            },
+        ];
+
+        let p = File::new("");
+
+        for c in CASES {
+            let tasks = p.tasks_from_content(c.file_content_before).unwrap();
+            assert_eq!(1, tasks.len(), "Test '{}' was failed", c.name);
+            let r = p.delete_task_from_content(&tasks[0], c.file_content_before);
+            assert!(r.is_ok(), "{}: {}", c.name, r.unwrap_err());
+            assert_eq!(c.file_content_after, r.unwrap(), "Test '{}' was failed", c.name);
+        }
+
+        let tasks = p.tasks_from_content("- [ ] Some text").unwrap();
+        assert!(p.delete_task_from_content(&tasks[0], "- [ ] Changed text").is_err());
+    }
+
+    #[test]
+    fn add_task_to_content_test() {
+        struct Case<'a> {
+            name: &'a str,
+            file_content_before: &'a str,
+            task: Task,
+            file_content_after: &'a str,
+        }
+        let due = Some(DateTimeUtc::from_naive_utc_and_offset(
+            NaiveDate::parse_from_str("2025-01-27", "%Y-%m-%d")
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+            Utc,
+        ));
+        let cases: &[Case] = &[
+            Case {
+                name: "empty content",
+                file_content_before: "",
+                task: Task {
+                    state: State::Uncompleted,
+                    text: "Some text".to_string(),
+                    ..Default::default()
+                },
+                file_content_after: "- [ ] Some text\n",
+            },
+            Case {
+                name: "content without trailing new line",
+                file_content_before: "some text",
+                task: Task {
+                    state: State::Uncompleted,
+                    text: "Some text".to_string(),
+                    ..Default::default()
+                },
+                file_content_after: "some text\n- [ ] Some text\n",
+            },
+            Case {
+                name: "task with due and priority",
+                file_content_before: "some text\n",
+                task: Task {
+                    state: State::Uncompleted,
+                    text: "Some text".to_string(),
+                    due,
+                    priority: Priority::High,
+                    ..Default::default()
+                },
+                file_content_after: "some text\n- [ ] Some text ⏫ 📅 2025-01-27\n",
+            },
+        ];
+
+        let p = File::new("");
+
+        for c in cases {
+            let result = add_task_to_content(&c.task, c.file_content_before);
+            assert_eq!(c.file_content_after, result, "Test '{}' was failed", c.name);
+
+            let tasks = p.tasks_from_content(&result).unwrap();
+            assert_eq!(1, tasks.len(), "Test '{}' was failed", c.name);
+            assert_eq!(tasks[0].text, c.task.text, "Test '{}' was failed", c.name);
+            assert_eq!(tasks[0].due, c.task.due, "Test '{}' was failed", c.name);
+            assert_eq!(tasks[0].priority, c.task.priority, "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn extract_tags_test() {
+        struct Case<'a> {
+            name: &'a str,
+            line: &'a str,
+            expected_tags: &'a [&'a str],
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "empty string",
+                line: "",
+                expected_tags: &[],
+            },
+            Case {
+                name: "string without tags",
+                line: "Some text with issue #123 and url http://site/#anchor",
+                expected_tags: &[],
+            },
+            Case {
+                name: "several tags",
+                line: "#work Some text #project/tatuin with tags #home",
+                expected_tags: &["work", "project/tatuin", "home"],
+            },
+            Case {
+                name: "cyrillic tag",
+                line: "Задача #работа",
+                expected_tags: &["работа"],
+            },
+        ];
+
+        for c in CASES {
+            let tags = extract_tags(c.line);
+            assert_eq!(tags, c.expected_tags, "Test {} was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn extract_recurrence_test() {
+        struct Case<'a> {
+            name: &'a str,
+            line: &'a str,
+            expected_string: &'a str,
+            expected_rule: Option<&'a str>,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "string without recurrence",
+                line: "Some text",
+                expected_string: "Some text",
+                expected_rule: None,
+            },
+            Case {
+                name: "recurrence at the end",
+                line: "Some text 🔁 every week",
+                expected_string: "Some text",
+                expected_rule: Some("every week"),
+            },
+            Case {
+                name: "recurrence before another emoji",
+                line: "Some text 🔁 every 2 days when done ⏳ 2025-01-01",
+                expected_string: "Some text ⏳ 2025-01-01",
+                expected_rule: Some("every 2 days when done"),
+            },
+        ];
+
+        for c in CASES {
+            let (s, rule) = extract_recurrence(c.line);
+            assert_eq!(s, c.expected_string, "Test {} was failed", c.name);
+            assert_eq!(rule.as_deref(), c.expected_rule, "Test {} was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn complete_recurring_task_in_content_test() {
+        let completed_string = format!("✅ {}", chrono::Utc::now().format("%Y-%m-%d"));
+        let next_when_done = (chrono::Local::now().date_naive() + chrono::Days::new(1)).format("%Y-%m-%d");
+        struct Case<'a> {
+            name: &'a str,
+            file_content_before: &'a str,
+            file_content_after: String,
+        }
+        let cases: &[Case] = &[
+            Case {
+                name: "next occurrence from the due date",
+                file_content_before: "some text\n    - [ ] Some text ⏫ 🔁 every week 📅 2025-01-27\nsome another text",
+                file_content_after: format!(
+                    "some text\n    - [ ] Some text ⏫ 🔁 every week 📅 2025-02-03\n    - [x] Some text ⏫ 🔁 every week 📅 2025-01-27 {completed_string}\nsome another text"
+                ),
+            },
+            Case {
+                name: "next occurrence from the completion date",
+                file_content_before: "- [ ] Some text 🔁 every day when done 📅 2025-01-27",
+                file_content_after: format!(
+                    "- [ ] Some text 🔁 every day when done 📅 {next_when_done}\n- [x] Some text 🔁 every day when done 📅 2025-01-27 {completed_string}"
+                ),
+            },
+            Case {
+                name: "unsupported rule completes the task only",
+                file_content_before: "- [ ] Some text 🔁 every full moon",
+                file_content_after: format!("- [x] Some text 🔁 every full moon {completed_string}"),
+            },
+        ];
+
+        let p = File::new("");
+
+        for c in cases {
+            let tasks = p.tasks_from_content(c.file_content_before).unwrap();
+            assert_eq!(tasks.len(), 1, "Test '{}' was failed", c.name);
+            let result = p
+                .change_state_in_content(&tasks[0], State::Completed, c.file_content_before)
+                .unwrap();
+            assert_eq!(result, c.file_content_after, "Test '{}' was failed", c.name);
+
+            let mut completed = tasks[0].clone();
+            completed.state = State::Completed;
+            let result = p
+                .update_task_in_content(&tasks[0], &completed, c.file_content_before)
+                .unwrap();
+            assert_eq!(result, c.file_content_after, "Test '{}' was failed", c.name);
+        }
+    }
+
     #[test]
     fn parse_priority_test() {
         struct Case<'a> {
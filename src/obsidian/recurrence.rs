@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: MIT
+
+// Recurrence rules of the Obsidian Tasks plugin, like "every 2 weeks" or "every monday, friday when done"
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+const WHEN_DONE_SUFFIX: &str = " when done";
+
+pub fn is_when_done(rule: &str) -> bool {
+    rule.trim().to_lowercase().ends_with(WHEN_DONE_SUFFIX)
+}
+
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    match s.trim() {
+        "monday" => Some(Weekday::Mon),
+        "tuesday" => Some(Weekday::Tue),
+        "wednesday" => Some(Weekday::Wed),
+        "thursday" => Some(Weekday::Thu),
+        "friday" => Some(Weekday::Fri),
+        "saturday" => Some(Weekday::Sat),
+        "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn next_weekday(base: NaiveDate, days: &[Weekday]) -> Option<NaiveDate> {
+    (1..=7)
+        .map(|i| base + Days::new(i))
+        .find(|d| days.contains(&d.weekday()))
+}
+
+// Returns the date of the next occurrence after the base date
+pub fn next_date(rule: &str, base: NaiveDate) -> Result<NaiveDate, String> {
+    let err = || format!("unsupported recurrence rule '{rule}'");
+
+    let r = rule.trim().to_lowercase();
+    let r = r.strip_suffix(WHEN_DONE_SUFFIX).unwrap_or(r.as_str());
+    let r = r.strip_prefix("every ").ok_or_else(err)?.trim();
+
+    if r == "weekday" {
+        const WORKING_DAYS: [Weekday; 5] = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+        return next_weekday(base, &WORKING_DAYS).ok_or_else(err);
+    }
+
+    let days = r.strip_prefix("week on ").unwrap_or(r);
+    let days = days
+        .split(&[',', ' '])
+        .filter(|s| !s.is_empty() && *s != "and")
+        .map(parse_weekday)
+        .collect::<Option<Vec<Weekday>>>();
+    if let Some(days) = days {
+        if !days.is_empty() {
+            return next_weekday(base, &days).ok_or_else(err);
+        }
+    }
+
+    let (count, unit) = match r.split_once(' ') {
+        Some((count, unit)) => (count.parse::<u32>().map_err(|_| err())?, unit),
+        None => (1, r),
+    };
+
+    let result = match unit.trim_end_matches('s') {
+        "day" => base.checked_add_days(Days::new(count.into())),
+        "week" => base.checked_add_days(Days::new(u64::from(count) * 7)),
+        "month" => base.checked_add_months(Months::new(count)),
+        "year" => base.checked_add_months(Months::new(count * 12)),
+        _ => None,
+    };
+
+    result.ok_or_else(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_date_test() {
+        struct Case<'a> {
+            name: &'a str,
+            rule: &'a str,
+            base: &'a str,
+            expected: Option<&'a str>,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "every day",
+                rule: "every day",
+                base: "2025-01-31",
+                expected: Some("2025-02-01"),
+            },
+            Case {
+                name: "every several days",
+                rule: "every 3 days",
+                base: "2025-01-01",
+                expected: Some("2025-01-04"),
+            },
+            Case {
+                name: "every week when done",
+                rule: "every week when done",
+                base: "2025-01-01",
+                expected: Some("2025-01-08"),
+            },
+            Case {
+                name: "every month at the end of month",
+                rule: "every month",
+                base: "2025-01-31",
+                expected: Some("2025-02-28"),
+            },
+            Case {
+                name: "every 2 years",
+                rule: "Every 2 years",
+                base: "2024-02-29",
+                expected: Some("2026-02-28"),
+            },
+            Case {
+                name: "every weekday on friday",
+                rule: "every weekday",
+                base: "2025-01-03",
+                expected: Some("2025-01-06"),
+            },
+            Case {
+                name: "every listed days",
+                rule: "every monday, thursday",
+                base: "2025-01-06",
+                expected: Some("2025-01-09"),
+            },
+            Case {
+                name: "every week on a day",
+                rule: "every week on Sunday",
+                base: "2025-01-06",
+                expected: Some("2025-01-12"),
+            },
+            Case {
+                name: "unsupported rule",
+                rule: "every third tuesday",
+                base: "2025-01-06",
+                expected: None,
+            },
+            Case {
+                name: "not a rule",
+                rule: "sometimes",
+                base: "2025-01-06",
+                expected: None,
+            },
+        ];
+
+        for c in CASES {
+            let base = NaiveDate::parse_from_str(c.base, "%Y-%m-%d").unwrap();
+            let result = next_date(c.rule, base).ok().map(|d| d.format("%Y-%m-%d").to_string());
+            assert_eq!(result.as_deref(), c.expected, "Test '{}' was failed", c.name);
+        }
+    }
+}
@@ -78,6 +78,9 @@ pub struct Task {
     pub due: Option<DateTimeUtc>,
     pub completed_at: Option<DateTimeUtc>,
     pub priority: Priority,
+    pub labels: Vec<String>,
+    pub parent_id: Option<String>,
+    pub recurrence: Option<String>,
 }
 
 impl PartialEq for Task {
@@ -88,6 +91,8 @@ impl PartialEq for Task {
             && self.text == o.text
             && self.due == o.due
             && self.priority == o.priority
+            && self.labels == o.labels
+            && self.recurrence == o.recurrence
     }
 }
 
@@ -139,6 +144,18 @@ impl TaskTrait for Task {
         self.completed_at
     }
 
+    fn labels(&self) -> Vec<String> {
+        self.labels.clone()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.parent_id.clone()
+    }
+
+    fn recurrence(&self) -> Option<String> {
+        self.recurrence.clone()
+    }
+
     fn provider(&self) -> String {
         self.provider.to_string()
     }
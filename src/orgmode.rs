@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MIT
+
+// A provider for the Emacs Org mode: TODO headlines of the .org files in a directory.
+
+mod client;
+mod org_file;
+mod project;
+mod task;
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::provider::{NewTask, PatchError, Provider as ProviderTrait, TaskPatch};
+use crate::task::{State, Task as TaskTrait};
+use async_trait::async_trait;
+use ratatui::style::Color;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "Org";
+
+pub struct Provider {
+    name: String,
+    c: client::Client,
+    color: Color,
+}
+
+impl Provider {
+    // keywords are the default TODO keywords in the #+TODO line format (aka "TODO NEXT | DONE CANCELLED")
+    // for the files without their own #+TODO line
+    pub fn new(name: &str, path: &str, keywords: Option<&str>, color: &Color) -> Result<Self, Box<dyn Error>> {
+        let keywords = match keywords {
+            Some(k) => org_file::Keywords::parse(k)
+                .ok_or_else(|| Box::<dyn Error>::from(format!("Wrong TODO keywords '{k}'")))?,
+            None => org_file::Keywords::default(),
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            c: client::Client::new(path, keywords),
+            color: *color,
+        })
+    }
+}
+
+fn cast(t: &dyn TaskTrait) -> &task::Task {
+    match t.as_any().downcast_ref::<task::Task>() {
+        Some(t) => t,
+        None => panic!("Wrong casting!"),
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        let project = project.map(|p| p.id());
+
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for mut t in self.c.tasks(f)? {
+            t.provider = self.name();
+            if project
+                .as_ref()
+                .is_some_and(|p| t.project().is_none_or(|tp| tp.id() != *p))
+            {
+                continue;
+            }
+            result.push(Box::new(t));
+        }
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for f in self.c.all_supported_files()? {
+            result.push(Box::new(project::Project::new(&self.name, self.c.path(), &f)));
+        }
+        Ok(result)
+    }
+
+    async fn create_task(&mut self, project: Option<Box<dyn ProjectTrait>>, t: &NewTask) -> Result<(), Box<dyn Error>> {
+        let p = match project {
+            Some(p) => p,
+            None => return Err(Box::<dyn Error>::from("An org file should be chosen to add the task")),
+        };
+
+        let task = task::Task {
+            text: t.text.to_string(),
+            description: t.description.clone(),
+            deadline: t.due,
+            priority: task::priority_to_char(&t.priority),
+            ..Default::default()
+        };
+
+        self.c.create_task(p.id().as_str(), &task)
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let mut patch = TaskPatch::new(task.clone_boxed());
+        patch.state = Some(state);
+
+        match self.patch_tasks(&[patch]).await.pop() {
+            Some(e) => Err(Box::<dyn Error>::from(e.error)),
+            None => Ok(()),
+        }
+    }
+
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        let mut patch = TaskPatch::new(task.clone_boxed());
+        patch.delete = true;
+
+        match self.patch_tasks(&[patch]).await.pop() {
+            Some(e) => Err(Box::<dyn Error>::from(e.error)),
+            None => Ok(()),
+        }
+    }
+
+    async fn patch_tasks(&mut self, patches: &[TaskPatch]) -> Vec<PatchError> {
+        let mut client_patches = Vec::new();
+        let mut errors = Vec::new();
+        for p in patches.iter() {
+            if p.description.is_some() && !p.delete {
+                errors.push(PatchError {
+                    task: p.task.clone_boxed(),
+                    error: "The description of Org tasks can be changed only in the file".to_string(),
+                });
+                continue;
+            }
+
+            client_patches.push(client::TaskPatch {
+                task: cast(p.task.as_ref()),
+                state: p.state.clone(),
+                text: p.text.clone(),
+                due: p.due.clone(),
+                priority: p.priority.clone(),
+                delete: p.delete,
+            });
+        }
+
+        for e in self.c.patch_tasks(&client_patches) {
+            errors.push(PatchError {
+                task: e.task.clone_boxed(),
+                error: e.error,
+            })
+        }
+
+        errors
+    }
+
+    async fn reload(&mut self) {
+        // The files are read on every request
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
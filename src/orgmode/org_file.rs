@@ -0,0 +1,649 @@
+// SPDX-License-Identifier: MIT
+
+use super::task::Task;
+use crate::task::{DateTimeUtc, State, Task as TaskTrait};
+use chrono::{Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use regex::Regex;
+use std::error::Error;
+use std::fs;
+use std::sync::LazyLock;
+
+static HEADLINE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\*+)\s+(.*?)\s*$").unwrap());
+static TAGS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(^|\s+)((:[\p{L}\p{N}_@#%]+)+:)$").unwrap());
+static PRIORITY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\[#([A-Z0-9])\]\s*").unwrap());
+static PLANNING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(DEADLINE|SCHEDULED|CLOSED):\s*([<\[][^>\]]*[>\]])").unwrap());
+static TIMESTAMP_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[<\[](\d{4}-\d{2}-\d{2})(?:\s+[^\s\d>\]]+)?(?:\s+(\d{1,2}:\d{2}))?").unwrap());
+static KEYWORDS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^#\+(?:SEQ_|TYP_)?TODO:(.*)$").unwrap());
+
+const DEADLINE: &str = "DEADLINE";
+const SCHEDULED: &str = "SCHEDULED";
+const CLOSED: &str = "CLOSED";
+// Active keywords which mean that the work on the task has been started
+const IN_PROGRESS_KEYWORDS: [&str; 7] = [
+    "NEXT",
+    "STARTED",
+    "DOING",
+    "INPROGRESS",
+    "IN-PROGRESS",
+    "PROGRESS",
+    "WIP",
+];
+
+// TODO keywords of the #+TODO line: the active ones and the ones after "|" which mean the task is done
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keywords {
+    active: Vec<String>,
+    done: Vec<String>,
+}
+
+impl Default for Keywords {
+    fn default() -> Self {
+        Self {
+            active: vec!["TODO".to_string()],
+            done: vec!["DONE".to_string()],
+        }
+    }
+}
+
+impl Keywords {
+    pub fn parse(s: &str) -> Option<Self> {
+        // Fast access keys like TODO(t) or DONE(d!) aren't the part of the keyword
+        let words = s
+            .split_whitespace()
+            .map(|w| w.split('(').next().unwrap_or_default().to_string())
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<String>>();
+
+        let (active, done) = match words.iter().position(|w| w == "|") {
+            Some(i) => (words[..i].to_vec(), words[i + 1..].to_vec()),
+            // Without the separator the last keyword is the done one
+            None if words.len() > 1 => (words[..words.len() - 1].to_vec(), words[words.len() - 1..].to_vec()),
+            None => return None,
+        };
+
+        if active.is_empty() || done.is_empty() {
+            return None;
+        }
+
+        Some(Self { active, done })
+    }
+
+    fn append(&mut self, other: Keywords) {
+        self.active.extend(other.active);
+        self.done.extend(other.done);
+    }
+
+    fn contains(&self, keyword: &str) -> bool {
+        self.active.iter().chain(self.done.iter()).any(|k| k == keyword)
+    }
+
+    fn state(&self, keyword: &str) -> State {
+        if self.active.first().is_some_and(|k| k == keyword) {
+            State::Uncompleted
+        } else if self.done.first().is_some_and(|k| k == keyword) {
+            State::Completed
+        } else if self.done.iter().any(|k| k == keyword) {
+            State::Unknown('-')
+        } else if IN_PROGRESS_KEYWORDS.contains(&keyword) {
+            State::InProgress
+        } else {
+            State::Unknown(keyword.chars().next().unwrap_or('?').to_ascii_lowercase())
+        }
+    }
+
+    fn keyword(&self, state: &State) -> Result<String, Box<dyn Error>> {
+        let k = match state {
+            State::Uncompleted => self.active.first(),
+            State::Completed => self.done.first(),
+            State::InProgress => self.active.iter().find(|k| IN_PROGRESS_KEYWORDS.contains(&k.as_str())),
+            State::Unknown('-') => self.done.get(1),
+            State::Unknown(c) => self
+                .active
+                .iter()
+                .skip(1)
+                .find(|k| k.chars().next().map(|x| x.to_ascii_lowercase()) == Some(*c)),
+        };
+
+        k.cloned().ok_or_else(|| {
+            Box::<dyn Error>::from(format!(
+                "there is no TODO keyword for the state {state:?}, add it to the #+TODO line"
+            ))
+        })
+    }
+}
+
+fn parse_timestamp(s: &str) -> Option<DateTimeUtc> {
+    let caps = TIMESTAMP_RE.captures(s)?;
+    let d = NaiveDate::parse_from_str(&caps[1], "%Y-%m-%d").ok()?;
+
+    match caps.get(2) {
+        Some(t) => {
+            let t = NaiveTime::parse_from_str(t.as_str(), "%H:%M").ok()?;
+            Local
+                .from_local_datetime(&d.and_time(t))
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc))
+        }
+        None => Some(DateTimeUtc::from_naive_utc_and_offset(d.and_time(NaiveTime::MIN), Utc)),
+    }
+}
+
+fn date_timestamp(d: &DateTimeUtc) -> String {
+    d.format("<%Y-%m-%d %a>").to_string()
+}
+
+fn closed_timestamp(d: &DateTimeUtc) -> String {
+    d.with_timezone(&Local).format("[%Y-%m-%d %a %H:%M]").to_string()
+}
+
+fn is_planning_line(l: &str) -> bool {
+    let l = l.trim_start();
+    [DEADLINE, SCHEDULED, CLOSED]
+        .iter()
+        .any(|k| l.starts_with(format!("{k}:").as_str()))
+}
+
+fn headline_to_string(t: &Task) -> String {
+    let mut parts = vec!["*".repeat(t.level)];
+    if !t.keyword.is_empty() {
+        parts.push(t.keyword.to_string());
+    }
+    if let Some(p) = t.priority {
+        parts.push(format!("[#{p}]"));
+    }
+    if !t.text.is_empty() {
+        parts.push(t.text.to_string());
+    }
+    if !t.tags.is_empty() {
+        parts.push(format!(":{}:", t.tags.join(":")));
+    }
+    parts.join(" ")
+}
+
+pub struct File {
+    file_path: String,
+    lines: Vec<String>,
+}
+
+impl File {
+    pub fn new(file_path: &str) -> Self {
+        Self {
+            file_path: file_path.to_string(),
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn open(&mut self) -> Result<(), std::io::Error> {
+        let content = fs::read_to_string(self.file_path.as_str())?;
+        self.lines = content.split('\n').map(|l| l.to_string()).collect();
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), Box<dyn Error>> {
+        fs::write(self.file_path.as_str(), self.lines.join("\n"))?;
+        Ok(())
+    }
+
+    pub fn headline(&self, line: usize) -> String {
+        self.lines.get(line).cloned().unwrap_or_default()
+    }
+
+    // Keywords of the #+TODO lines of the file or the default ones
+    fn keywords(&self, default: &Keywords) -> Keywords {
+        let mut result: Option<Keywords> = None;
+        for l in &self.lines {
+            if let Some(k) = KEYWORDS_RE.captures(l).and_then(|caps| Keywords::parse(&caps[1])) {
+                match result.as_mut() {
+                    Some(r) => r.append(k),
+                    None => result = Some(k),
+                }
+            }
+        }
+        result.unwrap_or_else(|| default.clone())
+    }
+
+    fn parse_headline(&self, i: usize, keywords: &Keywords) -> Option<Task> {
+        let caps = HEADLINE_RE.captures(&self.lines[i])?;
+        let level = caps[1].len();
+        let mut rest = caps[2].to_string();
+
+        let (keyword, r) = rest.split_once(' ').unwrap_or((rest.as_str(), ""));
+        if !keywords.contains(keyword) {
+            return None;
+        }
+        let keyword = keyword.to_string();
+        rest = r.trim_start().to_string();
+
+        let mut priority = None;
+        if let Some(caps) = PRIORITY_RE.captures(&rest) {
+            priority = caps[1].chars().next();
+            rest = rest[caps[0].len()..].to_string();
+        }
+
+        let mut tags = Vec::new();
+        if let Some(caps) = TAGS_RE.captures(&rest) {
+            tags = caps[2]
+                .split(':')
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_string())
+                .collect();
+            rest = rest[..caps.get(0).unwrap().start()].to_string();
+        }
+
+        let mut t = Task {
+            file_path: self.file_path.to_string(),
+            line: i,
+            headline: self.lines[i].to_string(),
+            level,
+            state: keywords.state(&keyword),
+            keyword,
+            priority,
+            text: rest.trim().to_string(),
+            tags,
+            ..Default::default()
+        };
+
+        if let Some(l) = self.lines.get(i + 1).filter(|l| is_planning_line(l)) {
+            for caps in PLANNING_RE.captures_iter(l) {
+                let ts = parse_timestamp(&caps[2]);
+                match &caps[1] {
+                    DEADLINE => t.deadline = ts,
+                    SCHEDULED => t.scheduled = ts,
+                    _ => t.closed = ts,
+                }
+            }
+        }
+
+        t.description = self.description(i);
+        Some(t)
+    }
+
+    // The text under the headline without the planning line and drawers like :PROPERTIES:
+    fn description(&self, headline: usize) -> Option<String> {
+        let mut result = Vec::new();
+        let mut in_drawer = false;
+
+        for l in self.lines.iter().skip(headline + 1) {
+            if HEADLINE_RE.is_match(l) {
+                break;
+            }
+
+            let trimmed = l.trim();
+            if in_drawer {
+                in_drawer = !trimmed.eq_ignore_ascii_case(":END:");
+                continue;
+            }
+            if trimmed.len() > 2 && trimmed.starts_with(':') && trimmed.ends_with(':') && !trimmed.contains(' ') {
+                in_drawer = true;
+                continue;
+            }
+            if is_planning_line(l) && result.is_empty() {
+                continue;
+            }
+
+            result.push(trimmed);
+        }
+
+        let result = result.join("\n").trim().to_string();
+        if result.is_empty() { None } else { Some(result) }
+    }
+
+    pub fn tasks(&self, default_keywords: &Keywords) -> Vec<Task> {
+        let keywords = self.keywords(default_keywords);
+        let mut result: Vec<Task> = Vec::new();
+        // Levels and ids of the headlines which contain the current one, None for the headlines that aren't tasks
+        let mut parents: Vec<(usize, Option<String>)> = Vec::new();
+
+        for i in 0..self.lines.len() {
+            let level = match HEADLINE_RE.captures(&self.lines[i]) {
+                Some(caps) => caps[1].len(),
+                None => continue,
+            };
+            parents.retain(|(l, _)| *l < level);
+
+            match self.parse_headline(i, &keywords) {
+                Some(mut t) => {
+                    t.parent_id = parents.iter().rev().find_map(|(_, id)| id.clone());
+                    parents.push((level, Some(t.id())));
+                    result.push(t);
+                }
+                None => parents.push((level, None)),
+            }
+        }
+
+        result
+    }
+
+    fn check_task_is_actual(&self, t: &Task) -> Result<(), Box<dyn Error>> {
+        if self.lines.get(t.line) != Some(&t.headline) {
+            return Err(Box::<dyn Error>::from("Task has been changed since last loading"));
+        }
+        Ok(())
+    }
+
+    // Sets or removes a timestamp of the planning line right after the headline
+    fn set_planning(&mut self, t: &Task, key: &str, value: Option<String>) {
+        let planning_idx = t.line + 1;
+        let has_planning = self.lines.get(planning_idx).is_some_and(|l| is_planning_line(l));
+
+        let mut items = Vec::new();
+        let indent = if has_planning {
+            let l = &self.lines[planning_idx];
+            for caps in PLANNING_RE.captures_iter(l) {
+                items.push((caps[1].to_string(), caps[2].to_string()));
+            }
+            l.chars().take_while(|c| c.is_whitespace()).collect::<String>()
+        } else {
+            " ".repeat(t.level + 1)
+        };
+
+        match (items.iter_mut().find(|(k, _)| k == key), value) {
+            (Some(item), Some(v)) => item.1 = v,
+            (Some(_), None) => items.retain(|(k, _)| k != key),
+            (None, Some(v)) if key == CLOSED => items.insert(0, (key.to_string(), v)),
+            (None, Some(v)) => items.push((key.to_string(), v)),
+            (None, None) => {}
+        }
+
+        let line = items
+            .iter()
+            .map(|(k, v)| format!("{k}: {v}"))
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        match (has_planning, line.is_empty()) {
+            (true, true) => {
+                self.lines.remove(planning_idx);
+            }
+            (true, false) => self.lines[planning_idx] = format!("{indent}{line}"),
+            (false, false) => self.lines.insert(planning_idx, format!("{indent}{line}")),
+            (false, true) => {}
+        }
+    }
+
+    pub fn change_state(&mut self, t: &Task, s: &State, default_keywords: &Keywords) -> Result<(), Box<dyn Error>> {
+        self.check_task_is_actual(t)?;
+
+        let keyword = self.keywords(default_keywords).keyword(s)?;
+
+        // Only the keyword is replaced, so the alignment of the tags and the spacing are kept
+        let start = HEADLINE_RE
+            .captures(&t.headline)
+            .and_then(|caps| caps.get(2))
+            .map(|m| m.start())
+            .ok_or("The headline has no keyword")?;
+        self.lines[t.line] = [&t.headline[..start], &keyword, &t.headline[start + t.keyword.len()..]].join("");
+
+        let closed = match s {
+            State::Completed if t.state != State::Completed => Some(closed_timestamp(&Utc::now())),
+            State::Completed => return Ok(()),
+            _ => None,
+        };
+        self.set_planning(t, CLOSED, closed);
+
+        Ok(())
+    }
+
+    pub fn update_task(&mut self, t: &Task, new_task: &Task) -> Result<(), Box<dyn Error>> {
+        self.check_task_is_actual(t)?;
+
+        self.lines[t.line] = headline_to_string(new_task);
+        if new_task.deadline != t.deadline || new_task.scheduled != t.scheduled {
+            self.set_planning(t, DEADLINE, new_task.deadline.as_ref().map(date_timestamp));
+            if new_task.scheduled.is_none() {
+                self.set_planning(t, SCHEDULED, None);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Deletes the headline with its text, the subheadlines are kept
+    pub fn delete_task(&mut self, t: &Task) -> Result<(), Box<dyn Error>> {
+        self.check_task_is_actual(t)?;
+
+        let end = self
+            .lines
+            .iter()
+            .enumerate()
+            .skip(t.line + 1)
+            .find(|(_, l)| HEADLINE_RE.is_match(l))
+            .map(|(i, _)| i)
+            .unwrap_or(self.lines.len());
+
+        self.lines.drain(t.line..end);
+        if self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        Ok(())
+    }
+
+    pub fn add_task(&mut self, t: &Task, default_keywords: &Keywords) -> Result<(), Box<dyn Error>> {
+        let t = Task {
+            keyword: self.keywords(default_keywords).keyword(&State::Uncompleted)?,
+            ..t.clone()
+        };
+
+        // The file usually ends with a new line, so the task is added before the last empty line
+        let mut idx = self.lines.len();
+        if self.lines.last().is_some_and(|l| l.is_empty()) {
+            idx -= 1;
+        }
+        self.lines.insert(idx, headline_to_string(&t));
+
+        let mut i = idx + 1;
+        if let Some(d) = &t.deadline {
+            self.lines.insert(
+                i,
+                format!("{}{DEADLINE}: {}", " ".repeat(t.level + 1), date_timestamp(d)),
+            );
+            i += 1;
+        }
+        if let Some(d) = &t.description {
+            for l in d.lines() {
+                self.lines.insert(i, format!("{}{l}", " ".repeat(t.level + 1)));
+                i += 1;
+            }
+        }
+
+        if self.lines.last().is_none_or(|l| !l.is_empty()) {
+            self.lines.push(String::new());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orgmode::task::priority_to_char;
+    use crate::task::Priority;
+
+    fn file(content: &str) -> File {
+        File {
+            file_path: "/vault/tasks.org".to_string(),
+            lines: content.split('\n').map(|l| l.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parse_test() {
+        let f = file(
+            "#+TODO: TODO NEXT WAIT | DONE CANCELLED
+* Project                                                    :work:
+** TODO [#A] Write the report                                  :urgent:docs:
+   DEADLINE: <2025-01-27 Mon> SCHEDULED: <2025-01-25 Sat>
+   :PROPERTIES:
+   :ID: 123
+   :END:
+   Some notes
+*** NEXT Collect data
+    SCHEDULED: <2025-01-24 Fri 10:00>
+*** DONE Ask for access
+    CLOSED: [2025-01-20 Mon 12:00]
+** WAIT Call Bob
+** CANCELLED Old stuff
+* Just a headline with TODO inside
+",
+        );
+
+        let tasks = f.tasks(&Keywords::default());
+        assert_eq!(tasks.len(), 5);
+
+        let report = &tasks[0];
+        assert_eq!(report.text(), "Write the report");
+        assert_eq!(report.state(), State::Uncompleted);
+        assert_eq!(report.priority(), Priority::High);
+        assert_eq!(report.labels(), vec!["urgent", "docs"]);
+        assert_eq!(report.due().unwrap().format("%Y-%m-%d").to_string(), "2025-01-27");
+        assert_eq!(report.description(), Some("Some notes".to_string()));
+        assert_eq!(report.parent_id(), None);
+        assert_eq!(report.place(), "/vault/tasks.org:3");
+
+        let collect = &tasks[1];
+        assert_eq!(collect.state(), State::InProgress);
+        assert_eq!(collect.parent_id(), Some(report.id()));
+        assert!(collect.due().is_some());
+
+        let access = &tasks[2];
+        assert_eq!(access.state(), State::Completed);
+        assert!(access.completed_at().is_some());
+        assert_eq!(access.parent_id(), Some(report.id()));
+
+        assert_eq!(tasks[3].state(), State::Unknown('w'));
+        assert_eq!(tasks[3].parent_id(), None);
+        assert_eq!(tasks[4].state(), State::Unknown('-'));
+    }
+
+    #[test]
+    fn keywords_test() {
+        struct Case<'a> {
+            name: &'a str,
+            line: &'a str,
+            expected: Option<(&'a [&'a str], &'a [&'a str])>,
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "with separator",
+                line: "TODO(t) NEXT(n) | DONE(d!) CANCELLED(c@)",
+                expected: Some((&["TODO", "NEXT"], &["DONE", "CANCELLED"])),
+            },
+            Case {
+                name: "without separator",
+                line: "TODO STARTED FINISHED",
+                expected: Some((&["TODO", "STARTED"], &["FINISHED"])),
+            },
+            Case {
+                name: "single keyword",
+                line: "TODO",
+                expected: None,
+            },
+        ];
+
+        for c in CASES {
+            let expected = c.expected.map(|(active, done)| Keywords {
+                active: active.iter().map(|s| s.to_string()).collect(),
+                done: done.iter().map(|s| s.to_string()).collect(),
+            });
+            assert_eq!(Keywords::parse(c.line), expected, "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn change_state_test() {
+        let mut f = file("* TODO Task :tag:\n  DEADLINE: <2025-01-27 Mon>\n* TODO Other\n");
+        let tasks = f.tasks(&Keywords::default());
+
+        f.change_state(&tasks[1], &State::Completed, &Keywords::default())
+            .unwrap();
+        f.change_state(&tasks[0], &State::Completed, &Keywords::default())
+            .unwrap();
+
+        let content = f.lines.join("\n");
+        let closed = closed_timestamp(&Utc::now());
+        let closed = &closed[..closed.len() - 6];
+        assert!(
+            content.starts_with(&format!("* DONE Task :tag:\n  CLOSED: {closed}")),
+            "{content}"
+        );
+        assert!(
+            content.contains("DEADLINE: <2025-01-27 Mon>\n* DONE Other\n  CLOSED: "),
+            "{content}"
+        );
+
+        let tasks = f.tasks(&Keywords::default());
+        f.change_state(&tasks[1], &State::Uncompleted, &Keywords::default())
+            .unwrap();
+        f.change_state(&tasks[0], &State::Uncompleted, &Keywords::default())
+            .unwrap();
+        assert_eq!(
+            f.lines.join("\n"),
+            "* TODO Task :tag:\n  DEADLINE: <2025-01-27 Mon>\n* TODO Other\n"
+        );
+
+        let tasks = f.tasks(&Keywords::default());
+        assert!(
+            f.change_state(&tasks[0], &State::InProgress, &Keywords::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn change_state_keeps_headline_test() {
+        let mut f = file(
+            "** TODO [#A] Call  mom                                        :family:call:
+",
+        );
+        let tasks = f.tasks(&Keywords::default());
+
+        f.change_state(&tasks[0], &State::Completed, &Keywords::default())
+            .unwrap();
+        assert_eq!(
+            f.lines[0],
+            "** DONE [#A] Call  mom                                        :family:call:"
+        );
+    }
+
+    #[test]
+    fn update_and_delete_test() {
+        let mut f = file("* TODO [#C] Task :tag:\n  SCHEDULED: <2025-01-25 Sat>\n  Notes\n** TODO Subtask\n");
+        let tasks = f.tasks(&Keywords::default());
+
+        let due = DateTimeUtc::from_naive_utc_and_offset(
+            NaiveDate::from_ymd_opt(2025, 2, 3).unwrap().and_time(NaiveTime::MIN),
+            Utc,
+        );
+        let new_task = Task {
+            text: "New text".to_string(),
+            priority: priority_to_char(&Priority::Highest),
+            deadline: Some(due),
+            scheduled: None,
+            ..tasks[0].clone()
+        };
+        f.update_task(&tasks[0], &new_task).unwrap();
+        assert_eq!(
+            f.lines.join("\n"),
+            "* TODO [#A] New text :tag:\n  DEADLINE: <2025-02-03 Mon>\n  Notes\n** TODO Subtask\n"
+        );
+
+        let tasks = f.tasks(&Keywords::default());
+        f.delete_task(&tasks[0]).unwrap();
+        assert_eq!(f.lines.join("\n"), "** TODO Subtask\n");
+
+        f.add_task(
+            &Task {
+                text: "Added".to_string(),
+                deadline: Some(due),
+                ..Default::default()
+            },
+            &Keywords::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            f.lines.join("\n"),
+            "** TODO Subtask\n* TODO Added\n  DEADLINE: <2025-02-03 Mon>\n"
+        );
+    }
+}
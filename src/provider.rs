@@ -2,14 +2,71 @@
 
 use crate::filter;
 use crate::project::Project as ProjectTrait;
-use crate::task::{State, Task as TaskTrait};
+use crate::task::{DateTimeUtc, Priority, State, Task as TaskTrait};
 use async_trait::async_trait;
 use ratatui::style::Color;
 use std::error::Error;
+use std::time::Duration;
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, PartialEq)]
+pub enum DuePatch {
+    NoDate,
+    Date(DateTimeUtc),
+}
 
 pub struct TaskPatch {
     pub task: Box<dyn TaskTrait>,
     pub state: Option<State>,
+    pub text: Option<String>,
+    pub description: Option<String>,
+    pub due: Option<DuePatch>,
+    pub priority: Option<Priority>,
+    pub delete: bool,
+}
+
+impl TaskPatch {
+    pub fn new(task: Box<dyn TaskTrait>) -> Self {
+        Self {
+            task,
+            state: None,
+            text: None,
+            description: None,
+            due: None,
+            priority: None,
+            delete: false,
+        }
+    }
+
+    pub fn has_edits(&self) -> bool {
+        self.text.is_some() || self.description.is_some() || self.due.is_some() || self.priority.is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state.is_none() && !self.has_edits() && !self.delete
+    }
+}
+
+impl Clone for TaskPatch {
+    fn clone(&self) -> Self {
+        Self {
+            task: self.task.clone_boxed(),
+            state: self.state.clone(),
+            text: self.text.clone(),
+            description: self.description.clone(),
+            due: self.due.clone(),
+            priority: self.priority.clone(),
+            delete: self.delete,
+        }
+    }
+}
+
+pub struct NewTask {
+    pub text: String,
+    pub description: Option<String>,
+    pub due: Option<DateTimeUtc>,
+    pub priority: Priority,
 }
 
 pub struct PatchError {
@@ -33,11 +90,53 @@ pub trait Provider: Send {
         f: &filter::Filter,
     ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>>;
     async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>>;
+    async fn create_task(
+        &mut self,
+        _project: Option<Box<dyn ProjectTrait>>,
+        _task: &NewTask,
+    ) -> Result<(), Box<dyn Error>> {
+        Err(Box::<dyn Error>::from(format!(
+            "Provider {} doesn't support creating tasks",
+            self.type_name()
+        )))
+    }
     async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>>;
+    async fn update_task(&mut self, _patch: &TaskPatch) -> Result<(), Box<dyn Error>> {
+        Err(Box::<dyn Error>::from(format!(
+            "Provider {} doesn't support editing tasks",
+            self.type_name()
+        )))
+    }
+    async fn delete_task(&mut self, _task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        Err(Box::<dyn Error>::from(format!(
+            "Provider {} doesn't support deleting tasks",
+            self.type_name()
+        )))
+    }
     async fn patch_tasks(&mut self, patches: &[TaskPatch]) -> Vec<PatchError> {
         let mut errors = Vec::new();
 
         for patch in patches.iter() {
+            if patch.delete {
+                if let Err(e) = self.delete_task(patch.task.as_ref()).await {
+                    errors.push(PatchError {
+                        task: patch.task.clone_boxed(),
+                        error: e.to_string(),
+                    });
+                }
+                continue;
+            }
+
+            if patch.has_edits() {
+                if let Err(e) = self.update_task(patch).await {
+                    errors.push(PatchError {
+                        task: patch.task.clone_boxed(),
+                        error: e.to_string(),
+                    });
+                    continue;
+                }
+            }
+
             if let Some(s) = &patch.state {
                 if let Err(e) = self.change_task_state(patch.task.as_ref(), s.clone()).await {
                     errors.push(PatchError {
@@ -52,4 +151,9 @@ pub trait Provider: Send {
     }
     async fn reload(&mut self);
     fn color(&self) -> Color;
+    // Completing a task removes it from the provider (e.g. deletes a comment from the code),
+    // so the completion is confirmed like a deletion
+    fn completion_deletes_tasks(&self) -> bool {
+        false
+    }
 }
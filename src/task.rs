@@ -4,7 +4,9 @@ use crate::filter;
 use crate::project::Project as ProjectTrait;
 use chrono::DateTime;
 use chrono::prelude::*;
+use clap::ValueEnum;
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::cmp::Ordering;
 use std::fmt;
@@ -12,7 +14,7 @@ use std::fmt::Write;
 
 pub type DateTimeUtc = DateTime<Utc>;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum State {
     Unknown(char),
     Uncompleted,
@@ -31,7 +33,7 @@ impl fmt::Display for State {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default, ValueEnum, Serialize, Deserialize)]
 pub enum Priority {
     Lowest,
     Low,
@@ -81,6 +83,15 @@ pub trait Task: Send + Sync {
     fn place(&self) -> String {
         String::new()
     }
+    fn labels(&self) -> Vec<String> {
+        Vec::new()
+    }
+    fn parent_id(&self) -> Option<String> {
+        None
+    }
+    fn recurrence(&self) -> Option<String> {
+        None
+    }
     fn provider(&self) -> String;
 
     fn project(&self) -> Option<Box<dyn ProjectTrait>>;
@@ -109,6 +120,19 @@ where
     String::from("-")
 }
 
+pub fn date_from_str(s: &str) -> Result<DateTimeUtc, String> {
+    let d = match s.trim() {
+        "today" => Local::now().date_naive(),
+        "tomorrow" => Local::now().date_naive() + chrono::Days::new(1),
+        s => NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| format!("wrong date '{s}': {e}"))?,
+    };
+
+    Ok(DateTimeUtc::from_naive_utc_and_offset(
+        d.and_hms_opt(0, 0, 0).unwrap_or_default(),
+        Utc,
+    ))
+}
+
 pub fn format(t: &dyn Task) -> String {
     format!(
         "- [{}] {} ({}) ({})",
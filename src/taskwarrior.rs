@@ -0,0 +1,608 @@
+// SPDX-License-Identifier: MIT
+
+// A provider for the local Taskwarrior tasks, it drives the `task` binary with `export` and `import`.
+
+mod client;
+mod structs;
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::provider::{DuePatch, NewTask, PatchError, Provider as ProviderTrait, TaskPatch};
+use crate::task::{DateTimeUtc, Priority, State, Task as TaskTrait, due_group};
+use async_trait::async_trait;
+use chrono::{Local, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use ratatui::style::Color;
+use std::any::Any;
+use std::collections::HashMap;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "Taskwarrior";
+
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const PROJECT_SEPARATOR: char = '.';
+
+fn str_to_date(s: &str) -> Option<DateTimeUtc> {
+    NaiveDateTime::parse_from_str(s, DATE_FORMAT)
+        .ok()
+        .map(|dt| DateTimeUtc::from_naive_utc_and_offset(dt, Utc))
+}
+
+fn date_to_str(d: &DateTimeUtc) -> String {
+    d.format(DATE_FORMAT).to_string()
+}
+
+// Taskwarrior keeps dates like "due:tomorrow" as the local midnight, it's shown as a date without time
+fn due_from_str(s: &str) -> Option<DateTimeUtc> {
+    let dt = str_to_date(s)?;
+    let local = dt.with_timezone(&Local);
+    if local.time() == NaiveTime::MIN {
+        return Some(DateTimeUtc::from_naive_utc_and_offset(
+            local.date_naive().and_time(NaiveTime::MIN),
+            Utc,
+        ));
+    }
+    Some(dt)
+}
+
+fn due_to_str(d: &DateTimeUtc) -> String {
+    match Local
+        .from_local_datetime(&d.date_naive().and_time(NaiveTime::MIN))
+        .earliest()
+    {
+        Some(local) => date_to_str(&local.with_timezone(&Utc)),
+        None => date_to_str(d),
+    }
+}
+
+fn priority_from_str(p: Option<&str>) -> Priority {
+    match p {
+        Some("H") => Priority::High,
+        Some("M") => Priority::Medium,
+        Some("L") => Priority::Low,
+        _ => Priority::Normal,
+    }
+}
+
+fn priority_to_str(p: &Priority) -> Option<String> {
+    match p {
+        Priority::Highest | Priority::High => Some("H".to_string()),
+        Priority::Medium => Some("M".to_string()),
+        Priority::Low | Priority::Lowest => Some("L".to_string()),
+        Priority::Normal => None,
+    }
+}
+
+// Taskwarrior projects are hierarchical: "Home.Garden" is a subproject of "Home"
+#[derive(Clone)]
+pub struct Project {
+    name: String,
+    provider: String,
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        String::new()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.name
+            .rsplit_once(PROJECT_SEPARATOR)
+            .map(|(parent, _)| parent.to_string())
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone)]
+pub struct Task {
+    t: structs::Task,
+    provider: String,
+    parent_id: Option<String>,
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        self.t.uuid.to_string()
+    }
+
+    fn text(&self) -> String {
+        self.t.description.to_string()
+    }
+
+    fn description(&self) -> Option<String> {
+        if self.t.annotations.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.t
+                .annotations
+                .iter()
+                .map(|a| a.description.as_str())
+                .collect::<Vec<&str>>()
+                .join("\n"),
+        )
+    }
+
+    fn priority(&self) -> Priority {
+        priority_from_str(self.t.priority.as_deref())
+    }
+
+    fn state(&self) -> State {
+        match self.t.status.as_str() {
+            "pending" | "waiting" if self.t.start.is_some() => State::InProgress,
+            "pending" | "waiting" => State::Uncompleted,
+            "completed" => State::Completed,
+            "deleted" => State::Unknown('-'),
+            _ => State::Unknown('?'),
+        }
+    }
+
+    fn created_at(&self) -> Option<DateTimeUtc> {
+        self.t.entry.as_deref().and_then(str_to_date)
+    }
+
+    fn updated_at(&self) -> Option<DateTimeUtc> {
+        self.t.modified.as_deref().and_then(str_to_date)
+    }
+
+    fn completed_at(&self) -> Option<DateTimeUtc> {
+        self.t.end.as_deref().and_then(str_to_date)
+    }
+
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.t.due.as_deref().and_then(due_from_str)
+    }
+
+    fn place(&self) -> String {
+        self.t.uuid.chars().take(8).collect()
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.t.tags.clone()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.parent_id.clone()
+    }
+
+    fn recurrence(&self) -> Option<String> {
+        self.t.recur.clone()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        self.t.project.as_ref().map(|p| {
+            Box::new(Project {
+                name: p.to_string(),
+                provider: self.provider.to_string(),
+            }) as Box<dyn ProjectTrait>
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+// The tasks a task depends on are shown as its subtasks.
+// If several tasks depend on the same one, it's shown under the first of them.
+fn parents(tasks: &[structs::Task]) -> HashMap<String, String> {
+    let mut result = HashMap::new();
+    for t in tasks {
+        for d in &t.depends {
+            result.entry(d.to_string()).or_insert_with(|| t.uuid.to_string());
+        }
+    }
+    result
+}
+
+fn set_state(t: &mut structs::Task, state: &State) {
+    let now = date_to_str(&Utc::now());
+    match state {
+        State::Completed => {
+            t.status = "completed".to_string();
+            t.end = Some(now);
+            t.start = None;
+        }
+        State::Uncompleted => {
+            t.status = "pending".to_string();
+            t.end = None;
+            t.start = None;
+        }
+        State::InProgress => {
+            t.status = "pending".to_string();
+            t.end = None;
+            if t.start.is_none() {
+                t.start = Some(now);
+            }
+        }
+        State::Unknown(_) => {
+            t.status = "deleted".to_string();
+            t.end = Some(now);
+            t.start = None;
+        }
+    }
+}
+
+// Taskwarrior requires a uuid for imported tasks, a random one is made from a hash
+fn new_uuid(seed: &str) -> String {
+    let h = sha256::digest(format!(
+        "{seed}{}",
+        Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    format!(
+        "{}-{}-4{}-8{}-{}",
+        &h[0..8],
+        &h[8..12],
+        &h[13..16],
+        &h[17..20],
+        &h[20..32]
+    )
+}
+
+pub struct Provider {
+    name: String,
+    color: Color,
+    c: client::Client,
+    tasks: Vec<structs::Task>,
+}
+
+impl Provider {
+    pub fn new(name: &str, command: &str, data_location: Option<&str>, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            color: *color,
+            c: client::Client::new(command, data_location),
+            tasks: Vec::new(),
+        }
+    }
+
+    async fn load_tasks(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.tasks.is_empty() {
+            // Recurring templates aren't real tasks, their instances are pending tasks
+            self.tasks = self
+                .c
+                .export(&["status.not:recurring"])
+                .await?
+                .into_iter()
+                .filter(|t| t.status != "recurring")
+                .collect();
+        }
+        Ok(())
+    }
+
+    fn cast(task: &dyn TaskTrait) -> &Task {
+        match task.as_any().downcast_ref::<Task>() {
+            Some(t) => t,
+            None => panic!("Wrong casting!"),
+        }
+    }
+
+    fn patched_task(patch: &TaskPatch) -> Result<structs::Task, Box<dyn Error>> {
+        let mut t = Self::cast(patch.task.as_ref()).t.clone();
+
+        if patch.description.is_some() {
+            return Err(Box::<dyn Error>::from(
+                "Taskwarrior tasks don't have a description, use annotations instead",
+            ));
+        }
+
+        if let Some(text) = &patch.text {
+            t.description = text.to_string();
+        }
+        match &patch.due {
+            Some(DuePatch::Date(d)) => t.due = Some(due_to_str(d)),
+            Some(DuePatch::NoDate) => t.due = None,
+            None => {}
+        }
+        if let Some(p) = &patch.priority {
+            t.priority = priority_to_str(p);
+        }
+        if let Some(s) = &patch.state {
+            set_state(&mut t, s);
+        }
+        if patch.delete {
+            set_state(&mut t, &State::Unknown('-'));
+        }
+
+        t.modified = Some(date_to_str(&Utc::now()));
+        Ok(t)
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        self.load_tasks().await?;
+
+        let project = project.map(|p| p.id());
+        let parents = parents(&self.tasks);
+
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for t in &self.tasks {
+            if let Some(p) = &project {
+                let in_project = t
+                    .project
+                    .as_ref()
+                    .is_some_and(|tp| tp == p || tp.starts_with(format!("{p}{PROJECT_SEPARATOR}").as_str()));
+                if !in_project {
+                    continue;
+                }
+            }
+
+            let task = Task {
+                t: t.clone(),
+                provider: self.name(),
+                parent_id: parents.get(&t.uuid).cloned(),
+            };
+            if f.states.contains(&filter::FilterState::from(&task.state())) && f.due.contains(&due_group(&task)) {
+                result.push(Box::new(task));
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        self.load_tasks().await?;
+
+        let mut names = Vec::new();
+        for p in self.tasks.iter().filter_map(|t| t.project.as_ref()) {
+            // Parents of the subprojects are projects too even if they don't have their own tasks
+            let mut name = String::new();
+            for part in p.split(PROJECT_SEPARATOR) {
+                if !name.is_empty() {
+                    name.push(PROJECT_SEPARATOR);
+                }
+                name.push_str(part);
+                names.push(name.to_string());
+            }
+        }
+        names.sort();
+        names.dedup();
+
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for name in names {
+            result.push(Box::new(Project {
+                name,
+                provider: self.name(),
+            }));
+        }
+        Ok(result)
+    }
+
+    async fn create_task(&mut self, project: Option<Box<dyn ProjectTrait>>, t: &NewTask) -> Result<(), Box<dyn Error>> {
+        let now = date_to_str(&Utc::now());
+        let mut task = structs::Task {
+            uuid: new_uuid(&t.text),
+            description: t.text.to_string(),
+            status: "pending".to_string(),
+            entry: Some(now.to_string()),
+            modified: Some(now),
+            due: t.due.as_ref().map(due_to_str),
+            priority: priority_to_str(&t.priority),
+            project: project.map(|p| p.id()),
+            ..Default::default()
+        };
+        if let Some(d) = t.description.as_ref().filter(|d| !d.is_empty()) {
+            task.annotations.push(structs::Annotation {
+                entry: task.entry.clone(),
+                description: d.to_string(),
+            });
+        }
+
+        self.c.import(&[task]).await?;
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let mut t = Self::cast(task).t.clone();
+        set_state(&mut t, &state);
+        t.modified = Some(date_to_str(&Utc::now()));
+
+        self.c.import(&[t]).await?;
+        self.tasks.clear();
+        Ok(())
+    }
+
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        self.change_task_state(task, State::Unknown('-')).await
+    }
+
+    async fn patch_tasks(&mut self, patches: &[TaskPatch]) -> Vec<PatchError> {
+        let mut errors = Vec::new();
+        let mut tasks = Vec::new();
+
+        for p in patches {
+            match Self::patched_task(p) {
+                Ok(t) => tasks.push(t),
+                Err(e) => errors.push(PatchError {
+                    task: p.task.clone_boxed(),
+                    error: e.to_string(),
+                }),
+            }
+        }
+
+        // All the changes are imported at once
+        if !tasks.is_empty() {
+            if let Err(e) = self.c.import(&tasks).await {
+                for p in patches.iter().filter(|p| tasks.iter().any(|t| t.uuid == p.task.id())) {
+                    errors.push(PatchError {
+                        task: p.task.clone_boxed(),
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        self.tasks.clear();
+        errors
+    }
+
+    async fn reload(&mut self) {
+        self.tasks.clear();
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    const EXPORT: &str = r#"[
+{"id":1,"description":"Plant tomatoes","entry":"20250120T100000Z","modified":"20250121T100000Z","status":"pending","uuid":"aaaaaaaa-0000-4000-8000-000000000001","priority":"H","project":"Home.Garden","tags":["outside"],"depends":"aaaaaaaa-0000-4000-8000-000000000002","urgency":8.1},
+{"id":2,"description":"Buy seeds","entry":"20250120T100000Z","start":"20250121T100000Z","status":"pending","uuid":"aaaaaaaa-0000-4000-8000-000000000002","project":"Home","annotations":[{"entry":"20250120T100000Z","description":"Cherry ones"}],"urgency":4.5},
+{"id":0,"description":"Clean the shed","entry":"20250110T100000Z","end":"20250111T100000Z","status":"completed","uuid":"aaaaaaaa-0000-4000-8000-000000000003","priority":"L","urgency":0}
+]"#;
+
+    // A fake `task` binary: it prints the export and saves the imported tasks to a file
+    fn fake_task(name: &str) -> (String, String) {
+        let dir = std::env::temp_dir().join(format!("tatuin-taskwarrior-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let import = dir.join("import.json").to_str().unwrap().to_string();
+        let export = dir.join("export.json");
+        fs::write(&export, EXPORT).unwrap();
+
+        let script = dir.join("task");
+        fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\nfor a in \"$@\"; do\n  case \"$a\" in\n    export) cat '{}';;\n    import) cat > '{import}';;\n  esac\ndone\n",
+                export.to_str().unwrap()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        (script.to_str().unwrap().to_string(), import)
+    }
+
+    fn all_filter() -> filter::Filter {
+        filter::Filter {
+            states: vec![
+                filter::FilterState::Uncompleted,
+                filter::FilterState::InProgress,
+                filter::FilterState::Completed,
+            ],
+            due: vec![filter::Due::NoDate],
+            labels: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn tasks_test() {
+        let (command, _) = fake_task("tasks");
+        let mut p = Provider::new("Taskwarrior", &command, None, &Color::White);
+
+        let tasks = p.tasks(None, &all_filter()).await.unwrap();
+        assert_eq!(tasks.len(), 3);
+
+        let plant = &tasks[0];
+        assert_eq!(plant.text(), "Plant tomatoes");
+        assert_eq!(plant.state(), State::Uncompleted);
+        assert_eq!(plant.priority(), Priority::High);
+        assert_eq!(plant.labels(), vec!["outside".to_string()]);
+        assert_eq!(plant.parent_id(), None);
+        let project = plant.project().unwrap();
+        assert_eq!(project.name(), "Home.Garden");
+        assert_eq!(project.parent_id(), Some("Home".to_string()));
+
+        let buy = &tasks[1];
+        assert_eq!(buy.state(), State::InProgress);
+        assert_eq!(buy.description(), Some("Cherry ones".to_string()));
+        assert_eq!(buy.parent_id(), Some(plant.id()));
+
+        let clean = &tasks[2];
+        assert_eq!(clean.state(), State::Completed);
+        assert_eq!(clean.priority(), Priority::Low);
+        assert!(clean.completed_at().is_some());
+
+        let projects = p.projects().await.unwrap();
+        assert_eq!(
+            projects.iter().map(|p| p.name()).collect::<Vec<String>>(),
+            vec!["Home", "Home.Garden"]
+        );
+
+        let home_tasks = p.tasks(Some(projects[0].clone_boxed()), &all_filter()).await.unwrap();
+        assert_eq!(home_tasks.len(), 2);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn change_task_state_test() {
+        let (command, import) = fake_task("change-state");
+        let mut p = Provider::new("Taskwarrior", &command, None, &Color::White);
+
+        let tasks = p.tasks(None, &all_filter()).await.unwrap();
+        p.change_task_state(tasks[1].as_ref(), State::Completed).await.unwrap();
+
+        let imported: Vec<serde_json::Value> = serde_json::from_str(&fs::read_to_string(&import).unwrap()).unwrap();
+        assert_eq!(imported.len(), 1);
+        let t = &imported[0];
+        assert_eq!(t["uuid"], "aaaaaaaa-0000-4000-8000-000000000002");
+        assert_eq!(t["status"], "completed");
+        assert!(t.get("end").is_some());
+        assert!(t.get("start").is_none());
+        assert!(t.get("id").is_none());
+        assert!(t.get("urgency").is_none());
+        assert_eq!(t["annotations"][0]["description"], "Cherry ones");
+
+        p.change_task_state(tasks[2].as_ref(), State::Uncompleted)
+            .await
+            .unwrap();
+        let imported: Vec<serde_json::Value> = serde_json::from_str(&fs::read_to_string(&import).unwrap()).unwrap();
+        assert_eq!(imported[0]["status"], "pending");
+        assert!(imported[0].get("end").is_none());
+    }
+}
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MIT
+
+use super::structs::Task;
+use std::error::Error;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+// Fields that are calculated by Taskwarrior and shouldn't be imported
+const READ_ONLY_FIELDS: [&str; 2] = ["id", "urgency"];
+
+pub struct Client {
+    command: String,
+    rc: Vec<String>,
+}
+
+impl Client {
+    pub fn new(command: &str, data_location: Option<&str>) -> Self {
+        let mut rc = vec![
+            "rc.confirmation=off".to_string(),
+            "rc.verbose=nothing".to_string(),
+            "rc.json.array=on".to_string(),
+        ];
+        if let Some(d) = data_location {
+            rc.push(format!("rc.data.location={d}"));
+        }
+
+        Self {
+            command: command.to_string(),
+            rc,
+        }
+    }
+
+    async fn run(&self, args: &[&str], input: Option<&str>) -> Result<String, Box<dyn Error>> {
+        let mut child = Command::new(self.command.as_str())
+            .args(&self.rc)
+            .args(args)
+            .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("start '{}': {e}", self.command))?;
+
+        if let Some(input) = input {
+            let mut stdin = child.stdin.take().ok_or("stdin of the process isn't available")?;
+            stdin.write_all(input.as_bytes()).await?;
+            // The stdin is closed here, so the process knows that the input is over
+        }
+
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            return Err(Box::<dyn Error>::from(format!(
+                "{} has failed: {}",
+                self.command,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(String::from_utf8(output.stdout)?)
+    }
+
+    pub async fn export(&self, filter: &[&str]) -> Result<Vec<Task>, Box<dyn Error>> {
+        let mut args = filter.to_vec();
+        args.push("export");
+
+        let output = self.run(&args, None).await?;
+        Ok(serde_json::from_str::<Vec<Task>>(&output)?)
+    }
+
+    // Creates the tasks or updates the existing ones with the same uuid
+    pub async fn import(&self, tasks: &[Task]) -> Result<(), Box<dyn Error>> {
+        let tasks = tasks
+            .iter()
+            .map(|t| {
+                let mut t = t.clone();
+                for f in READ_ONLY_FIELDS {
+                    t.other.remove(f);
+                }
+                t
+            })
+            .collect::<Vec<Task>>();
+
+        self.run(&["import", "-"], Some(&serde_json::to_string(&tasks)?))
+            .await
+            .map(|_| ())
+    }
+}
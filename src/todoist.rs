@@ -6,7 +6,7 @@ mod task;
 
 use crate::filter;
 use crate::project::Project as ProjectTrait;
-use crate::provider::Provider as ProviderTrait;
+use crate::provider::{NewTask, Provider as ProviderTrait, TaskPatch};
 use crate::task::{State, Task as TaskTrait};
 use ratatui::style::Color;
 use std::cmp::Ordering;
@@ -136,20 +136,44 @@ impl ProviderTrait for Provider {
         Ok(result)
     }
 
+    async fn create_task(&mut self, project: Option<Box<dyn ProjectTrait>>, t: &NewTask) -> Result<(), Box<dyn Error>> {
+        let result = self.c.create_task(&project.map(|p| p.id()), t).await;
+        if result.is_ok() {
+            self.tasks.clear()
+        }
+        result
+    }
+
+    async fn update_task(&mut self, patch: &TaskPatch) -> Result<(), Box<dyn Error>> {
+        let result = self.c.update_task(patch.task.id().as_str(), patch).await;
+        if result.is_ok() {
+            self.tasks.clear()
+        }
+        result
+    }
+
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        let result = self.c.delete_task(task.id().as_str()).await;
+        if result.is_ok() {
+            self.tasks.clear()
+        }
+        result
+    }
+
     async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
-        match state {
-            State::Completed => {
-                let result = self.c.close_task(task.id().as_str()).await;
-                if result.is_ok() {
-                    self.tasks.clear()
-                }
-                result
-            }
-            State::InProgress | State::Unknown(_) => Err(Box::<dyn Error>::from("wrong state")),
-            State::Uncompleted => {
-                todo!("implement me")
-            }
+        let result = match state {
+            State::Completed => self.c.close_task(task.id().as_str()).await,
+            State::Uncompleted => self.c.reopen_task(task.id().as_str()).await,
+            State::InProgress | State::Unknown(_) => return Err(Box::<dyn Error>::from("wrong state")),
+        };
+
+        if result.is_ok() {
+            // Both the open and the completed lists are affected, so reload all of them.
+            // A recurring task isn't completed by Todoist but moved to the next occurrence.
+            self.tasks.clear()
         }
+
+        result
     }
     async fn reload(&mut self) {
         self.projects.clear();
@@ -2,10 +2,11 @@
 
 use crate::filter;
 use crate::project::Project as ProjectTrait;
+use crate::provider::{DuePatch, NewTask, TaskPatch};
 use crate::todoist::project::Project;
-use crate::todoist::task::Task;
+use crate::todoist::task::{Task, priority_to_int};
 use reqwest::header::HeaderMap;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
 use url_builder::URLBuilder;
 use urlencoding::encode;
@@ -191,6 +192,120 @@ impl Client {
         Ok(resp)
     }
 
+    pub async fn create_task(
+        &self,
+        project_id: &Option<String>,
+        t: &NewTask,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Serialize)]
+        struct Request<'a> {
+            content: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            description: &'a Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            project_id: &'a Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            due_date: Option<String>,
+            priority: i32,
+        }
+
+        let resp = self
+            .client
+            .post(format!("{BASE_URL}/tasks"))
+            .headers(self.default_header.clone())
+            .json(&Request {
+                content: t.text.as_str(),
+                description: &t.description,
+                project_id,
+                due_date: t.due.map(|d| d.format("%Y-%m-%d").to_string()),
+                priority: priority_to_int(&t.priority),
+            })
+            .send()
+            .await?;
+        if resp.status().is_success() {
+            return Ok(());
+        }
+        Err(Box::<dyn std::error::Error>::from(format!(
+            "wrong status: {}",
+            resp.status().as_str()
+        )))
+    }
+
+    pub async fn update_task(&self, task_id: &str, patch: &TaskPatch) -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Serialize)]
+        struct Request<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            content: &'a Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            description: &'a Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            due_date: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            due_string: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            priority: Option<i32>,
+        }
+
+        let (due_date, due_string) = match &patch.due {
+            Some(DuePatch::Date(d)) => (Some(d.format("%Y-%m-%d").to_string()), None),
+            Some(DuePatch::NoDate) => (None, Some("no date")),
+            None => (None, None),
+        };
+
+        let resp = self
+            .client
+            .post(format!("{BASE_URL}/tasks/{task_id}"))
+            .headers(self.default_header.clone())
+            .json(&Request {
+                content: &patch.text,
+                description: &patch.description,
+                due_date,
+                due_string,
+                priority: patch.priority.as_ref().map(priority_to_int),
+            })
+            .send()
+            .await?;
+        if resp.status().is_success() {
+            return Ok(());
+        }
+        Err(Box::<dyn std::error::Error>::from(format!(
+            "wrong status: {}",
+            resp.status().as_str()
+        )))
+    }
+
+    pub async fn reopen_task(&self, task_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let resp = self
+            .client
+            .post(format!("{BASE_URL}/tasks/{task_id}/reopen"))
+            .headers(self.default_header.clone())
+            .send()
+            .await?;
+        if resp.status().is_success() {
+            return Ok(());
+        }
+        Err(Box::<dyn std::error::Error>::from(format!(
+            "wrong status: {}",
+            resp.status().as_str()
+        )))
+    }
+
+    pub async fn delete_task(&self, task_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let resp = self
+            .client
+            .delete(format!("{BASE_URL}/tasks/{task_id}"))
+            .headers(self.default_header.clone())
+            .send()
+            .await?;
+        if resp.status().is_success() {
+            return Ok(());
+        }
+        Err(Box::<dyn std::error::Error>::from(format!(
+            "wrong status: {}",
+            resp.status().as_str()
+        )))
+    }
+
     pub async fn close_task(&self, task_id: &str) -> Result<(), Box<dyn std::error::Error>> {
         let resp = self
             .client
@@ -74,6 +74,15 @@ fn str_to_date(s: &str) -> Option<DateTimeUtc> {
     None
 }
 
+pub fn priority_to_int(p: &Priority) -> i32 {
+    match p {
+        Priority::Lowest | Priority::Low | Priority::Normal => 1,
+        Priority::Medium => 2,
+        Priority::High => 3,
+        Priority::Highest => 4,
+    }
+}
+
 impl TaskTrait for Task {
     fn id(&self) -> String {
         self.id.to_string()
@@ -134,6 +143,21 @@ impl TaskTrait for Task {
         }
     }
 
+    fn labels(&self) -> Vec<String> {
+        self.labels.clone().unwrap_or_default()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.parent_id.clone()
+    }
+
+    fn recurrence(&self) -> Option<String> {
+        self.due
+            .as_ref()
+            .filter(|d| d.is_recurring)
+            .map(|d| d.string.to_string())
+    }
+
     fn provider(&self) -> String {
         match &self.provider {
             Some(p) => p.to_string(),
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: MIT
+
+// A provider for the todo.txt format (http://todotxt.org): a todo.txt file with
+// the tasks and an optional done.txt file with the archived completed tasks.
+
+mod client;
+mod project;
+mod task;
+
+use crate::filter;
+use crate::project::Project as ProjectTrait;
+use crate::provider::{NewTask, PatchError, Provider as ProviderTrait, TaskPatch};
+use crate::task::{State, Task as TaskTrait};
+use async_trait::async_trait;
+use chrono::Local;
+use ratatui::style::Color;
+use std::error::Error;
+
+pub const PROVIDER_NAME: &str = "todo.txt";
+
+pub struct Provider {
+    name: String,
+    c: client::Client,
+    color: Color,
+}
+
+impl Provider {
+    pub fn new(name: &str, todo_path: &str, done_path: &str, archive: bool, color: &Color) -> Self {
+        Self {
+            name: name.to_string(),
+            c: client::Client::new(todo_path, done_path, archive),
+            color: *color,
+        }
+    }
+}
+
+fn cast(t: &dyn TaskTrait) -> &task::Task {
+    match t.as_any().downcast_ref::<task::Task>() {
+        Some(t) => t,
+        None => panic!("Wrong casting!"),
+    }
+}
+
+#[async_trait]
+impl ProviderTrait for Provider {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn type_name(&self) -> String {
+        PROVIDER_NAME.to_string()
+    }
+
+    async fn tasks(
+        &mut self,
+        project: Option<Box<dyn ProjectTrait>>,
+        f: &filter::Filter,
+    ) -> Result<Vec<Box<dyn TaskTrait>>, Box<dyn Error>> {
+        let project = project.map(|p| p.name());
+
+        let mut result: Vec<Box<dyn TaskTrait>> = Vec::new();
+        for mut t in self.c.tasks(f)? {
+            if project.as_ref().is_some_and(|p| !t.projects().contains(p)) {
+                continue;
+            }
+
+            t.provider = self.name();
+            result.push(Box::new(t));
+        }
+        Ok(result)
+    }
+
+    async fn projects(&mut self) -> Result<Vec<Box<dyn ProjectTrait>>, Box<dyn Error>> {
+        let mut result: Vec<Box<dyn ProjectTrait>> = Vec::new();
+        for p in self.c.projects()? {
+            result.push(Box::new(project::Project::new(&self.name, &p)));
+        }
+        Ok(result)
+    }
+
+    async fn create_task(&mut self, project: Option<Box<dyn ProjectTrait>>, t: &NewTask) -> Result<(), Box<dyn Error>> {
+        if t.description.is_some() {
+            return Err(Box::<dyn Error>::from("todo.txt tasks don't have a description"));
+        }
+
+        let mut task = task::Task {
+            created_at: Some(Local::now().date_naive()),
+            ..Default::default()
+        };
+        task.set_text(&t.text);
+        if let Some(p) = project {
+            let tag = format!("+{}", p.name());
+            if !t.text.split_whitespace().any(|w| w == tag) {
+                task.body.push_str(format!(" {tag}").as_str());
+            }
+        }
+        task.set_due(t.due.map(|d| d.date_naive()));
+        task.set_priority(&t.priority);
+
+        self.c.create_task(&task)
+    }
+
+    async fn change_task_state(&mut self, task: &dyn TaskTrait, state: State) -> Result<(), Box<dyn Error>> {
+        let mut patch = TaskPatch::new(task.clone_boxed());
+        patch.state = Some(state);
+
+        match self.patch_tasks(&[patch]).await.pop() {
+            Some(e) => Err(Box::<dyn Error>::from(e.error)),
+            None => Ok(()),
+        }
+    }
+
+    async fn delete_task(&mut self, task: &dyn TaskTrait) -> Result<(), Box<dyn Error>> {
+        let mut patch = TaskPatch::new(task.clone_boxed());
+        patch.delete = true;
+
+        match self.patch_tasks(&[patch]).await.pop() {
+            Some(e) => Err(Box::<dyn Error>::from(e.error)),
+            None => Ok(()),
+        }
+    }
+
+    async fn patch_tasks(&mut self, patches: &[TaskPatch]) -> Vec<PatchError> {
+        let mut client_patches = Vec::new();
+        let mut errors = Vec::new();
+        for p in patches.iter() {
+            if p.description.is_some() && !p.delete {
+                errors.push(PatchError {
+                    task: p.task.clone_boxed(),
+                    error: "todo.txt tasks don't have a description".to_string(),
+                });
+                continue;
+            }
+
+            client_patches.push(client::TaskPatch {
+                task: cast(p.task.as_ref()),
+                state: p.state.clone(),
+                text: p.text.clone(),
+                due: p.due.clone(),
+                priority: p.priority.clone(),
+                delete: p.delete,
+            });
+        }
+
+        for e in self.c.patch_tasks(&client_patches) {
+            errors.push(PatchError {
+                task: e.task.clone_boxed(),
+                error: e.error,
+            })
+        }
+
+        errors
+    }
+
+    async fn reload(&mut self) {
+        // The files are read on every request
+    }
+
+    fn color(&self) -> Color {
+        self.color
+    }
+}
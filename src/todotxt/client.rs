@@ -0,0 +1,315 @@
+// SPDX-License-Identifier: MIT
+
+use super::task::Task;
+use crate::filter;
+use crate::provider::DuePatch;
+use crate::task::{Priority, State, Task as TaskTrait, due_group};
+use chrono::{Local, NaiveDate};
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+
+pub struct TaskPatch<'a> {
+    pub task: &'a Task,
+    pub state: Option<State>,
+    pub text: Option<String>,
+    pub due: Option<DuePatch>,
+    pub priority: Option<Priority>,
+    pub delete: bool,
+}
+
+impl TaskPatch<'_> {
+    fn patched_task(&self, today: NaiveDate) -> Result<Task, Box<dyn Error>> {
+        let mut t = self.task.clone();
+
+        if let Some(text) = &self.text {
+            t.set_text(text);
+        }
+        match &self.due {
+            Some(DuePatch::Date(d)) => t.set_due(Some(d.date_naive())),
+            Some(DuePatch::NoDate) => t.set_due(None),
+            None => {}
+        }
+        if let Some(p) = &self.priority {
+            t.set_priority(p);
+        }
+        match &self.state {
+            Some(State::Completed) => t.complete(today),
+            Some(State::Uncompleted) => t.uncomplete(),
+            Some(s) => {
+                return Err(Box::<dyn Error>::from(format!(
+                    "todo.txt tasks can't be in the state {s:?}, only completed or uncompleted"
+                )));
+            }
+            None => {}
+        }
+
+        Ok(t)
+    }
+}
+
+pub struct PatchError {
+    pub task: Task,
+    pub error: String,
+}
+
+struct File {
+    file_path: String,
+    lines: Vec<String>,
+}
+
+impl File {
+    // A missing file is treated as an empty one if it's allowed, e.g. done.txt doesn't exist before the first archiving
+    fn open(file_path: &str, allow_missing: bool) -> Result<Self, Box<dyn Error>> {
+        let content = match fs::read_to_string(file_path) {
+            Ok(c) => c,
+            Err(e) if allow_missing && e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(Box::<dyn Error>::from(format!("read {file_path}: {e}"))),
+        };
+
+        Ok(Self {
+            file_path: file_path.to_string(),
+            lines: content.lines().map(|l| l.to_string()).collect(),
+        })
+    }
+
+    fn flush(&self) -> Result<(), Box<dyn Error>> {
+        let mut content = self.lines.join("\n");
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        fs::write(&self.file_path, content)?;
+        Ok(())
+    }
+
+    fn tasks(&self) -> Vec<Task> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, l)| {
+                Task::parse(l).map(|t| Task {
+                    file_path: self.file_path.to_string(),
+                    line: i,
+                    ..t
+                })
+            })
+            .collect()
+    }
+
+    fn check_task_is_actual(&self, t: &Task) -> Result<(), Box<dyn Error>> {
+        if self.lines.get(t.line) != Some(&t.raw) {
+            return Err(Box::<dyn Error>::from("Task has been changed since last loading"));
+        }
+        Ok(())
+    }
+}
+
+pub struct Client {
+    todo_path: String,
+    done_path: String,
+    archive: bool,
+}
+
+impl Client {
+    pub fn new(todo_path: &str, done_path: &str, archive: bool) -> Self {
+        Self {
+            todo_path: todo_path.to_string(),
+            done_path: done_path.to_string(),
+            archive,
+        }
+    }
+
+    fn all_tasks(&self) -> Result<Vec<Task>, Box<dyn Error>> {
+        let mut tasks = File::open(&self.todo_path, false)?.tasks();
+        tasks.append(&mut File::open(&self.done_path, true)?.tasks());
+        Ok(tasks)
+    }
+
+    pub fn tasks(&self, f: &filter::Filter) -> Result<Vec<Task>, Box<dyn Error>> {
+        Ok(self.all_tasks()?.into_iter().filter(|t| accept_filter(t, f)).collect())
+    }
+
+    pub fn projects(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut result: Vec<String> = self.all_tasks()?.iter().flat_map(|t| t.projects()).collect();
+        result.sort();
+        result.dedup();
+        Ok(result)
+    }
+
+    pub fn create_task(&self, t: &Task) -> Result<(), Box<dyn Error>> {
+        let mut f = File::open(&self.todo_path, false)?;
+        f.lines.push(t.to_line());
+        f.flush()
+    }
+
+    fn apply_patch(&self, p: &TaskPatch, today: NaiveDate) -> Result<(), Box<dyn Error>> {
+        let mut f = File::open(&p.task.file_path, false)?;
+        f.check_task_is_actual(p.task)?;
+
+        if p.delete {
+            f.lines.remove(p.task.line);
+            return f.flush();
+        }
+
+        let new_task = p.patched_task(today)?;
+        let is_done_file = p.task.file_path == self.done_path;
+
+        // Completed tasks are moved to done.txt if archiving is enabled and uncompleted ones are moved back
+        let target_path = match (new_task.completed, is_done_file) {
+            (true, false) if self.archive => Some(&self.done_path),
+            (false, true) => Some(&self.todo_path),
+            _ => None,
+        };
+
+        match target_path {
+            Some(path) => {
+                let mut target = File::open(path, true)?;
+                target.lines.push(new_task.to_line());
+                target.flush()?;
+
+                f.lines.remove(p.task.line);
+            }
+            None => f.lines[p.task.line] = new_task.to_line(),
+        }
+
+        f.flush()
+    }
+
+    pub fn patch_tasks(&self, patches: &[TaskPatch]) -> Vec<PatchError> {
+        let today = Local::now().date_naive();
+
+        // Lines are changed from the end of the files, so the line numbers of the other tasks stay correct
+        let mut patches = patches.iter().collect::<Vec<&TaskPatch>>();
+        patches.sort_by(|a, b| {
+            a.task
+                .file_path
+                .cmp(&b.task.file_path)
+                .then(b.task.line.cmp(&a.task.line))
+        });
+
+        let mut errors = Vec::new();
+        for p in patches {
+            if let Err(e) = self.apply_patch(p, today) {
+                errors.push(PatchError {
+                    task: p.task.clone(),
+                    error: e.to_string(),
+                });
+            }
+        }
+
+        errors
+    }
+}
+
+fn accept_filter(t: &Task, f: &filter::Filter) -> bool {
+    f.states.contains(&filter::FilterState::from(&t.state())) && f.due.contains(&due_group(t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn temp_dir(name: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("tatuin-todotxt-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.to_str().unwrap().to_string()
+    }
+
+    fn all_filter() -> filter::Filter {
+        filter::Filter {
+            states: vec![filter::FilterState::Uncompleted, filter::FilterState::Completed],
+            due: vec![
+                filter::Due::Overdue,
+                filter::Due::Today,
+                filter::Due::Future,
+                filter::Due::NoDate,
+            ],
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn archive_test() {
+        let dir = temp_dir("archive");
+        let todo_path = Path::new(&dir).join("todo.txt").to_str().unwrap().to_string();
+        let done_path = Path::new(&dir).join("done.txt").to_str().unwrap().to_string();
+        fs::write(&todo_path, "(A) First +Work\nSecond\nThird @home\n").unwrap();
+
+        let c = Client::new(&todo_path, &done_path, true);
+        let tasks = c.tasks(&all_filter()).unwrap();
+        assert_eq!(tasks.len(), 3);
+
+        let patches = [
+            TaskPatch {
+                task: &tasks[0],
+                state: Some(State::Completed),
+                text: None,
+                due: None,
+                priority: None,
+                delete: false,
+            },
+            TaskPatch {
+                task: &tasks[1],
+                state: None,
+                text: None,
+                due: None,
+                priority: None,
+                delete: true,
+            },
+            TaskPatch {
+                task: &tasks[2],
+                state: None,
+                text: Some("Third task".to_string()),
+                due: None,
+                priority: Some(Priority::High),
+                delete: false,
+            },
+        ];
+        let errors = c.patch_tasks(&patches);
+        assert!(
+            errors.is_empty(),
+            "{:?}",
+            errors.iter().map(|e| &e.error).collect::<Vec<_>>()
+        );
+
+        let today = Local::now().date_naive().format("%Y-%m-%d");
+        assert_eq!(fs::read_to_string(&todo_path).unwrap(), "(B) Third task @home\n");
+        assert_eq!(
+            fs::read_to_string(&done_path).unwrap(),
+            format!("x {today} First +Work pri:A\n")
+        );
+
+        // The file has been changed, so the old tasks can't be patched
+        let errors = c.patch_tasks(&[TaskPatch {
+            task: &tasks[2],
+            state: Some(State::Completed),
+            text: None,
+            due: None,
+            priority: None,
+            delete: false,
+        }]);
+        assert_eq!(errors.len(), 1);
+
+        let tasks = c.tasks(&all_filter()).unwrap();
+        let done = tasks.iter().find(|t| t.completed).unwrap();
+        let errors = c.patch_tasks(&[TaskPatch {
+            task: done,
+            state: Some(State::Uncompleted),
+            text: None,
+            due: None,
+            priority: None,
+            delete: false,
+        }]);
+        assert!(errors.is_empty());
+        assert_eq!(
+            fs::read_to_string(&todo_path).unwrap(),
+            "(B) Third task @home\n(A) First +Work\n"
+        );
+        assert_eq!(fs::read_to_string(&done_path).unwrap(), "");
+        assert_eq!(c.projects().unwrap(), vec!["Work".to_string()]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
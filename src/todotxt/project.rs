@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: MIT
+
+use crate::project::Project as ProjectTrait;
+
+// A +project tag of todo.txt
+#[derive(Clone)]
+pub struct Project {
+    provider: String,
+    name: String,
+}
+
+impl Project {
+    pub fn new(provider: &str, name: &str) -> Self {
+        Self {
+            provider: provider.to_string(),
+            name: name.to_string(),
+        }
+    }
+}
+
+impl ProjectTrait for Project {
+    fn id(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn description(&self) -> String {
+        String::new()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        None
+    }
+
+    fn is_inbox(&self) -> bool {
+        false
+    }
+
+    fn is_favorite(&self) -> bool {
+        false
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ProjectTrait> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,437 @@
+// SPDX-License-Identifier: MIT
+
+use super::project::Project;
+use crate::project::Project as ProjectTrait;
+use crate::task::{DateTimeUtc, Priority, State, Task as TaskTrait};
+use chrono::{NaiveDate, Utc};
+use sha256::digest;
+use std::any::Any;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DUE_KEY: &str = "due";
+// Priority of a completed task is kept in this tag to restore it when the task is uncompleted
+const PRIORITY_KEY: &str = "pri";
+
+const PRIORITIES: [(char, Priority); 5] = [
+    ('A', Priority::Highest),
+    ('B', Priority::High),
+    ('C', Priority::Medium),
+    ('D', Priority::Low),
+    ('E', Priority::Lowest),
+];
+
+fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, DATE_FORMAT).ok()
+}
+
+fn date_to_datetime(d: NaiveDate) -> Option<DateTimeUtc> {
+    d.and_hms_opt(0, 0, 0)
+        .map(|dt| DateTimeUtc::from_naive_utc_and_offset(dt, Utc))
+}
+
+fn split_first_word(s: &str) -> (&str, &str) {
+    match s.split_once(' ') {
+        Some((w, rest)) => (w, rest.trim_start()),
+        None => (s, ""),
+    }
+}
+
+// +project, @context and key:value words are metadata, the other words are the task's text
+fn is_metadata(word: &str) -> bool {
+    if word.len() > 1 && (word.starts_with('+') || word.starts_with('@')) {
+        return true;
+    }
+
+    match word.split_once(':') {
+        // Urls like https://example.com aren't key:value tags
+        Some((k, v)) => {
+            !k.is_empty() && !v.is_empty() && !v.starts_with('/') && k.chars().all(|c| c.is_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+pub fn priority_from_char(c: char) -> Priority {
+    PRIORITIES
+        .iter()
+        .find(|(p, _)| *p == c)
+        .map(|(_, p)| p.clone())
+        .unwrap_or(Priority::Lowest)
+}
+
+pub fn priority_to_char(p: &Priority) -> Option<char> {
+    PRIORITIES.iter().find(|(_, x)| x == p).map(|(c, _)| *c)
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Task {
+    pub provider: String,
+    pub file_path: String,
+    pub line: usize,
+    pub raw: String,
+
+    pub completed: bool,
+    pub priority: Option<char>,
+    pub completed_at: Option<NaiveDate>,
+    pub created_at: Option<NaiveDate>,
+    // Everything after the priority and dates: the text with projects, contexts and tags
+    pub body: String,
+}
+
+impl Task {
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut rest = line.trim();
+        if rest.is_empty() {
+            return None;
+        }
+
+        let mut t = Task {
+            raw: line.to_string(),
+            ..Default::default()
+        };
+
+        if let Some(r) = rest.strip_prefix("x ") {
+            t.completed = true;
+            rest = r.trim_start();
+
+            let (w, r) = split_first_word(rest);
+            if let Some(d) = parse_date(w) {
+                t.completed_at = Some(d);
+                rest = r;
+            }
+        } else {
+            let bytes = rest.as_bytes();
+            if bytes.len() > 3
+                && bytes[0] == b'('
+                && bytes[1].is_ascii_uppercase()
+                && bytes[2] == b')'
+                && bytes[3] == b' '
+            {
+                t.priority = Some(bytes[1] as char);
+                rest = rest[4..].trim_start();
+            }
+        }
+
+        let (w, r) = split_first_word(rest);
+        if let Some(d) = parse_date(w) {
+            t.created_at = Some(d);
+            rest = r;
+        }
+
+        t.body = rest.to_string();
+        Some(t)
+    }
+
+    pub fn to_line(&self) -> String {
+        let mut parts = Vec::new();
+
+        if self.completed {
+            parts.push("x".to_string());
+            if let Some(d) = self.completed_at {
+                parts.push(d.format(DATE_FORMAT).to_string());
+            }
+        } else if let Some(p) = self.priority {
+            parts.push(format!("({p})"));
+        }
+
+        if let Some(d) = self.created_at {
+            parts.push(d.format(DATE_FORMAT).to_string());
+        }
+
+        parts.push(self.body.to_string());
+        parts.join(" ")
+    }
+
+    fn words(&self) -> impl Iterator<Item = &str> {
+        self.body.split_whitespace()
+    }
+
+    pub fn tag(&self, key: &str) -> Option<String> {
+        self.words()
+            .filter(|w| is_metadata(w))
+            .filter_map(|w| w.split_once(':'))
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.to_string())
+    }
+
+    pub fn set_tag(&mut self, key: &str, value: Option<&str>) {
+        let prefix = format!("{key}:");
+        let mut words = self
+            .words()
+            .filter(|w| !(is_metadata(w) && w.starts_with(prefix.as_str())))
+            .map(|w| w.to_string())
+            .collect::<Vec<String>>();
+        if let Some(v) = value {
+            words.push(format!("{prefix}{v}"));
+        }
+        self.body = words.join(" ");
+    }
+
+    pub fn projects(&self) -> Vec<String> {
+        self.words()
+            .filter(|w| w.len() > 1)
+            .filter_map(|w| w.strip_prefix('+'))
+            .map(|w| w.to_string())
+            .collect()
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        let mut words = vec![text.trim().to_string()];
+        words.extend(self.words().filter(|w| is_metadata(w)).map(|w| w.to_string()));
+        self.body = words.join(" ");
+    }
+
+    pub fn set_due(&mut self, d: Option<NaiveDate>) {
+        self.set_tag(DUE_KEY, d.map(|d| d.format(DATE_FORMAT).to_string()).as_deref());
+    }
+
+    pub fn set_priority(&mut self, p: &Priority) {
+        let c = priority_to_char(p);
+        if self.completed {
+            self.set_tag(PRIORITY_KEY, c.map(|c| c.to_string()).as_deref());
+        } else {
+            self.priority = c;
+        }
+    }
+
+    pub fn complete(&mut self, date: NaiveDate) {
+        if self.completed {
+            return;
+        }
+
+        self.completed = true;
+        self.completed_at = Some(date);
+        if let Some(p) = self.priority.take() {
+            self.set_tag(PRIORITY_KEY, Some(p.to_string().as_str()));
+        }
+    }
+
+    pub fn uncomplete(&mut self) {
+        if !self.completed {
+            return;
+        }
+
+        self.completed = false;
+        self.completed_at = None;
+        if let Some(p) = self.tag(PRIORITY_KEY) {
+            self.priority = p.chars().next().filter(|c| c.is_ascii_uppercase());
+            self.set_tag(PRIORITY_KEY, None);
+        }
+    }
+}
+
+impl TaskTrait for Task {
+    fn id(&self) -> String {
+        digest(format!("{}:{}:{}", self.file_path, self.line, self.raw))
+    }
+
+    fn text(&self) -> String {
+        self.words()
+            .filter(|w| !is_metadata(w))
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    fn state(&self) -> State {
+        if self.completed {
+            State::Completed
+        } else {
+            State::Uncompleted
+        }
+    }
+
+    fn priority(&self) -> Priority {
+        let p = if self.completed {
+            self.tag(PRIORITY_KEY).and_then(|p| p.chars().next())
+        } else {
+            self.priority
+        };
+
+        p.map(priority_from_char).unwrap_or_default()
+    }
+
+    fn created_at(&self) -> Option<DateTimeUtc> {
+        self.created_at.and_then(date_to_datetime)
+    }
+
+    fn completed_at(&self) -> Option<DateTimeUtc> {
+        self.completed_at.and_then(date_to_datetime)
+    }
+
+    fn due(&self) -> Option<DateTimeUtc> {
+        self.tag(DUE_KEY)
+            .and_then(|d| parse_date(&d))
+            .and_then(date_to_datetime)
+    }
+
+    fn place(&self) -> String {
+        format!(
+            "{}:{}",
+            std::path::Path::new(&self.file_path)
+                .file_name()
+                .unwrap_or_default()
+                .to_str()
+                .unwrap_or_default(),
+            self.line + 1
+        )
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.words()
+            .filter(|w| w.len() > 1)
+            .filter_map(|w| w.strip_prefix('@'))
+            .map(|w| w.to_string())
+            .collect()
+    }
+
+    fn provider(&self) -> String {
+        self.provider.to_string()
+    }
+
+    fn project(&self) -> Option<Box<dyn ProjectTrait>> {
+        self.projects()
+            .first()
+            .map(|p| Box::new(Project::new(&self.provider, p)) as Box<dyn ProjectTrait>)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_boxed(&self) -> Box<dyn TaskTrait> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        struct Case<'a> {
+            name: &'a str,
+            line: &'a str,
+            text: &'a str,
+            state: State,
+            priority: Priority,
+            due: Option<&'a str>,
+            completed_at: Option<&'a str>,
+            project: Option<&'a str>,
+            labels: &'a [&'a str],
+        }
+        const CASES: &[Case] = &[
+            Case {
+                name: "simple task",
+                line: "Buy milk",
+                text: "Buy milk",
+                state: State::Uncompleted,
+                priority: Priority::Normal,
+                due: None,
+                completed_at: None,
+                project: None,
+                labels: &[],
+            },
+            Case {
+                name: "full task",
+                line: "(A) 2025-01-20 Call mom +Family @phone due:2025-01-27 @home",
+                text: "Call mom",
+                state: State::Uncompleted,
+                priority: Priority::Highest,
+                due: Some("2025-01-27"),
+                completed_at: None,
+                project: Some("Family"),
+                labels: &["phone", "home"],
+            },
+            Case {
+                name: "completed task with kept priority",
+                line: "x 2025-01-28 2025-01-20 Write report +Work pri:C",
+                text: "Write report",
+                state: State::Completed,
+                priority: Priority::Medium,
+                due: None,
+                completed_at: Some("2025-01-28"),
+                project: Some("Work"),
+                labels: &[],
+            },
+            Case {
+                name: "urls and lonely signs are text",
+                line: "(F) Read https://example.com + @ later",
+                text: "Read https://example.com + @ later",
+                state: State::Uncompleted,
+                priority: Priority::Lowest,
+                due: None,
+                completed_at: None,
+                project: None,
+                labels: &[],
+            },
+            Case {
+                name: "priority not at the beginning",
+                line: "Call (A) mom",
+                text: "Call (A) mom",
+                state: State::Uncompleted,
+                priority: Priority::Normal,
+                due: None,
+                completed_at: None,
+                project: None,
+                labels: &[],
+            },
+        ];
+
+        for c in CASES {
+            let t = Task::parse(c.line).unwrap();
+            assert_eq!(t.text(), c.text, "Test '{}' was failed", c.name);
+            assert_eq!(t.state(), c.state, "Test '{}' was failed", c.name);
+            assert_eq!(t.priority(), c.priority, "Test '{}' was failed", c.name);
+            assert_eq!(
+                t.due().map(|d| d.format(DATE_FORMAT).to_string()).as_deref(),
+                c.due,
+                "Test '{}' was failed",
+                c.name
+            );
+            assert_eq!(
+                t.completed_at().map(|d| d.format(DATE_FORMAT).to_string()).as_deref(),
+                c.completed_at,
+                "Test '{}' was failed",
+                c.name
+            );
+            assert_eq!(
+                t.project().map(|p| p.name()).as_deref(),
+                c.project,
+                "Test '{}' was failed",
+                c.name
+            );
+            assert_eq!(t.labels(), c.labels, "Test '{}' was failed", c.name);
+            assert_eq!(t.to_line(), c.line, "Test '{}' was failed", c.name);
+        }
+    }
+
+    #[test]
+    fn complete_and_uncomplete_test() {
+        let mut t = Task::parse("(B) 2025-01-20 Call mom +Family due:2025-01-27").unwrap();
+
+        t.complete(NaiveDate::from_ymd_opt(2025, 1, 28).unwrap());
+        assert_eq!(
+            t.to_line(),
+            "x 2025-01-28 2025-01-20 Call mom +Family due:2025-01-27 pri:B"
+        );
+        assert_eq!(t.priority(), Priority::High);
+
+        t.uncomplete();
+        assert_eq!(t.to_line(), "(B) 2025-01-20 Call mom +Family due:2025-01-27");
+    }
+
+    #[test]
+    fn edit_test() {
+        let mut t = Task::parse("(B) Call mom +Family due:2025-01-27 @phone").unwrap();
+
+        t.set_text("Visit mom");
+        t.set_due(NaiveDate::from_ymd_opt(2025, 2, 1));
+        t.set_priority(&Priority::Normal);
+        assert_eq!(t.to_line(), "Visit mom +Family @phone due:2025-02-01");
+
+        t.set_due(None);
+        t.set_priority(&Priority::Low);
+        assert_eq!(t.to_line(), "(D) Visit mom +Family @phone");
+    }
+}
@@ -1,7 +1,9 @@
 // SPDX-License-Identifier: MIT
 
 use super::state::{StateSettings, StatefulObject};
+use crate::cache::Cache;
 use crate::filter;
+use crate::provider::NewTask;
 use crate::state::{State, state_from_str, state_to_str};
 use crate::{project, provider};
 use async_trait::async_trait;
@@ -20,7 +22,9 @@ use std::hash::Hash;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::sync::{OnceCell, RwLock};
+mod confirmation_dialog;
 mod dialog;
 mod filter_widget;
 mod header;
@@ -28,13 +32,16 @@ mod hyperlink;
 mod key_bindings_help_dialog;
 mod key_buffer;
 mod list;
+mod provider_item;
 mod selectable_list;
 mod shortcut;
 mod states_dialog;
 pub mod style;
+mod task_dialog;
 mod task_info_widget;
 mod tasks_widget;
 mod text_input_dialog;
+use provider_item::{LoadedTasks, LoadingState, ProviderItem};
 use selectable_list::SelectableList;
 use strum::{Display, EnumString};
 use tokio_stream::StreamExt;
@@ -69,7 +76,7 @@ trait AppBlockWidget {
 
 pub struct App {
     should_exit: bool,
-    providers: Arc<RwLock<SelectableList<Box<dyn provider::Provider>>>>,
+    providers: Arc<RwLock<SelectableList<ProviderItem>>>,
     projects: Arc<RwLock<SelectableList<Box<dyn project::Project>>>>,
     current_block: AppBlock,
 
@@ -88,17 +95,40 @@ pub struct App {
     save_state_shortcut: Shortcut,
     commit_changes_shortcut: Shortcut,
     show_keybindings_help_shortcut: Shortcut,
+    add_task_shortcut: Shortcut,
+    edit_task_shortcut: Shortcut,
+    delete_task_shortcut: Shortcut,
+    toggle_subtasks_shortcut: Shortcut,
 
     all_shortcuts: Vec<Arc<std::sync::RwLock<shortcut::SharedData>>>,
 
     dialog: Option<Box<dyn dialog::DialogTrait>>,
 
     settings: Arc<RwLock<Box<dyn StateSettings>>>,
+
+    cache: Cache,
+    load_generation: u64,
+    tasks_loaded_tx: UnboundedSender<LoadedTasks>,
+    tasks_loaded_rx: Option<UnboundedReceiver<LoadedTasks>>,
 }
 
 #[allow(clippy::arc_with_non_send_sync)] // TODO: think how to remove this
 impl App {
-    pub fn new(providers: Vec<Box<dyn provider::Provider>>, settings: Box<dyn StateSettings>) -> Self {
+    pub fn new(
+        providers: Vec<Box<dyn provider::Provider>>,
+        timeouts: &HashMap<String, Duration>,
+        cache: Cache,
+        settings: Box<dyn StateSettings>,
+    ) -> Self {
+        let providers = providers
+            .into_iter()
+            .map(|p| {
+                let timeout = timeouts.get(&p.name()).copied().unwrap_or(provider::DEFAULT_TIMEOUT);
+                ProviderItem::new(p, timeout)
+            })
+            .collect();
+        let (tasks_loaded_tx, tasks_loaded_rx) = tokio::sync::mpsc::unbounded_channel();
+
         let mut s = Self {
             should_exit: false,
             current_block: AppBlock::TaskList,
@@ -115,6 +145,7 @@ impl App {
             filter_widget: filter_widget::FilterWidget::new(filter::Filter {
                 states: vec![filter::FilterState::Uncompleted],
                 due: vec![filter::Due::Today, filter::Due::Overdue],
+                labels: Vec::new(),
             }),
             tasks_widget: Arc::new(RwLock::new(tasks_widget::TasksWidget::default())),
             task_description_widget: Arc::new(RwLock::new(task_info_widget::TaskInfoWidget::default())),
@@ -128,9 +159,17 @@ impl App {
             save_state_shortcut: Shortcut::new("Save the current state", &['s', 's']),
             commit_changes_shortcut: Shortcut::new("Commit changes", &['c', 'c']),
             show_keybindings_help_shortcut: Shortcut::new("Show help", &['?']),
+            add_task_shortcut: Shortcut::new("Add task", &['a']),
+            edit_task_shortcut: Shortcut::new("Edit the selected task", &['e']),
+            delete_task_shortcut: Shortcut::new("Delete the selected task", &['d', 'd']),
+            toggle_subtasks_shortcut: Shortcut::new("Collapse/expand subtasks of the selected task", &['z', 'a']),
             all_shortcuts: Vec::new(),
             dialog: None,
             settings: Arc::new(RwLock::new(settings)),
+            cache,
+            load_generation: 0,
+            tasks_loaded_tx,
+            tasks_loaded_rx: Some(tasks_loaded_rx),
         };
 
         s.app_blocks.insert(AppBlock::Providers, s.providers.clone());
@@ -146,6 +185,10 @@ impl App {
         s.all_shortcuts.push(s.save_state_shortcut.internal_data());
         s.all_shortcuts.push(s.commit_changes_shortcut.internal_data());
         s.all_shortcuts.push(s.show_keybindings_help_shortcut.internal_data());
+        s.all_shortcuts.push(s.add_task_shortcut.internal_data());
+        s.all_shortcuts.push(s.edit_task_shortcut.internal_data());
+        s.all_shortcuts.push(s.delete_task_shortcut.internal_data());
+        s.all_shortcuts.push(s.toggle_subtasks_shortcut.internal_data());
 
         s.stateful_widgets.insert(AppBlock::Providers, s.providers.clone());
         s.stateful_widgets.insert(AppBlock::Projects, s.projects.clone());
@@ -166,6 +209,7 @@ impl App {
             self.save_state(None).await;
         }
 
+        self.load_cached_tasks().await;
         self.load_tasks().await;
         self.restore_state(None).await;
 
@@ -183,6 +227,11 @@ impl App {
         let mut save_state_accepted = self.save_state_shortcut.subscribe_to_accepted();
         let mut commit_changes_accepted = self.commit_changes_shortcut.subscribe_to_accepted();
         let mut show_keybindings_help_shortcut_accepted = self.show_keybindings_help_shortcut.subscribe_to_accepted();
+        let mut add_task_accepted = self.add_task_shortcut.subscribe_to_accepted();
+        let mut edit_task_accepted = self.edit_task_shortcut.subscribe_to_accepted();
+        let mut delete_task_accepted = self.delete_task_shortcut.subscribe_to_accepted();
+        let mut toggle_subtasks_accepted = self.toggle_subtasks_shortcut.subscribe_to_accepted();
+        let mut tasks_loaded_rx = self.tasks_loaded_rx.take().unwrap();
 
         while !self.should_exit {
             if let Some(d) = &self.dialog {
@@ -204,6 +253,11 @@ impl App {
                 _ = save_state_accepted.recv() => self.save_state_as(),
                 _ = commit_changes_accepted.recv() => self.commit_changes().await,
                 _ = show_keybindings_help_shortcut_accepted.recv() => self.show_keybindings_help().await,
+                _ = add_task_accepted.recv() => self.add_task().await,
+                _ = edit_task_accepted.recv() => self.edit_task().await,
+                _ = delete_task_accepted.recv() => self.tasks_widget.write().await.toggle_deletion(),
+                _ = toggle_subtasks_accepted.recv() => self.toggle_subtasks().await,
+                Some(loaded) = tasks_loaded_rx.recv() => self.on_tasks_loaded(loaded).await,
             }
         }
         Ok(())
@@ -243,22 +297,80 @@ impl App {
     }
 
     async fn load_tasks(&mut self) {
+        self.fetch_tasks(false).await;
+    }
+
+    // Every provider is loaded in its own background task, the results come to on_tasks_loaded.
+    async fn fetch_tasks(&mut self, reload: bool) {
+        let f = self.filter_widget.read().await.filter();
+        self.tasks_widget.write().await.set_labels_filter(&f.labels);
+
+        self.load_generation += 1;
+        for p in self.providers.write().await.iter_mut() {
+            p.load_tasks(&f, reload, self.load_generation, self.tasks_loaded_tx.clone());
+        }
+    }
+
+    // Shows the tasks from the last session while the providers are loading
+    async fn load_cached_tasks(&mut self) {
+        let f = self.filter_widget.read().await.filter();
+
+        for p in self.providers.write().await.iter_mut() {
+            if let Some(snapshot) = self.cache.load(p.name().as_str(), &f).await {
+                self.tasks_widget
+                    .write()
+                    .await
+                    .set_provider_tasks(p.name().as_str(), snapshot.tasks, true);
+                p.cached_at = Some(snapshot.updated_at);
+            }
+        }
+
+        let labels = self.tasks_widget.read().await.tasks_labels();
+        self.filter_widget.write().await.set_available_labels(&labels);
+        self.load_projects().await;
+    }
+
+    async fn on_tasks_loaded(&mut self, loaded: LoadedTasks) {
+        if loaded.generation != self.load_generation {
+            return;
+        }
+
         let project_id = self.selected_project_id().await;
+        let name = loaded.provider.as_str();
 
-        let errors = self
-            .tasks_widget
-            .write()
-            .await
-            .load_tasks(
-                &mut self.providers.write().await.iter_mut(),
-                &self.filter_widget.read().await.filter(),
-            )
-            .await;
+        let (state, cached_at) = match loaded.result {
+            Ok(tasks) => {
+                if let Err(e) = self.cache.save(name, &tasks).await {
+                    self.add_error(format!("Save cache of provider {name} failure: {e}").as_str());
+                }
+                self.tasks_widget.write().await.set_provider_tasks(name, tasks, false);
+                (LoadingState::Loaded, None)
+            }
+            Err(e) => {
+                // The provider is unreachable, so fall back to the tasks from the last successful loading
+                let f = self.filter_widget.read().await.filter();
+                let cached_at = match self.cache.load(name, &f).await {
+                    Some(snapshot) => {
+                        self.tasks_widget
+                            .write()
+                            .await
+                            .set_provider_tasks(name, snapshot.tasks, true);
+                        Some(snapshot.updated_at)
+                    }
+                    None => None,
+                };
+                (LoadingState::Failed(e), cached_at)
+            }
+        };
 
-        for e in errors {
-            self.add_error(e.to_string().as_str());
+        if let Some(p) = self.providers.write().await.iter_mut().find(|p| p.name() == name) {
+            p.state = state;
+            p.cached_at = cached_at;
         }
 
+        let labels = self.tasks_widget.read().await.tasks_labels();
+        self.filter_widget.write().await.set_available_labels(&labels);
+
         if project_id.is_none() {
             self.load_projects().await;
         }
@@ -299,6 +411,10 @@ impl App {
             &mut self.save_state_shortcut,
             &mut self.commit_changes_shortcut,
             &mut self.show_keybindings_help_shortcut,
+            &mut self.add_task_shortcut,
+            &mut self.edit_task_shortcut,
+            &mut self.delete_task_shortcut,
+            &mut self.toggle_subtasks_shortcut,
         ];
         for s in shortcuts {
             match s.accept(&keys) {
@@ -361,11 +477,7 @@ impl App {
             KeyCode::Tab => self.select_next_block().await,
             KeyCode::BackTab => self.select_previous_block().await,
             KeyCode::Char(' ') => self.change_check_state().await,
-            KeyCode::Char('r') => {
-                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                    self.reload().await;
-                }
-            }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => self.reload().await,
             _ => {}
         }
     }
@@ -377,11 +489,7 @@ impl App {
     }
 
     async fn reload(&mut self) {
-        for p in self.providers.write().await.iter_mut() {
-            p.reload().await;
-        }
-
-        self.load_tasks().await;
+        self.fetch_tasks(true).await;
     }
 
     async fn change_check_state(&mut self) {
@@ -614,7 +722,26 @@ impl App {
     async fn render_providers(&mut self, area: Rect, buf: &mut Buffer) {
         self.providers.write().await.render(
             "Providers",
-            |p| -> ListItem { ListItem::from(Span::styled(format!("{} ({})", p.name(), p.type_name()), p.color())) },
+            |p| -> ListItem {
+                let mut spans = vec![Span::styled(format!("{} ({})", p.name(), p.type_name()), p.color())];
+                if let Some(d) = p.cached_at {
+                    spans.push(Span::styled(
+                        format!(
+                            " 🕓 cached {}",
+                            d.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                        ),
+                        style::CACHED_PROVIDER_FG,
+                    ));
+                }
+                match &p.state {
+                    LoadingState::Loading => spans.push(Span::styled(" ⏳", style::LOADING_PROVIDER_FG)),
+                    LoadingState::Loaded => {}
+                    LoadingState::Failed(e) => {
+                        spans.push(Span::styled(format!(" ⚠ {e}"), style::FAILED_PROVIDER_FG));
+                    }
+                }
+                ListItem::from(Line::from(spans))
+            },
             area,
             buf,
         );
@@ -686,9 +813,9 @@ impl App {
             self.add_error(e.as_str());
         }
 
-        let e = self.settings.write().await.save(name, state);
-        if e.is_err() {
-            self.add_error(format!("Save state error: {}", e.unwrap_err()).as_str());
+        let result = self.settings.write().await.save(name, state);
+        if let Err(e) = result {
+            self.add_error(format!("Save state error: {e}").as_str());
         }
     }
 
@@ -730,15 +857,119 @@ impl App {
                 self.save_state(Some(t.as_str())).await;
             }
         }
+
+        if let Some(d) = &d.as_any().downcast_ref::<confirmation_dialog::Dialog>() {
+            if d.confirmed() {
+                self.apply_changes().await;
+            }
+        }
+
+        if let Some(d) = &d.as_any().downcast_ref::<task_dialog::Dialog>() {
+            if let Some(t) = d.new_task() {
+                self.create_task(&t).await;
+            } else if let Some(p) = d.task_patch() {
+                self.tasks_widget.write().await.set_task_patch(p);
+            }
+        }
+    }
+
+    async fn add_task(&mut self) {
+        let provider_name = self.providers.read().await.selected().map(|p| p.name());
+        let provider_name = match provider_name {
+            Some(n) => n,
+            None => {
+                self.add_error("Select a provider to add a task to");
+                return;
+            }
+        };
+
+        let title = match self.projects.read().await.selected() {
+            Some(p) => format!("New task ({provider_name}: {})", p.name()),
+            None => format!("New task ({provider_name})"),
+        };
+
+        self.dialog = Some(Box::new(task_dialog::Dialog::new(title.as_str())));
+    }
+
+    async fn toggle_subtasks(&mut self) {
+        self.tasks_widget.write().await.toggle_collapsed();
+        self.set_current_task().await;
+    }
+
+    async fn edit_task(&mut self) {
+        let t = self.tasks_widget.read().await.selected_task();
+        if let Some(t) = t {
+            let pending = self.tasks_widget.read().await.task_patch(t.as_ref());
+            let title = format!("Edit task ({})", t.provider());
+            self.dialog = Some(Box::new(task_dialog::Dialog::edit(
+                title.as_str(),
+                t.as_ref(),
+                pending.as_ref(),
+            )));
+        }
+    }
+
+    async fn create_task(&mut self, t: &NewTask) {
+        let provider_name = self.providers.read().await.selected().map(|p| p.name());
+        let project = self.projects.read().await.selected().map(|p| p.clone_boxed());
+
+        let provider = self
+            .providers
+            .read()
+            .await
+            .iter()
+            .find(|p| Some(p.name()) == provider_name)
+            .map(|p| p.provider.clone());
+
+        let mut result = Ok(());
+        if let Some(p) = provider {
+            // A loading provider stays locked until its timeout, waiting for it would freeze the UI
+            result = match p.try_lock() {
+                Ok(mut p) => {
+                    let r = p.create_task(project, t).await;
+                    if r.is_ok() {
+                        p.reload().await;
+                    }
+                    r
+                }
+                Err(_) => Err(Box::<dyn std::error::Error>::from(
+                    "the provider is busy with loading, try again after it's loaded",
+                )),
+            };
+        }
+
+        if let Err(e) = result {
+            self.add_error(format!("Add task error: {e}").as_str());
+        }
+
+        self.load_tasks().await;
     }
 
     async fn commit_changes(&mut self) {
+        let mut deleted_count = self.tasks_widget.read().await.deleted_count();
+        for p in self.providers.read().await.iter() {
+            if p.completion_deletes_tasks() {
+                deleted_count += self.tasks_widget.read().await.completed_count(&p.name());
+            }
+        }
+        if deleted_count != 0 {
+            let d = confirmation_dialog::Dialog::new(
+                "Commit changes",
+                format!("{deleted_count} task(s) will be deleted. Are you sure?").as_str(),
+            );
+            self.dialog = Some(Box::new(d));
+        } else {
+            self.apply_changes().await;
+        }
+    }
+
+    async fn apply_changes(&mut self) {
         if self.tasks_widget.read().await.has_changes() {
             let errors = self
                 .tasks_widget
                 .write()
                 .await
-                .commit_changes(&mut self.providers.write().await.iter_mut())
+                .commit_changes(self.providers.read().await.iter())
                 .await;
 
             for e in errors {
@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: MIT
+
+use super::dialog::DialogTrait;
+use super::style;
+use async_trait::async_trait;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Rect, Size};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget, Wrap};
+
+pub struct Dialog {
+    title: String,
+    text: String,
+    should_be_closed: bool,
+    confirmed: bool,
+}
+
+impl Dialog {
+    pub fn new(title: &str, text: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            text: text.to_string(),
+            should_be_closed: false,
+            confirmed: false,
+        }
+    }
+
+    pub fn confirmed(&self) -> bool {
+        self.confirmed
+    }
+}
+
+#[async_trait]
+impl DialogTrait for Dialog {
+    async fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let b = Block::default()
+            .title_alignment(ratatui::layout::Alignment::Center)
+            .title_top(self.title.clone())
+            .title_bottom("Press y or Enter to confirm, n or Esc to cancel")
+            .borders(Borders::ALL)
+            .border_style(style::BORDER_COLOR);
+        Paragraph::new(self.text.clone())
+            .block(b)
+            .centered()
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                self.confirmed = true;
+                self.should_be_closed = true;
+            }
+            KeyCode::Char('n') | KeyCode::Char('q') | KeyCode::Esc => {
+                self.should_be_closed = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn should_be_closed(&self) -> bool {
+        self.should_be_closed
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn size(&self) -> Size {
+        Size::new(60, 4)
+    }
+}
@@ -27,6 +27,7 @@ const POSSIBLE_DUE: [Due; 4] = [Due::NoDate, Due::Overdue, Due::Today, Due::Futu
 enum FilterBlock {
     State,
     Due,
+    Labels,
 }
 
 pub struct FilterWidget {
@@ -35,14 +36,21 @@ pub struct FilterWidget {
     filter: Filter,
     filter_state_state: ListState,
     filter_due_state: ListState,
+    filter_labels_state: ListState,
+    labels: Vec<String>,
     state_shortcut: Shortcut,
     due_shortcut: Shortcut,
+    labels_shortcut: Shortcut,
 }
 
 #[async_trait]
 impl AppBlockWidget for FilterWidget {
     fn activate_shortcuts(&mut self) -> Vec<&mut Shortcut> {
-        vec![&mut self.state_shortcut, &mut self.due_shortcut]
+        vec![
+            &mut self.state_shortcut,
+            &mut self.due_shortcut,
+            &mut self.labels_shortcut,
+        ]
     }
 
     fn set_active(&mut self, is_active: bool) {
@@ -53,6 +61,7 @@ impl AppBlockWidget for FilterWidget {
         match self.current_block {
             FilterBlock::State => self.filter_state_state.select_next(),
             FilterBlock::Due => self.filter_due_state.select_next(),
+            FilterBlock::Labels => self.filter_labels_state.select_next(),
         }
     }
 
@@ -60,6 +69,7 @@ impl AppBlockWidget for FilterWidget {
         match self.current_block {
             FilterBlock::State => self.filter_state_state.select_previous(),
             FilterBlock::Due => self.filter_due_state.select_previous(),
+            FilterBlock::Labels => self.filter_labels_state.select_previous(),
         }
     }
 
@@ -67,6 +77,7 @@ impl AppBlockWidget for FilterWidget {
         match self.current_block {
             FilterBlock::State => self.filter_state_state.select_first(),
             FilterBlock::Due => self.filter_due_state.select_first(),
+            FilterBlock::Labels => self.filter_labels_state.select_first(),
         }
     }
 
@@ -74,6 +85,7 @@ impl AppBlockWidget for FilterWidget {
         match self.current_block {
             FilterBlock::State => self.filter_state_state.select_last(),
             FilterBlock::Due => self.filter_due_state.select_last(),
+            FilterBlock::Labels => self.filter_labels_state.select_last(),
         }
     }
 }
@@ -86,8 +98,11 @@ impl FilterWidget {
             filter: f,
             filter_state_state: ListState::default(),
             filter_due_state: ListState::default(),
+            filter_labels_state: ListState::default(),
+            labels: Vec::new(),
             state_shortcut: Shortcut::new("Activate Filter->State block", &['g', 's']),
             due_shortcut: Shortcut::new("Activate Filter->Due block", &['g', 'd']),
+            labels_shortcut: Shortcut::new("Activate Filter->Labels block", &['g', 'l']),
         }));
 
         tokio::spawn({
@@ -116,13 +131,30 @@ impl FilterWidget {
                 }
             }
         });
+        tokio::spawn({
+            let s = s.clone();
+            async move {
+                let mut rx = s.read().await.labels_shortcut.subscribe_to_accepted();
+                loop {
+                    if rx.recv().await.is_err() {
+                        return;
+                    }
+
+                    s.write().await.current_block = FilterBlock::Labels;
+                }
+            }
+        });
         s
     }
 
     pub fn set_active(&mut self, is_active: bool, backward: bool) {
         self.is_active = is_active;
         if is_active {
-            self.current_block = if backward { FilterBlock::Due } else { FilterBlock::State };
+            self.current_block = if backward {
+                FilterBlock::Labels
+            } else {
+                FilterBlock::State
+            };
         }
     }
 
@@ -148,9 +180,26 @@ impl FilterWidget {
                     }
                 }
             }
+            FilterBlock::Labels => {
+                if let Some(label) = self.filter_labels_state.selected().and_then(|idx| self.labels.get(idx)) {
+                    if let Some(i) = self.filter.labels.iter().position(|l| l == label) {
+                        self.filter.labels.remove(i);
+                    } else {
+                        self.filter.labels.push(label.clone());
+                    }
+                }
+            }
         }
     }
 
+    pub fn set_available_labels(&mut self, labels: &[String]) {
+        let mut all_labels = labels.to_vec();
+        all_labels.extend(self.filter.labels.iter().cloned());
+        all_labels.sort();
+        all_labels.dedup();
+        self.labels = all_labels;
+    }
+
     pub fn filter(&self) -> Filter {
         self.filter.clone()
     }
@@ -161,7 +210,11 @@ impl FilterWidget {
                 self.current_block = FilterBlock::Due;
                 true
             }
-            FilterBlock::Due => false,
+            FilterBlock::Due => {
+                self.current_block = FilterBlock::Labels;
+                true
+            }
+            FilterBlock::Labels => false,
         }
     }
 
@@ -172,6 +225,10 @@ impl FilterWidget {
                 self.current_block = FilterBlock::State;
                 true
             }
+            FilterBlock::Labels => {
+                self.current_block = FilterBlock::Due;
+                true
+            }
         }
     }
 
@@ -214,19 +271,41 @@ impl FilterWidget {
             &mut self.filter_due_state,
         );
     }
+
+    fn render_filter_labels(&mut self, area: Rect, buf: &mut Buffer) {
+        let items = self
+            .labels
+            .iter()
+            .map(|l| {
+                let t = filter_element_to_text(l.clone(), &self.filter.labels);
+                ListItem::from(t)
+            })
+            .collect::<Vec<ListItem>>();
+
+        StatefulWidget::render(
+            list::List::new(&items, self.is_active && self.current_block == FilterBlock::Labels)
+                .title("Task labels")
+                .shortcut(&self.labels_shortcut)
+                .widget(),
+            area,
+            buf,
+            &mut self.filter_labels_state,
+        );
+    }
 }
 
 impl Widget for &mut FilterWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let [header_area, body_area] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
-        let [filter_state_area, filter_due_area] =
-            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(body_area);
+        let [filter_state_area, filter_due_area, filter_labels_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1), Constraint::Fill(1)]).areas(body_area);
 
         header::Header::new("Filter", self.is_active, None)
             .block()
             .render(header_area, buf);
         self.render_filter_state(filter_state_area, buf);
         self.render_filter_due(filter_due_area, buf);
+        self.render_filter_labels(filter_labels_area, buf);
     }
 }
 
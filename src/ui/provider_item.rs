@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: MIT
+
+use crate::filter::Filter;
+use crate::provider::Provider as ProviderTrait;
+use crate::task::{DateTimeUtc, Task as TaskTrait};
+use ratatui::style::Color;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Clone, PartialEq)]
+pub enum LoadingState {
+    Loading,
+    Loaded,
+    Failed(String),
+}
+
+pub struct LoadedTasks {
+    pub generation: u64,
+    pub provider: String,
+    pub result: Result<Vec<Box<dyn TaskTrait>>, String>,
+}
+
+pub struct ProviderItem {
+    pub provider: Arc<Mutex<Box<dyn ProviderTrait>>>,
+    pub state: LoadingState,
+    pub cached_at: Option<DateTimeUtc>,
+    name: String,
+    type_name: String,
+    color: Color,
+    completion_deletes_tasks: bool,
+    timeout: Duration,
+}
+
+impl ProviderItem {
+    pub fn new(p: Box<dyn ProviderTrait>, timeout: Duration) -> Self {
+        Self {
+            name: p.name(),
+            type_name: p.type_name(),
+            color: p.color(),
+            completion_deletes_tasks: p.completion_deletes_tasks(),
+            provider: Arc::new(Mutex::new(p)),
+            state: LoadingState::Loaded,
+            cached_at: None,
+            timeout,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    pub fn type_name(&self) -> String {
+        self.type_name.to_string()
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    pub fn completion_deletes_tasks(&self) -> bool {
+        self.completion_deletes_tasks
+    }
+
+    // Loads the tasks in the background and sends the result to the channel.
+    // The provider stays locked until the loading is finished or the timeout is expired.
+    pub fn load_tasks(&mut self, f: &Filter, reload: bool, generation: u64, tx: UnboundedSender<LoadedTasks>) {
+        self.state = LoadingState::Loading;
+
+        let provider = self.provider.clone();
+        let name = self.name();
+        let timeout = self.timeout;
+        let f = f.clone();
+
+        tokio::spawn(async move {
+            let mut p = provider.lock().await;
+            // A reload may hang on an unreachable provider as well, so it shares the timeout with the loading
+            let load = async {
+                if reload {
+                    p.reload().await;
+                }
+                p.tasks(None, &f).await
+            };
+
+            let result = match tokio::time::timeout(timeout, load).await {
+                Ok(Ok(tasks)) => Ok(tasks),
+                Ok(Err(e)) => Err(e.to_string()),
+                Err(_) => Err(format!("no response in {} seconds", timeout.as_secs())),
+            };
+
+            let _ = tx.send(LoadedTasks {
+                generation,
+                provider: name,
+                result,
+            });
+        });
+    }
+}
@@ -10,6 +10,10 @@ pub const OVERDUE_TASK_FG: Color = Color::LightRed;
 pub const TODAY_TASK_FG: Color = Color::White;
 pub const FUTURE_TASK_FG: Color = Color::LightGreen;
 pub const NO_DATE_TASK_FG: Color = TODAY_TASK_FG;
+pub const LABELS_FG: Color = Color::Cyan;
+pub const LOADING_PROVIDER_FG: Color = Color::Yellow;
+pub const FAILED_PROVIDER_FG: Color = Color::Red;
+pub const CACHED_PROVIDER_FG: Color = Color::DarkGray;
 pub const DESCRIPTION_KEY_COLOR: Color = Color::Blue;
 pub const DESCRIPTION_VALUE_COLOR: Color = Color::White;
 pub const NORMAL_ROW_BG: Color = SLATE.c950;
@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: MIT
+
+use super::dialog::DialogTrait;
+use super::style;
+use crate::provider::{DuePatch, NewTask, TaskPatch};
+use crate::task::{DateTimeUtc, Priority, Task as TaskTrait, date_from_str};
+use async_trait::async_trait;
+use clap::ValueEnum;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Rect, Size};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget};
+
+#[derive(Eq, PartialEq, Clone, Copy)]
+enum Field {
+    Text,
+    Description,
+    Due,
+    Priority,
+}
+
+const FIELD_ORDER: [Field; 4] = [Field::Text, Field::Description, Field::Due, Field::Priority];
+
+fn due_to_str(d: Option<DateTimeUtc>) -> String {
+    d.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default()
+}
+
+pub struct Dialog {
+    title: String,
+    task: Option<Box<dyn TaskTrait>>,
+    text: String,
+    description: String,
+    due: String,
+    priority: Priority,
+    current_field: Field,
+    error: Option<String>,
+    should_be_closed: bool,
+    accepted: bool,
+}
+
+impl Dialog {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            task: None,
+            text: String::new(),
+            description: String::new(),
+            due: String::new(),
+            priority: Priority::Normal,
+            current_field: Field::Text,
+            error: None,
+            should_be_closed: false,
+            accepted: false,
+        }
+    }
+
+    pub fn edit(title: &str, t: &dyn TaskTrait, pending: Option<&TaskPatch>) -> Self {
+        let text = pending.and_then(|p| p.text.clone()).unwrap_or(t.text());
+        let description = pending
+            .and_then(|p| p.description.clone())
+            .unwrap_or(t.description().unwrap_or_default());
+        let due = match pending.and_then(|p| p.due.clone()) {
+            Some(DuePatch::Date(d)) => due_to_str(Some(d)),
+            Some(DuePatch::NoDate) => String::new(),
+            None => due_to_str(t.due()),
+        };
+        let priority = pending.and_then(|p| p.priority.clone()).unwrap_or(t.priority());
+
+        Self {
+            task: Some(t.clone_boxed()),
+            text,
+            description,
+            due,
+            priority,
+            ..Self::new(title)
+        }
+    }
+
+    pub fn new_task(&self) -> Option<NewTask> {
+        if !self.accepted || self.task.is_some() || self.text.trim().is_empty() {
+            return None;
+        }
+
+        Some(NewTask {
+            text: self.text.trim().to_string(),
+            description: if self.description.is_empty() {
+                None
+            } else {
+                Some(self.description.clone())
+            },
+            due: date_from_str(self.due.as_str()).ok(),
+            priority: self.priority.clone(),
+        })
+    }
+
+    pub fn task_patch(&self) -> Option<TaskPatch> {
+        if !self.accepted {
+            return None;
+        }
+
+        let t = self.task.as_ref()?;
+        let mut patch = TaskPatch::new(t.clone_boxed());
+
+        let text = self.text.trim().to_string();
+        if text != t.text() {
+            patch.text = Some(text);
+        }
+
+        if self.description != t.description().unwrap_or_default() {
+            patch.description = Some(self.description.clone());
+        }
+
+        let due = date_from_str(self.due.as_str()).ok();
+        if due_to_str(due) != due_to_str(t.due()) {
+            patch.due = Some(match due {
+                Some(d) => DuePatch::Date(d),
+                None => DuePatch::NoDate,
+            });
+        }
+
+        if self.priority != t.priority() {
+            patch.priority = Some(self.priority.clone());
+        }
+
+        Some(patch)
+    }
+
+    fn current_text_mut(&mut self) -> Option<&mut String> {
+        match self.current_field {
+            Field::Text => Some(&mut self.text),
+            Field::Description => Some(&mut self.description),
+            Field::Due => Some(&mut self.due),
+            Field::Priority => None,
+        }
+    }
+
+    fn select_next_field(&mut self) {
+        let idx = FIELD_ORDER.iter().position(|f| *f == self.current_field).unwrap();
+        self.current_field = FIELD_ORDER[(idx + 1) % FIELD_ORDER.len()];
+    }
+
+    fn select_previous_field(&mut self) {
+        let idx = FIELD_ORDER.iter().position(|f| *f == self.current_field).unwrap();
+        self.current_field = FIELD_ORDER[(idx + FIELD_ORDER.len() - 1) % FIELD_ORDER.len()];
+    }
+
+    fn change_priority(&mut self, forward: bool) {
+        let priorities = Priority::value_variants();
+        let idx = priorities.iter().position(|p| *p == self.priority).unwrap_or_default();
+        let idx = if forward {
+            std::cmp::min(idx + 1, priorities.len() - 1)
+        } else {
+            idx.saturating_sub(1)
+        };
+        self.priority = priorities[idx].clone();
+    }
+
+    fn accept(&mut self) {
+        if self.text.trim().is_empty() {
+            self.error = Some("The text shouldn't be empty".to_string());
+            return;
+        }
+
+        if !self.due.is_empty() {
+            if let Err(e) = date_from_str(self.due.as_str()) {
+                self.error = Some(e);
+                return;
+            }
+        }
+
+        self.accepted = true;
+        self.should_be_closed = true;
+    }
+
+    fn field_line(&self, field: Field, name: &str, value: String) -> Line<'_> {
+        let label_style = if field == self.current_field {
+            Style::new()
+                .fg(style::DESCRIPTION_KEY_COLOR)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::new().fg(style::DESCRIPTION_KEY_COLOR)
+        };
+        let cursor = if field == self.current_field && field != Field::Priority {
+            "_"
+        } else {
+            ""
+        };
+
+        Line::from(vec![
+            Span::styled(format!("{name}: "), label_style),
+            Span::styled(format!("{value}{cursor}"), style::DESCRIPTION_VALUE_COLOR),
+        ])
+    }
+}
+
+#[async_trait]
+impl DialogTrait for Dialog {
+    async fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let b = Block::default()
+            .title_alignment(ratatui::layout::Alignment::Center)
+            .title_top(self.title.clone())
+            .title_bottom("Tab to move between fields, ←→ to change priority, Enter to apply")
+            .borders(Borders::ALL)
+            .border_style(style::BORDER_COLOR);
+
+        let mut lines = vec![
+            self.field_line(Field::Text, "Text", self.text.clone()),
+            self.field_line(Field::Description, "Description", self.description.clone()),
+            self.field_line(Field::Due, "Due (YYYY-MM-DD, today, tomorrow)", self.due.clone()),
+            self.field_line(Field::Priority, "Priority", format!("< {} >", self.priority)),
+        ];
+
+        if let Some(e) = &self.error {
+            lines.push(Line::styled(e.clone(), style::OVERDUE_TASK_FG));
+        }
+
+        Paragraph::new(lines).block(b).render(area, buf);
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.should_be_closed = true;
+            }
+            KeyCode::Tab | KeyCode::Down => self.select_next_field(),
+            KeyCode::BackTab | KeyCode::Up => self.select_previous_field(),
+            KeyCode::Left if self.current_field == Field::Priority => self.change_priority(false),
+            KeyCode::Right if self.current_field == Field::Priority => self.change_priority(true),
+            KeyCode::Char(ch) => {
+                if let Some(t) = self.current_text_mut() {
+                    t.push(ch);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(t) = self.current_text_mut() {
+                    t.pop();
+                }
+            }
+            KeyCode::Enter => self.accept(),
+            _ => {}
+        }
+    }
+
+    fn should_be_closed(&self) -> bool {
+        self.should_be_closed
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn size(&self) -> Size {
+        Size::new(80, 7)
+    }
+}
@@ -74,6 +74,12 @@ impl Widget for &mut TaskInfoWidget {
                 text.push(styled_line("Due", &due));
             }
 
+            let recurrence;
+            if let Some(r) = t.recurrence() {
+                recurrence = r;
+                text.push(styled_line("Repeat", recurrence.as_str()));
+            }
+
             let completed_at;
             if t.completed_at().is_some() {
                 completed_at = task::datetime_to_str(t.completed_at(), &tz);
@@ -83,6 +89,11 @@ impl Widget for &mut TaskInfoWidget {
             let priority = t.priority().to_string();
             text.push(styled_line("Priority", priority.as_str()));
 
+            let labels = t.labels().join(", ");
+            if !labels.is_empty() {
+                text.push(styled_line("Labels", labels.as_str()));
+            }
+
             let description;
             if let Some(d) = t.description() {
                 if !d.is_empty() {
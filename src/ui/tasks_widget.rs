@@ -1,9 +1,9 @@
 // SPDX-License-Identifier: MIT
 
 use super::AppBlockWidget;
-use crate::filter::Filter;
+use super::provider_item::ProviderItem;
 use crate::project::Project as ProjectTrait;
-use crate::provider::{Provider as ProviderTrait, TaskPatch};
+use crate::provider::{DuePatch, TaskPatch};
 use crate::state::StatefulObject;
 use crate::task;
 use crate::task::{State, Task as TaskTrait, due_group, equal};
@@ -13,12 +13,13 @@ use async_trait::async_trait;
 use chrono::Local;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{ListItem, ListState};
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
-use std::slice::IterMut;
+use std::slice::Iter;
 
 use super::shortcut::Shortcut;
 
@@ -27,12 +28,59 @@ struct ChangedState {
     new_state: State,
 }
 
+struct TreeNode {
+    depth: usize,
+    children_count: usize,
+}
+
+fn task_key(t: &dyn TaskTrait) -> String {
+    format!("{}:{}", t.provider(), t.id())
+}
+
+fn append_subtree(
+    t: &dyn TaskTrait,
+    depth: usize,
+    children: &HashMap<String, Vec<&dyn TaskTrait>>,
+    collapsed: &[String],
+    items: &mut Vec<Box<dyn TaskTrait>>,
+    nodes: &mut HashMap<String, TreeNode>,
+) {
+    let key = task_key(t);
+    if nodes.contains_key(&key) {
+        return;
+    }
+
+    let subtasks = children.get(&key).map(|c| c.as_slice()).unwrap_or_default();
+    nodes.insert(
+        key.clone(),
+        TreeNode {
+            depth,
+            children_count: subtasks.len(),
+        },
+    );
+    items.push(t.clone_boxed());
+
+    if collapsed.contains(&key) {
+        return;
+    }
+
+    for c in subtasks {
+        append_subtree(*c, depth + 1, children, collapsed, items, nodes);
+    }
+}
+
 pub struct TasksWidget {
     all_tasks: Vec<Box<dyn TaskTrait>>,
     changed_state_tasks: Vec<ChangedState>,
+    edited_tasks: Vec<TaskPatch>,
+    deleted_tasks: Vec<Box<dyn TaskTrait>>,
     tasks: SelectableList<Box<dyn TaskTrait>>,
     providers_filter: Vec<String>,
     projects_filter: Vec<String>,
+    labels_filter: Vec<String>,
+    collapsed_tasks: Vec<String>,
+    tree: HashMap<String, TreeNode>,
+    cached_providers: Vec<String>,
 }
 
 impl Default for TasksWidget {
@@ -40,11 +88,17 @@ impl Default for TasksWidget {
         Self {
             all_tasks: Vec::new(),
             changed_state_tasks: Vec::new(),
+            edited_tasks: Vec::new(),
+            deleted_tasks: Vec::new(),
             tasks: SelectableList::default()
                 .shortcut(Shortcut::new("Activate Tasks block", &['g', 't']))
                 .show_count_in_title(false),
             projects_filter: Vec::new(),
             providers_filter: Vec::new(),
+            labels_filter: Vec::new(),
+            collapsed_tasks: Vec::new(),
+            tree: HashMap::new(),
+            cached_providers: Vec::new(),
         }
     }
 }
@@ -87,45 +141,92 @@ impl TasksWidget {
         self.filter_tasks();
     }
 
+    pub fn set_labels_filter(&mut self, labels: &[String]) {
+        self.labels_filter = labels.to_vec();
+        self.filter_tasks();
+    }
+
     fn filter_tasks(&mut self) {
-        self.tasks.set_items(
-            self.all_tasks
-                .iter()
-                .filter(|t| {
-                    let mut result = true;
-                    if !self.providers_filter.is_empty() && !self.providers_filter.contains(&t.provider()) {
+        let tasks = self
+            .all_tasks
+            .iter()
+            .filter(|t| {
+                let mut result = true;
+                if !self.providers_filter.is_empty() && !self.providers_filter.contains(&t.provider()) {
+                    result = false;
+                }
+                if let Some(tp) = t.project() {
+                    if !self.projects_filter.is_empty() && !self.projects_filter.contains(&tp.name()) {
                         result = false;
                     }
-                    if let Some(tp) = t.project() {
-                        if !self.projects_filter.is_empty() && !self.projects_filter.contains(&tp.name()) {
-                            result = false;
-                        }
-                    }
-                    result
-                })
-                .map(|t| t.clone_boxed())
-                .collect(),
-        );
-        let state = if self.all_tasks.is_empty() {
+                }
+                if !self.labels_filter.is_empty() && !t.labels().iter().any(|l| self.labels_filter.contains(l)) {
+                    result = false;
+                }
+                result
+            })
+            .map(|t| t.as_ref())
+            .collect::<Vec<&dyn TaskTrait>>();
+
+        // Subtasks are shown under their parents keeping the sort order within each level,
+        // a task whose parent is filtered out becomes a top level one.
+        let keys = tasks.iter().map(|t| task_key(*t)).collect::<HashSet<String>>();
+        let mut children: HashMap<String, Vec<&dyn TaskTrait>> = HashMap::new();
+        let mut roots = Vec::new();
+        for t in &tasks {
+            match t.parent_id().map(|id| format!("{}:{id}", t.provider())) {
+                Some(parent_key) if keys.contains(&parent_key) => children.entry(parent_key).or_default().push(*t),
+                _ => roots.push(*t),
+            }
+        }
+
+        let mut items = Vec::new();
+        let mut nodes = HashMap::new();
+        for t in roots {
+            append_subtree(t, 0, &children, &self.collapsed_tasks, &mut items, &mut nodes);
+        }
+
+        // Keep the selected task when tasks are loaded or the tree is changed
+        let selected_key = self.tasks.selected().map(|t| task_key(t.as_ref()));
+        let selected_pos = selected_key.and_then(|k| items.iter().position(|t| task_key(t.as_ref()) == k));
+
+        self.tree = nodes;
+        self.tasks.set_items(items);
+        let count = self.tasks.len();
+        let state = if count == 0 {
             ListState::default()
         } else {
             let selected_idx = self
                 .tasks
                 .state()
                 .selected()
-                .map(|i| {
-                    if i >= self.all_tasks.len() {
-                        self.all_tasks.len() - 1
-                    } else {
-                        i
-                    }
-                })
+                .map(|i| selected_pos.unwrap_or(if i >= count { count - 1 } else { i }))
                 .unwrap_or_else(|| 0);
             ListState::default().with_selected(Some(selected_idx))
         };
         self.tasks.set_state(state);
     }
 
+    pub fn toggle_collapsed(&mut self) {
+        let key = match self.tasks.selected() {
+            Some(t) => task_key(t.as_ref()),
+            None => return,
+        };
+
+        if self.tree.get(&key).is_none_or(|n| n.children_count == 0) {
+            return;
+        }
+
+        match self.collapsed_tasks.iter().position(|k| *k == key) {
+            Some(p) => {
+                self.collapsed_tasks.remove(p);
+            }
+            None => self.collapsed_tasks.push(key),
+        }
+
+        self.filter_tasks();
+    }
+
     pub fn tasks_projects(&self) -> Vec<Box<dyn ProjectTrait>> {
         let mut projects: Vec<Box<dyn ProjectTrait>> = Vec::new();
 
@@ -143,6 +244,13 @@ impl TasksWidget {
         projects
     }
 
+    pub fn tasks_labels(&self) -> Vec<String> {
+        let mut labels = self.all_tasks.iter().flat_map(|t| t.labels()).collect::<Vec<String>>();
+        labels.sort();
+        labels.dedup();
+        labels
+    }
+
     pub fn selected_task(&self) -> Option<Box<dyn TaskTrait>> {
         if let Some(t) = self.tasks.selected() {
             Some(t.clone_boxed())
@@ -152,39 +260,115 @@ impl TasksWidget {
     }
 
     pub fn has_changes(&self) -> bool {
-        !self.changed_state_tasks.is_empty()
+        !self.changed_state_tasks.is_empty() || !self.edited_tasks.is_empty() || !self.deleted_tasks.is_empty()
+    }
+
+    pub fn deleted_count(&self) -> usize {
+        self.deleted_tasks.len()
+    }
+
+    pub fn completed_count(&self, provider: &str) -> usize {
+        self.changed_state_tasks
+            .iter()
+            .filter(|c| c.new_state == task::State::Completed && c.task.provider() == provider)
+            .count()
     }
 
-    pub async fn commit_changes(&mut self, providers: &mut IterMut<'_, Box<dyn ProviderTrait>>) -> Vec<Box<dyn Error>> {
+    fn uncommitted_count(&self) -> usize {
+        self.deleted_tasks.len()
+            + self.changed_state_tasks.len()
+            + self
+                .edited_tasks
+                .iter()
+                .filter(|e| {
+                    !self
+                        .changed_state_tasks
+                        .iter()
+                        .any(|c| equal(c.task.as_ref(), e.task.as_ref()))
+                })
+                .count()
+    }
+
+    pub fn task_patch(&self, t: &dyn TaskTrait) -> Option<TaskPatch> {
+        self.edited_tasks.iter().find(|p| equal(p.task.as_ref(), t)).cloned()
+    }
+
+    pub fn set_task_patch(&mut self, patch: TaskPatch) {
+        self.deleted_tasks.retain(|t| !equal(t.as_ref(), patch.task.as_ref()));
+        self.edited_tasks
+            .retain(|p| !equal(p.task.as_ref(), patch.task.as_ref()));
+        if !patch.is_empty() {
+            self.edited_tasks.push(patch);
+        }
+    }
+
+    pub async fn commit_changes(&mut self, providers: Iter<'_, ProviderItem>) -> Vec<Box<dyn Error>> {
         let mut result = Vec::new();
 
         for p in providers {
             let name = p.name();
-            let patches = self
-                .changed_state_tasks
+            let mut patches = self
+                .edited_tasks
                 .iter()
-                .filter(|c| c.task.provider() == name)
-                .map(|c| TaskPatch {
-                    task: c.task.clone_boxed(),
-                    state: Some(c.new_state.clone()),
-                })
+                .filter(|p| p.task.provider() == name)
+                .cloned()
                 .collect::<Vec<TaskPatch>>();
 
-            if !patches.is_empty() {
-                let errors = p.patch_tasks(&patches).await;
-                result.extend(errors.iter().map(|e| {
-                    Box::<dyn Error>::from(format!("Provider {name} returns error when changing the task: {e}"))
-                }));
+            for t in self.deleted_tasks.iter().filter(|t| t.provider() == name) {
+                patches.push(TaskPatch {
+                    delete: true,
+                    ..TaskPatch::new(t.clone_boxed())
+                });
+            }
 
-                for p in patches {
-                    if !errors.iter().any(|pe| equal(p.task.as_ref(), pe.task.as_ref())) {
-                        self.changed_state_tasks
-                            .retain(|c| !equal(c.task.as_ref(), p.task.as_ref()));
-                    }
+            for c in self.changed_state_tasks.iter().filter(|c| c.task.provider() == name) {
+                match patches.iter_mut().find(|p| equal(p.task.as_ref(), c.task.as_ref())) {
+                    Some(p) => p.state = Some(c.new_state.clone()),
+                    None => patches.push(TaskPatch {
+                        state: Some(c.new_state.clone()),
+                        ..TaskPatch::new(c.task.clone_boxed())
+                    }),
                 }
+            }
+
+            if patches.is_empty() {
+                continue;
+            }
+
+            if self.cached_providers.contains(&name) {
+                result.push(Box::<dyn Error>::from(format!(
+                    "Provider {name} shows cached tasks, the changes can be committed after it's loaded"
+                )));
+                continue;
+            }
 
-                p.reload().await;
+            // A loading provider stays locked until its timeout, waiting for it would freeze the UI
+            let mut p = match p.provider.try_lock() {
+                Ok(p) => p,
+                Err(_) => {
+                    result.push(Box::<dyn Error>::from(format!(
+                        "Provider {name} is busy with loading, the changes can be committed after it's loaded"
+                    )));
+                    continue;
+                }
+            };
+            let errors = p.patch_tasks(&patches).await;
+            result.extend(
+                errors.iter().map(|e| {
+                    Box::<dyn Error>::from(format!("Provider {name} returns error when changing the task: {e}"))
+                }),
+            );
+
+            for p in patches {
+                if !errors.iter().any(|pe| equal(p.task.as_ref(), pe.task.as_ref())) {
+                    self.changed_state_tasks
+                        .retain(|c| !equal(c.task.as_ref(), p.task.as_ref()));
+                    self.edited_tasks.retain(|e| !equal(e.task.as_ref(), p.task.as_ref()));
+                    self.deleted_tasks.retain(|t| !equal(t.as_ref(), p.task.as_ref()));
+                }
             }
+
+            p.reload().await;
         }
 
         result
@@ -198,6 +382,8 @@ impl TasksWidget {
 
         let t = selected.unwrap();
 
+        self.deleted_tasks.retain(|d| !equal(d.as_ref(), t.as_ref()));
+
         match self
             .changed_state_tasks
             .iter()
@@ -222,23 +408,54 @@ impl TasksWidget {
         Ok(())
     }
 
+    pub fn toggle_deletion(&mut self) {
+        let t = match self.tasks.selected() {
+            Some(t) => t.clone_boxed(),
+            None => return,
+        };
+
+        match self.deleted_tasks.iter().position(|d| equal(d.as_ref(), t.as_ref())) {
+            Some(p) => {
+                self.deleted_tasks.remove(p);
+            }
+            None => {
+                self.changed_state_tasks.retain(|c| !equal(c.task.as_ref(), t.as_ref()));
+                self.edited_tasks.retain(|e| !equal(e.task.as_ref(), t.as_ref()));
+                self.deleted_tasks.push(t);
+            }
+        }
+    }
+
     pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
         let changed = &self.changed_state_tasks;
+        let edited = &self.edited_tasks;
+        let deleted = &self.deleted_tasks;
+        let tree = &self.tree;
+        let collapsed = &self.collapsed_tasks;
+        let cached_providers = &self.cached_providers;
         let mut title = format!("Tasks ({})", self.tasks.len());
         let tz = Local::now().timezone();
 
-        if !changed.is_empty() {
+        if self.has_changes() {
             title = format!(
                 "{title} (uncommitted count {}, use 'c'+'c' to commit them)",
-                changed.len()
+                self.uncommitted_count()
             )
         }
 
         self.tasks.render(
             title.as_str(),
             |t| {
+                let patch = edited.iter().find(|p| equal(p.task.as_ref(), t.as_ref()));
+                let text = patch.and_then(|p| p.text.clone()).unwrap_or(t.text());
+                let priority = patch.and_then(|p| p.priority.clone()).unwrap_or(t.priority());
+                let due = match patch.and_then(|p| p.due.clone()) {
+                    Some(DuePatch::Date(d)) => Some(d),
+                    Some(DuePatch::NoDate) => None,
+                    None => t.due(),
+                };
                 let fg_color = {
-                    match t.due() {
+                    match due {
                         Some(d) => {
                             let now = chrono::Utc::now().date_naive();
                             match d.date_naive().cmp(&now) {
@@ -252,31 +469,74 @@ impl TasksWidget {
                 };
                 let (state, uncommitted) = match changed.iter().find(|c| equal(c.task.as_ref(), t.as_ref())) {
                     Some(c) => (c.new_state.clone(), true),
-                    None => (t.state(), false),
+                    None => (t.state(), patch.is_some()),
+                };
+                let is_deleted = deleted.iter().any(|d| equal(d.as_ref(), t.as_ref()));
+                let text_style = if is_deleted {
+                    Style::default().fg(fg_color).add_modifier(Modifier::CROSSED_OUT)
+                } else {
+                    Style::default().fg(fg_color)
+                };
+                let key = task_key(t.as_ref());
+                let tree_prefix = match tree.get(&key) {
+                    Some(n) => {
+                        let marker = if n.children_count == 0 {
+                            ""
+                        } else if collapsed.contains(&key) {
+                            "▸ "
+                        } else {
+                            "▾ "
+                        };
+                        format!("{}{marker}", "  ".repeat(n.depth))
+                    }
+                    None => String::new(),
                 };
                 let mut lines = vec![
+                    Span::from(tree_prefix),
                     Span::from(format!("[{state}] ")),
-                    Span::styled(t.text(), Style::default().fg(fg_color)),
+                    Span::styled(text, text_style),
                     Span::from(" ("),
                     Span::styled(
-                        format!("due: {}", task::datetime_to_str(t.due(), &tz)),
+                        format!("due: {}", task::datetime_to_str(due, &tz)),
                         Style::default().fg(Color::Blue),
                     ),
                     Span::from(") ("),
-                    Span::styled(
-                        format!("Priority: {}", t.priority()),
-                        style::priority_color(&t.priority()),
-                    ),
+                    Span::styled(format!("Priority: {priority}"), style::priority_color(&priority)),
                     Span::from(") ("),
                     Span::styled(t.place(), Style::default().fg(Color::Yellow)),
                     Span::from(")"),
                 ];
 
+                let labels = t.labels();
+                if !labels.is_empty() {
+                    lines.push(Span::styled(
+                        format!(
+                            " {}",
+                            labels
+                                .iter()
+                                .map(|l| format!("#{l}"))
+                                .collect::<Vec<String>>()
+                                .join(" ")
+                        ),
+                        style::LABELS_FG,
+                    ));
+                }
+
                 if !t.description().unwrap_or_default().is_empty() {
                     lines.push(Span::from(" 💬"));
                 }
 
-                if uncommitted {
+                if t.recurrence().is_some() {
+                    lines.push(Span::from(" 🔁"));
+                }
+
+                if cached_providers.contains(&t.provider()) {
+                    lines.push(Span::from(" 🕓"));
+                }
+
+                if is_deleted {
+                    lines.push(Span::from(" 🗑"));
+                } else if uncommitted {
                     lines.push(Span::from(" 📤"));
                 }
 
@@ -294,43 +554,56 @@ impl TasksWidget {
                 .find(|t| equal(t.as_ref(), c.task.as_ref()))
                 .is_some_and(|t| t.state() == c.task.state())
         });
+        self.edited_tasks.retain(|e| {
+            self.all_tasks
+                .iter()
+                .find(|t| equal(t.as_ref(), e.task.as_ref()))
+                .is_some_and(|t| t.state() == e.task.state())
+        });
+        self.deleted_tasks
+            .retain(|d| self.all_tasks.iter().any(|t| equal(t.as_ref(), d.as_ref())));
     }
 
-    pub async fn load_tasks(
-        &mut self,
-        providers: &mut IterMut<'_, Box<dyn ProviderTrait>>,
-        f: &Filter,
-    ) -> Vec<Box<dyn Error>> {
-        let mut all_tasks: Vec<Box<dyn task::Task>> = Vec::new();
+    // The changes could be made with the cached tasks, so point them to the loaded ones
+    fn refresh_changed_tasks(&mut self) {
+        let actual = |t: &dyn TaskTrait| {
+            self.all_tasks
+                .iter()
+                .find(|a| equal(a.as_ref(), t))
+                .map(|a| a.clone_boxed())
+                .unwrap_or(t.clone_boxed())
+        };
 
-        let mut errors = Vec::new();
+        for c in self.changed_state_tasks.iter_mut() {
+            c.task = actual(c.task.as_ref());
+        }
+        for e in self.edited_tasks.iter_mut() {
+            e.task = actual(e.task.as_ref());
+        }
+        for d in self.deleted_tasks.iter_mut() {
+            *d = actual(d.as_ref());
+        }
+    }
 
-        for p in providers {
-            let tasks = p.tasks(None, f).await;
+    pub fn set_provider_tasks(&mut self, provider: &str, tasks: Vec<Box<dyn TaskTrait>>, is_cached: bool) {
+        self.all_tasks.retain(|t| t.provider() != provider);
+        self.all_tasks.extend(tasks);
 
-            match tasks {
-                Ok(t) => all_tasks.append(&mut t.iter().map(|t| t.clone_boxed()).collect::<Vec<Box<dyn TaskTrait>>>()),
-                Err(err) => errors.push((p.name(), err)),
-            }
+        self.cached_providers.retain(|p| p != provider);
+        if is_cached {
+            self.cached_providers.push(provider.to_string());
         }
 
-        all_tasks.sort_by(|l, r| {
+        self.all_tasks.sort_by(|l, r| {
             due_group(l.as_ref())
                 .cmp(&due_group(r.as_ref()))
                 .then_with(|| r.priority().cmp(&l.priority()))
                 .then_with(|| l.due().cmp(&r.due()))
         });
 
-        self.all_tasks = all_tasks;
         self.remove_changed_tasks_that_are_not_exists_anymore();
+        self.refresh_changed_tasks();
         self.filter_tasks();
-
-        errors
-            .iter()
-            .map(|(provider_name, err)| {
-                Box::<dyn Error>::from(format!("Load provider {provider_name} projects failure: {err}"))
-            })
-            .collect()
     }
 }
 
@@ -47,10 +47,8 @@ impl DialogTrait for Dialog {
                 self.should_be_closed = true;
                 self.text.clear();
             }
-            KeyCode::Char(ch) => {
-                if self.input_re.is_match(format!("{}{ch}", self.text).as_str()) {
-                    self.text.push(ch);
-                }
+            KeyCode::Char(ch) if self.input_re.is_match(format!("{}{ch}", self.text).as_str()) => {
+                self.text.push(ch);
             }
             KeyCode::Backspace => {
                 self.text.pop();
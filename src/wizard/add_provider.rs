@@ -1,19 +1,47 @@
 // SPDX-License-Identifier: MIT
 
+use crate::caldav;
+use crate::code_todo;
+use crate::external;
+use crate::gitea;
 use crate::github_issues;
+use crate::github_notifications;
+use crate::github_projects;
+use crate::gitlab_issues;
 use crate::gitlab_todo;
+use crate::jira;
+use crate::linear;
+use crate::logseq;
+use crate::markdown;
 use crate::obsidian;
+use crate::orgmode;
 use crate::settings;
+use crate::taskwarrior;
 use crate::todoist;
+use crate::todotxt;
 use std::collections::HashMap;
 use std::io::{self, Write};
 use std::path;
 
 pub const AVAILABLE_PROVIDERS: &[&str] = &[
     obsidian::PROVIDER_NAME,
+    markdown::PROVIDER_NAME,
+    code_todo::PROVIDER_NAME,
+    orgmode::PROVIDER_NAME,
+    logseq::PROVIDER_NAME,
     todoist::PROVIDER_NAME,
     gitlab_todo::PROVIDER_NAME,
+    gitlab_issues::PROVIDER_NAME,
     github_issues::PROVIDER_NAME,
+    github_notifications::PROVIDER_NAME,
+    github_projects::PROVIDER_NAME,
+    gitea::PROVIDER_NAME,
+    jira::PROVIDER_NAME,
+    linear::PROVIDER_NAME,
+    caldav::PROVIDER_NAME,
+    todotxt::PROVIDER_NAME,
+    taskwarrior::PROVIDER_NAME,
+    external::PROVIDER_NAME,
 ];
 
 pub struct AddProvider {}
@@ -49,9 +77,23 @@ impl AddProvider {
 
                 let mut provider_cfg = match provider {
                     obsidian::PROVIDER_NAME => self.add_obsidian()?,
+                    markdown::PROVIDER_NAME => self.add_markdown()?,
+                    code_todo::PROVIDER_NAME => self.add_code_todo()?,
+                    orgmode::PROVIDER_NAME => self.add_orgmode()?,
+                    logseq::PROVIDER_NAME => self.add_logseq()?,
                     todoist::PROVIDER_NAME => self.add_todoist()?,
                     gitlab_todo::PROVIDER_NAME => self.add_gitlab_todo()?,
+                    gitlab_issues::PROVIDER_NAME => self.add_gitlab_todo()?,
                     github_issues::PROVIDER_NAME => self.add_github_issues()?,
+                    github_notifications::PROVIDER_NAME => self.add_github_notifications()?,
+                    github_projects::PROVIDER_NAME => self.add_github_projects()?,
+                    gitea::PROVIDER_NAME => self.add_gitea()?,
+                    jira::PROVIDER_NAME => self.add_jira()?,
+                    linear::PROVIDER_NAME => self.add_linear()?,
+                    caldav::PROVIDER_NAME => self.add_caldav()?,
+                    todotxt::PROVIDER_NAME => self.add_todotxt()?,
+                    taskwarrior::PROVIDER_NAME => self.add_taskwarrior()?,
+                    external::PROVIDER_NAME => self.add_external()?,
                     _ => panic!("Unknown provider {provider}"),
                 };
                 provider_cfg.insert("type".to_string(), provider.to_string());
@@ -83,6 +125,99 @@ impl AddProvider {
         Ok(HashMap::from([("path".to_string(), input_line)]))
     }
 
+    fn add_markdown(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a path to the directory with markdown files> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let path = input_line.trim().to_string();
+
+        if !path::Path::new(&path).is_dir() {
+            return Err(Box::<dyn std::error::Error>::from("The directory doesn't exist"));
+        }
+
+        let mut result = HashMap::from([("path".to_string(), path)]);
+
+        for (key, question) in [
+            (
+                "include",
+                format!(
+                    "Comma separated globs of the files to scan (leave empty for {})",
+                    markdown::DEFAULT_INCLUDE
+                ),
+            ),
+            (
+                "exclude",
+                "Comma separated globs of the files to skip (leave empty to scan all of them)".to_string(),
+            ),
+        ] {
+            print!("{question}> ");
+            let _ = io::stdout().flush();
+
+            let mut input_line = String::new();
+
+            io::stdin().read_line(&mut input_line).expect("Failed to read line");
+            let globs = input_line.trim().to_string();
+            if !globs.is_empty() {
+                result.insert(key.to_string(), globs);
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn add_code_todo(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a path to the source tree> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        input_line = input_line.trim().to_string();
+
+        if !path::Path::new(&input_line).is_dir() {
+            return Err(Box::<dyn std::error::Error>::from("The directory doesn't exist"));
+        }
+
+        Ok(HashMap::from([("path".to_string(), input_line)]))
+    }
+
+    fn add_orgmode(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a path to the directory with .org files> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        input_line = input_line.trim().to_string();
+
+        if !path::Path::new(&input_line).is_dir() {
+            return Err(Box::<dyn std::error::Error>::from("The directory doesn't exist"));
+        }
+
+        Ok(HashMap::from([("path".to_string(), input_line)]))
+    }
+
+    fn add_logseq(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a path to the graph> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        input_line = input_line.trim().to_string();
+
+        let p = path::Path::new(&input_line).join("logseq").join("config.edn");
+        if !p.exists() {
+            println!("The path doesn't contain a file logseq/config.edn");
+            return Err(Box::<dyn std::error::Error>::from("Wrong graph path"));
+        }
+
+        Ok(HashMap::from([("path".to_string(), input_line)]))
+    }
+
     fn add_todoist(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
         print!("Please, provide an api key> ");
         let _ = io::stdout().flush();
@@ -119,7 +254,7 @@ impl AddProvider {
     }
 
     fn add_github_issues(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
-        print!("Please, provide a repository (aka panter-dsd/tatuin)> ");
+        print!("Please, provide repositories or owners separated by commas (aka panter-dsd/tatuin, acme)> ");
         let _ = io::stdout().flush();
 
         let mut input_line = String::new();
@@ -127,6 +262,20 @@ impl AddProvider {
         io::stdin().read_line(&mut input_line).expect("Failed to read line");
         let repository = input_line.trim().to_string();
 
+        print!("Please, provide an issue search query (aka is:open assignee:@me, leave empty to skip)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let query = input_line.trim().to_string();
+
+        if repository.is_empty() && query.is_empty() {
+            return Err(Box::<dyn std::error::Error>::from(
+                "Either repositories or a query should be provided",
+            ));
+        }
+
         print!("Please, provide an api key> ");
         let _ = io::stdout().flush();
 
@@ -135,12 +284,290 @@ impl AddProvider {
         io::stdin().read_line(&mut input_line).expect("Failed to read line");
         let api_key = input_line.trim().to_string();
 
+        print!("Please, provide a label to mark issues in progress (leave empty to skip)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let in_progress_label = input_line.trim().to_string();
+
+        print!("Should pull requests be excluded (y/N)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let exclude_pull_requests = input_line.trim().eq_ignore_ascii_case("y");
+
+        let mut result = HashMap::from([
+            ("api_key".to_string(), api_key),
+            ("exclude_pull_requests".to_string(), exclude_pull_requests.to_string()),
+        ]);
+        if !repository.is_empty() {
+            result.insert("repository".to_string(), repository);
+        }
+        if !query.is_empty() {
+            result.insert("query".to_string(), query);
+        }
+        if !in_progress_label.is_empty() {
+            result.insert("in_progress_label".to_string(), in_progress_label);
+        }
+
+        Ok(result)
+    }
+
+    fn add_github_notifications(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide an api key with the notifications and repo scopes> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let api_key = input_line.trim().to_string();
+
+        print!("Should completed notifications be marked as done instead of read (y/N)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let mark_as_done = input_line.trim().eq_ignore_ascii_case("y");
+
+        Ok(HashMap::from([
+            ("api_key".to_string(), api_key),
+            ("mark_as_done".to_string(), mark_as_done.to_string()),
+        ]))
+    }
+
+    fn add_github_projects(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a user or an organization of the project (aka panter-dsd)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let owner = input_line.trim().to_string();
+
+        print!("Please, provide a number of the project (aka 1 for https://github.com/users/panter-dsd/projects/1)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let number = input_line.trim().to_string();
+
+        if number.parse::<i64>().is_err() {
+            return Err(Box::<dyn std::error::Error>::from("The number should be an integer"));
+        }
+
+        print!("Please, provide an api key with the project scope> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let api_key = input_line.trim().to_string();
+
+        print!("Please, provide a column of the in progress cards (leave empty for In Progress)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let in_progress_column = input_line.trim().to_string();
+
+        let mut result = HashMap::from([
+            ("owner".to_string(), owner),
+            ("number".to_string(), number),
+            ("api_key".to_string(), api_key),
+        ]);
+        if !in_progress_column.is_empty() {
+            result.insert("in_progress_columns".to_string(), in_progress_column);
+        }
+
+        Ok(result)
+    }
+
+    fn add_gitea(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a base url (aka https://codeberg.org)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let base_url = input_line.trim().to_string();
+
+        print!("Please, provide an access token> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let api_key = input_line.trim().to_string();
+
         Ok(HashMap::from([
-            ("repository".to_string(), repository),
+            ("base_url".to_string(), base_url),
             ("api_key".to_string(), api_key),
         ]))
     }
 
+    fn add_jira(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a base url (aka https://example.atlassian.net)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let base_url = input_line.trim().to_string();
+
+        if base_url.is_empty() {
+            return Err(Box::<dyn std::error::Error>::from("The url shouldn't be empty"));
+        }
+
+        print!("Please, provide an email of the Jira Cloud account (leave empty for a personal access token)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let username = input_line.trim().to_string();
+
+        print!("Please, provide an api token> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let api_key = input_line.trim().to_string();
+
+        let mut result = HashMap::from([("base_url".to_string(), base_url), ("api_key".to_string(), api_key)]);
+        if !username.is_empty() {
+            result.insert("username".to_string(), username);
+        }
+
+        Ok(result)
+    }
+
+    fn add_linear(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a personal api key (Settings -> Security & access)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        input_line = input_line.trim().to_string();
+
+        Ok(HashMap::from([("api_key".to_string(), input_line)]))
+    }
+
+    fn add_caldav(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a calendar or server url (aka https://cloud.example.com/remote.php/dav)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let url = input_line.trim().to_string();
+
+        if url.is_empty() {
+            return Err(Box::<dyn std::error::Error>::from("The url shouldn't be empty"));
+        }
+
+        print!("Please, provide a username> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let username = input_line.trim().to_string();
+
+        print!("Please, provide a password (an app password is recommended)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let password = input_line.trim().to_string();
+
+        Ok(HashMap::from([
+            ("url".to_string(), url),
+            ("username".to_string(), username),
+            ("password".to_string(), password),
+        ]))
+    }
+
+    fn add_todotxt(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a path to the todo.txt file> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let path = input_line.trim().to_string();
+
+        if !path::Path::new(&path).is_file() {
+            return Err(Box::<dyn std::error::Error>::from("The file doesn't exist"));
+        }
+
+        print!("Move completed tasks to done.txt next to it? (y/N)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let archive = input_line.trim().eq_ignore_ascii_case("y");
+
+        Ok(HashMap::from([
+            ("path".to_string(), path),
+            ("archive".to_string(), archive.to_string()),
+        ]))
+    }
+
+    fn add_taskwarrior(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a path to the task binary (leave empty to use task from PATH)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let command = input_line.trim().to_string();
+
+        print!("Please, provide a path to the data directory (leave empty to use the one from .taskrc)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        let data_location = input_line.trim().to_string();
+
+        let mut result = HashMap::new();
+        if !command.is_empty() {
+            result.insert("command".to_string(), command);
+        }
+        if !data_location.is_empty() {
+            result.insert("data_location".to_string(), data_location);
+        }
+
+        Ok(result)
+    }
+
+    fn add_external(&self) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        print!("Please, provide a command to start the provider process (aka /usr/local/bin/my-tracker --json)> ");
+        let _ = io::stdout().flush();
+
+        let mut input_line = String::new();
+
+        io::stdin().read_line(&mut input_line).expect("Failed to read line");
+        input_line = input_line.trim().to_string();
+
+        if input_line.is_empty() {
+            return Err(Box::<dyn std::error::Error>::from("The command shouldn't be empty"));
+        }
+
+        Ok(HashMap::from([("command".to_string(), input_line)]))
+    }
+
     fn get_provider_name(&self) -> Result<String, Box<dyn std::error::Error>> {
         print!("Please, provide the new provider's unique name> ");
         let _ = io::stdout().flush();